@@ -33,7 +33,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         .and_then(|g| g.get(IppAttribute::OPERATIONS_SUPPORTED))
         .ok_or(IppError::MissingAttribute)?;
 
-    if !ops_attr.value().into_iter().any(supports_multi_doc) {
+    if !ops_attr.values().iter().any(supports_multi_doc) {
         println!("ERROR: target printer does not support create/send operations");
         exit(2);
     }
@@ -0,0 +1,308 @@
+//!
+//! Derive macros for the `ipp` crate.
+//!
+//! * `#[derive(IppAttributes)]` maps a struct to an attribute group: it implements
+//!   `ipp::convert::FromAttributeGroup` and `ipp::convert::ToAttributeGroup`.
+//! * `#[derive(IppCollection)]` maps a struct to a collection value such as `media-col`: it implements
+//!   `ipp::convert::FromIppValue` and `ipp::convert::ToIppValue`.
+//!
+//! Field attributes:
+//! * `#[ipp(name = "media-size-name")]` - IPP name, defaults to the field name with underscores replaced by dashes.
+//! * `#[ipp(syntax = "name")]` - value syntax: `keyword`, `name`, `text`, `enum`, `integer`, `boolean`, `uri`,
+//!   `uri-scheme`, `charset`, `natural-language` or `mime-media-type`. String fields default to `keyword`.
+//!   The syntax is checked against the field type at compile time: string syntaxes require `String` fields,
+//!   `enum` and `integer` require `i32` and `boolean` requires `bool`, optionally wrapped in `Option` and `Vec`.
+//!
+//! `Option<T>` fields are optional: missing attributes are mapped to `None` and `None` values are not serialized.
+//! Missing required attributes produce `IppError::MissingNamedAttribute`, values of a wrong type or syntax
+//! produce `IppError::InvalidNamedAttributeType`, both with the name of the attribute or collection member.
+//!
+//!```rust,ignore
+//! use ipp::{IppAttributes, IppCollection, model::PrinterState};
+//!
+//! #[derive(IppCollection)]
+//! struct MediaCol {
+//!     media_size_name: String,
+//!     #[ipp(syntax = "name")]
+//!     media_source: Option<String>,
+//! }
+//!
+//! #[derive(IppAttributes)]
+//! struct Printer {
+//!     #[ipp(syntax = "name")]
+//!     printer_name: String,
+//!     printer_state: PrinterState,
+//!     media_col_default: Option<MediaCol>,
+//! }
+//!```
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, GenericArgument, LitStr, PathArguments, Type, parse_macro_input};
+
+/// Derive conversions between a struct and an IPP attribute group
+#[proc_macro_derive(IppAttributes, attributes(ipp))]
+pub fn derive_ipp_attributes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_attributes(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive conversions between a struct and an IPP collection value
+#[proc_macro_derive(IppCollection, attributes(ipp))]
+pub fn derive_ipp_collection(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_collection(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct FieldInfo {
+    ident: syn::Ident,
+    name: String,
+    syntax: TokenStream2,
+    optional: Option<Type>,
+}
+
+// parse the syntax name and check that the field type can be converted into it
+fn parse_syntax(lit: &LitStr, ty: &Type) -> syn::Result<TokenStream2> {
+    let (syntax, expected) = match lit.value().as_str() {
+        "keyword" => (quote!(Keyword), "String"),
+        "name" => (quote!(Name(255)), "String"),
+        "text" => (quote!(Text(1023)), "String"),
+        "enum" => (quote!(Enum), "i32"),
+        "integer" => (
+            quote!(Integer {
+                min: i32::MIN,
+                max: i32::MAX
+            }),
+            "i32",
+        ),
+        "boolean" => (quote!(Boolean), "bool"),
+        "uri" => (quote!(Uri), "String"),
+        "uri-scheme" => (quote!(UriScheme), "String"),
+        "charset" => (quote!(Charset), "String"),
+        "natural-language" => (quote!(NaturalLanguage), "String"),
+        "mime-media-type" => (quote!(MimeMediaType), "String"),
+        other => return Err(syn::Error::new(lit.span(), format!("unsupported IPP syntax: {other}"))),
+    };
+    if base_type(ty).is_none_or(|ident| ident != expected) {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!("IPP syntax `{}` requires a field of type `{expected}`", lit.value()),
+        ));
+    }
+    Ok(quote!(::core::option::Option::Some(::ipp::registry::Syntax::#syntax)))
+}
+
+// return the type name inside of Option and Vec wrappers
+fn base_type(ty: &Type) -> Option<&syn::Ident> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    match (&segment.arguments, segment.ident.to_string().as_str()) {
+        (PathArguments::AngleBracketed(args), "Option" | "Vec") => match args.args.first()? {
+            GenericArgument::Type(inner) => base_type(inner),
+            _ => None,
+        },
+        (PathArguments::None, _) => Some(&segment.ident),
+        _ => None,
+    }
+}
+
+// return the inner type of Option<T>
+fn option_inner(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(inner) => Some(inner.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn parse_fields(input: &DeriveInput) -> syn::Result<Vec<FieldInfo>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(input, "only structs are supported"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "only structs with named fields are supported",
+        ));
+    };
+
+    fields
+        .named
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().expect("named field");
+            let mut name = ident.to_string().trim_start_matches("r#").replace('_', "-");
+            let mut syntax = quote!(::core::option::Option::None);
+
+            for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("ipp")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        name = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else if meta.path.is_ident("syntax") {
+                        syntax = parse_syntax(&meta.value()?.parse::<LitStr>()?, &field.ty)?;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported ipp attribute, expected `name` or `syntax`"))
+                    }
+                })?;
+            }
+
+            Ok(FieldInfo {
+                ident,
+                name,
+                syntax,
+                optional: option_inner(&field.ty),
+            })
+        })
+        .collect()
+}
+
+// field initializers reading from `Option<&IppValue>` lookups
+fn from_fields(fields: &[FieldInfo], lookup: impl Fn(&str) -> TokenStream2) -> Vec<TokenStream2> {
+    fields
+        .iter()
+        .map(|field| {
+            let FieldInfo {
+                ident, name, syntax, ..
+            } = field;
+            let value = lookup(name);
+            match &field.optional {
+                Some(inner) => {
+                    quote!(#ident: ::ipp::convert::__private::optional::<#inner>(#name, #value, #syntax)?)
+                }
+                None => quote!(#ident: ::ipp::convert::__private::required(#name, #value, #syntax)?),
+            }
+        })
+        .collect()
+}
+
+// statements serializing the fields, `emit` produces a statement for a given value reference
+fn to_fields(
+    fields: &[FieldInfo],
+    emit: impl Fn(&str, TokenStream2, &TokenStream2) -> TokenStream2,
+) -> Vec<TokenStream2> {
+    fields
+        .iter()
+        .map(|field| {
+            let FieldInfo {
+                ident, name, syntax, ..
+            } = field;
+            if field.optional.is_some() {
+                let stmt = emit(name, quote!(value), syntax);
+                quote!(if let ::core::option::Option::Some(value) = &self.#ident { #stmt })
+            } else {
+                emit(name, quote!(&self.#ident), syntax)
+            }
+        })
+        .collect()
+}
+
+fn expand_attributes(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = parse_fields(input)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let from = from_fields(&fields, |name| quote!(group.get(#name).map(|attr| attr.value())));
+    let to = to_fields(
+        &fields,
+        |name, value, syntax| quote!(attributes.push(::ipp::convert::__private::attribute(#name, #value, #syntax)?);),
+    );
+
+    Ok(quote! {
+        impl #impl_generics ::ipp::convert::FromAttributeGroup for #ident #ty_generics #where_clause {
+            fn from_attribute_group(
+                group: &::ipp::attribute::IppAttributeGroup,
+            ) -> ::core::result::Result<Self, ::ipp::error::IppError> {
+                ::core::result::Result::Ok(Self { #(#from,)* })
+            }
+        }
+
+        impl #impl_generics ::ipp::convert::ToAttributeGroup for #ident #ty_generics #where_clause {
+            fn to_attributes(
+                &self,
+            ) -> ::core::result::Result<::std::vec::Vec<::ipp::attribute::IppAttribute>, ::ipp::error::IppError> {
+                let mut attributes = ::std::vec::Vec::new();
+                #(#to)*
+                ::core::result::Result::Ok(attributes)
+            }
+        }
+    })
+}
+
+fn expand_collection(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = parse_fields(input)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let from = from_fields(&fields, |name| quote!(collection.get(#name)));
+    let to = to_fields(
+        &fields,
+        |name, value, syntax| quote!(::ipp::convert::__private::member(&mut collection, #name, #value, #syntax)?;),
+    );
+
+    Ok(quote! {
+        impl #impl_generics ::ipp::convert::FromIppValue for #ident #ty_generics #where_clause {
+            fn from_ipp_value(
+                value: &::ipp::value::IppValue,
+            ) -> ::core::result::Result<Self, ::ipp::error::IppError> {
+                let collection = ::ipp::convert::__private::collection(value)?;
+                ::core::result::Result::Ok(Self { #(#from,)* })
+            }
+        }
+
+        impl #impl_generics ::ipp::convert::ToIppValue for #ident #ty_generics #where_clause {
+            fn to_ipp_value(&self) -> ::core::result::Result<::ipp::value::IppValue, ::ipp::error::IppError> {
+                let mut collection = ::ipp::value::IppCollection::new();
+                #(#to)*
+                ::core::result::Result::Ok(::ipp::value::IppValue::Collection(collection))
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syntax_field_type() {
+        let input: DeriveInput = syn::parse_quote! {
+            struct Job {
+                #[ipp(syntax = "name")]
+                job_name: Option<String>,
+                #[ipp(syntax = "enum")]
+                finishings: Vec<i32>,
+            }
+        };
+        assert!(expand_attributes(&input).is_ok());
+
+        let input: DeriveInput = syn::parse_quote! {
+            struct Job {
+                #[ipp(syntax = "name")]
+                media_col: Option<MediaCol>,
+            }
+        };
+        let err = expand_collection(&input).err().unwrap();
+        assert_eq!(err.to_string(), "IPP syntax `name` requires a field of type `String`");
+
+        let input: DeriveInput = syn::parse_quote! {
+            struct Job {
+                #[ipp(syntax = "integer")]
+                copies: String,
+            }
+        };
+        assert!(expand_attributes(&input).is_err());
+    }
+}
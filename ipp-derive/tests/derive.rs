@@ -0,0 +1,108 @@
+use ipp::{
+    IppAttributes, IppCollection,
+    attribute::{IppAttribute, IppAttributeGroup},
+    convert::{FromAttributeGroup, FromIppValue, ToAttributeGroup, ToIppValue},
+    error::IppError,
+    model::{DelimiterTag, PrinterState},
+    value::{IppCollection, IppValue},
+};
+
+#[derive(Debug, PartialEq, IppCollection)]
+struct MediaCol {
+    media_size_name: String,
+    #[ipp(syntax = "name")]
+    media_source: Option<String>,
+}
+
+#[derive(Debug, PartialEq, IppAttributes)]
+struct Printer {
+    #[ipp(syntax = "name")]
+    printer_name: String,
+    printer_state: PrinterState,
+    #[ipp(name = "printer-state-reasons")]
+    reasons: Vec<String>,
+    #[ipp(syntax = "text")]
+    printer_info: Option<String>,
+    media_col_default: Option<MediaCol>,
+}
+
+fn printer() -> Printer {
+    Printer {
+        printer_name: "office".to_owned(),
+        printer_state: PrinterState::Idle,
+        reasons: vec!["none".to_owned()],
+        printer_info: None,
+        media_col_default: Some(MediaCol {
+            media_size_name: "iso_a4_210x297".to_owned(),
+            media_source: Some("Manual Feed".to_owned()),
+        }),
+    }
+}
+
+#[test]
+fn test_attributes_round_trip() {
+    let group = printer().to_attribute_group(DelimiterTag::PrinterAttributes).unwrap();
+    assert_eq!(group.attributes().len(), 4);
+    assert_eq!(
+        group.get("printer-name").unwrap().value(),
+        &IppValue::new_name_without_language("office").unwrap()
+    );
+    assert_eq!(group.get("printer-state").unwrap().value(), &IppValue::Enum(3));
+    assert!(group.get("printer-info").is_none());
+
+    assert_eq!(Printer::from_attribute_group(&group).unwrap(), printer());
+}
+
+#[test]
+fn test_collection_round_trip() {
+    let media = MediaCol {
+        media_size_name: "na_letter_8.5x11in".to_owned(),
+        media_source: None,
+    };
+    let value = media.to_ipp_value().unwrap();
+    assert_eq!(
+        value,
+        IppValue::Collection(IppCollection::from([(
+            "media-size-name".try_into().unwrap(),
+            IppValue::new_keyword("na_letter_8.5x11in").unwrap()
+        )]))
+    );
+    assert_eq!(MediaCol::from_ipp_value(&value).unwrap(), media);
+}
+
+#[test]
+fn test_missing_attribute() {
+    let mut group = printer().to_attribute_group(DelimiterTag::PrinterAttributes).unwrap();
+    group
+        .attributes_mut()
+        .retain(|attr| attr.name().as_str() != "printer-state");
+
+    assert!(matches!(
+        Printer::from_attribute_group(&group),
+        Err(IppError::MissingNamedAttribute(name)) if name == "printer-state"
+    ));
+
+    // the name of a missing collection member is reported
+    let value = IppValue::Collection(IppCollection::from([(
+        "media-source".try_into().unwrap(),
+        IppValue::new_name_without_language("Manual Feed").unwrap(),
+    )]));
+    assert!(matches!(
+        MediaCol::from_ipp_value(&value),
+        Err(IppError::MissingNamedAttribute(name)) if name == "media-size-name"
+    ));
+}
+
+#[test]
+fn test_mistyped_attribute() {
+    let mut group = IppAttributeGroup::new(DelimiterTag::PrinterAttributes);
+    group.attributes_mut().extend(printer().to_attributes().unwrap());
+    // keyword instead of name
+    group.attributes_mut()[0] =
+        IppAttribute::with_name("printer-name", IppValue::new_keyword("office").unwrap()).unwrap();
+
+    assert!(matches!(
+        Printer::from_attribute_group(&group),
+        Err(IppError::InvalidNamedAttributeType(name)) if name == "printer-name"
+    ));
+}
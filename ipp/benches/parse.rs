@@ -0,0 +1,63 @@
+//!
+//! Compare the streaming parser with the in-memory `IppBytesParser`.
+//!
+//! The `strings` message consists of short name and keyword values which both parsers copy into owned strings,
+//! the `binary` message consists of large octetString values and a payload which `IppBytesParser` slices
+//! from the input buffer instead of copying.
+//!
+use std::io::Cursor;
+
+use bytes::Bytes;
+use criterion::{Criterion, criterion_group, criterion_main};
+use ipp::{
+    attribute::IppAttribute,
+    model::{DelimiterTag, IppVersion, StatusCode},
+    parser::{IppBytesParser, IppParser},
+    reader::IppReader,
+    request::IppRequestResponse,
+    value::IppValue,
+};
+
+fn message(value: impl Fn(usize) -> IppValue) -> Bytes {
+    let mut response = IppRequestResponse::new_response(IppVersion::v1_1(), StatusCode::SuccessfulOk, 1).unwrap();
+    for i in 0..1000 {
+        response.attributes_mut().add(
+            DelimiterTag::JobAttributes,
+            IppAttribute::with_name(format!("attribute-{i}"), value(i)).unwrap(),
+        );
+    }
+    let mut data = response.to_bytes().to_vec();
+    data.extend(vec![0u8; 1 << 20]);
+    data.into()
+}
+
+fn bench_parsers(c: &mut Criterion) {
+    let messages = [
+        (
+            "strings",
+            message(|i| IppValue::new_name_without_language(format!("job name {i}")).unwrap()),
+        ),
+        (
+            "binary",
+            message(|i| IppValue::OctetString(Bytes::from(vec![i as u8; 4096]))),
+        ),
+    ];
+
+    for (name, data) in messages {
+        let mut group = c.benchmark_group(name);
+        group.bench_function("IppParser", |b| {
+            b.iter(|| {
+                IppParser::new(IppReader::new(Cursor::new(data.clone())))
+                    .parse()
+                    .unwrap()
+            })
+        });
+        group.bench_function("IppBytesParser", |b| {
+            b.iter(|| IppBytesParser::new(data.clone()).parse().unwrap())
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_parsers);
+criterion_main!(benches);
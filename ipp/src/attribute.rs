@@ -2,22 +2,31 @@
 //! Attribute-related structs
 //!
 
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, fmt};
 
 use bytes::{BufMut, Bytes, BytesMut};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    convert::FromIppValue,
+    error::IppError,
     model::DelimiterTag,
     parser::IppParseError,
-    value::{IppDateTime, IppName, IppValue},
+    registry::{self, AttributeInfo},
+    value::{IppCollection, IppDateTime, IppName, IppValue},
 };
 
 macro_rules! define_attributes {
     ($($name:ident => $value:literal),* $(,)?) => {
         $(pub const $name: &'static str = $value;)*
     };
+    // also collect the names into a list, used to check that the registry covers them
+    ($list:ident: $($name:ident => $value:literal),* $(,)?) => {
+        define_attributes!($($name => $value),*);
+        #[cfg(test)]
+        const $list: &'static [&'static str] = &[$($value),*];
+    };
 }
 
 fn is_header_attr(attr: &str) -> bool {
@@ -26,7 +35,7 @@ fn is_header_attr(attr: &str) -> bool {
 
 /// `IppAttribute` represents an IPP attribute
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct IppAttribute {
     /// Attribute name
     name: IppName,
@@ -37,6 +46,7 @@ pub struct IppAttribute {
 impl IppAttribute {
     // Attributes defined in RFC 8011
     define_attributes! {
+        RFC_8011_ATTRIBUTES:
         ATTRIBUTES_CHARSET => "attributes-charset",
         ATTRIBUTES_NATURAL_LANGUAGE => "attributes-natural-language",
         CHARSET_CONFIGURED => "charset-configured",
@@ -165,6 +175,58 @@ impl IppAttribute {
         WHICH_JOBS => "which-jobs"
     }
 
+    // Attributes defined in the PWG 5100.x specifications
+    define_attributes! {
+        PWG_5100_ATTRIBUTES:
+        DOCUMENT_PASSWORD => "document-password",
+        FINISHINGS_COL => "finishings-col",
+        FINISHINGS_COL_DATABASE => "finishings-col-database",
+        FINISHINGS_COL_DEFAULT => "finishings-col-default",
+        FINISHINGS_COL_READY => "finishings-col-ready",
+        FINISHINGS_COL_SUPPORTED => "finishings-col-supported",
+        FIRST_INDEX => "first-index",
+        IPP_FEATURES_SUPPORTED => "ipp-features-supported",
+        JOB_ACCOUNT_ID => "job-account-id",
+        JOB_ACCOUNTING_USER_ID => "job-accounting-user-id",
+        JOB_IDS => "job-ids",
+        JOB_PASSWORD => "job-password",
+        JOB_PASSWORD_ENCRYPTION => "job-password-encryption",
+        JOB_UUID => "job-uuid",
+        MEDIA_COL => "media-col",
+        MEDIA_COL_DATABASE => "media-col-database",
+        MEDIA_COL_DEFAULT => "media-col-default",
+        MEDIA_COL_READY => "media-col-ready",
+        MEDIA_COL_SUPPORTED => "media-col-supported",
+        MEDIA_SIZE_SUPPORTED => "media-size-supported",
+        MEDIA_SOURCE_SUPPORTED => "media-source-supported",
+        MEDIA_TYPE_SUPPORTED => "media-type-supported",
+        OUTPUT_BIN => "output-bin",
+        OUTPUT_BIN_DEFAULT => "output-bin-default",
+        OUTPUT_BIN_SUPPORTED => "output-bin-supported",
+        PRINT_COLOR_MODE => "print-color-mode",
+        PRINT_COLOR_MODE_DEFAULT => "print-color-mode-default",
+        PRINT_COLOR_MODE_SUPPORTED => "print-color-mode-supported",
+        PRINT_CONTENT_OPTIMIZE => "print-content-optimize",
+        PRINT_CONTENT_OPTIMIZE_DEFAULT => "print-content-optimize-default",
+        PRINT_CONTENT_OPTIMIZE_SUPPORTED => "print-content-optimize-supported",
+        PRINT_SCALING => "print-scaling",
+        PRINT_SCALING_DEFAULT => "print-scaling-default",
+        PRINT_SCALING_SUPPORTED => "print-scaling-supported",
+        PRINTER_ALERT => "printer-alert",
+        PRINTER_ALERT_DESCRIPTION => "printer-alert-description",
+        PRINTER_DEVICE_ID => "printer-device-id",
+        PRINTER_ICONS => "printer-icons",
+        PRINTER_STATE_CHANGE_TIME => "printer-state-change-time",
+        PRINTER_SUPPLY => "printer-supply",
+        PRINTER_SUPPLY_DESCRIPTION => "printer-supply-description",
+        PRINTER_UUID => "printer-uuid",
+        PWG_RASTER_DOCUMENT_RESOLUTION_SUPPORTED => "pwg-raster-document-resolution-supported",
+        PWG_RASTER_DOCUMENT_SHEET_BACK => "pwg-raster-document-sheet-back",
+        PWG_RASTER_DOCUMENT_TYPE_SUPPORTED => "pwg-raster-document-type-supported",
+        REQUESTING_USER_URI => "requesting-user-uri",
+        WHICH_JOBS_SUPPORTED => "which-jobs-supported",
+    }
+
     // Special attribute groups defined in 4.2.5 and 4.3.4 of RFC 8011
     // can be used in "get-printer-attributes" or "get-job-attributes" operations to obtain several
     // attributes at once
@@ -222,15 +284,36 @@ impl IppAttribute {
     }
 
     /// Return the attribute value
+    ///
+    /// Parsed attributes and collection members registered as 1setOf in the [`registry`](crate::registry)
+    /// are always an [`IppValue::Array`], even with a single value. The wire format does not distinguish
+    /// a single value from a one-element 1setOf, so a single value of an unregistered attribute is a scalar;
+    /// use [`values`](Self::values) to handle both shapes.
     pub fn value(&self) -> &IppValue {
         &self.value
     }
 
+    /// Return the attribute values as a slice: all values of a 1setOf attribute or a single value otherwise
+    pub fn values(&self) -> &[IppValue] {
+        match self.value {
+            IppValue::Array(ref values) => values,
+            ref value => std::slice::from_ref(value),
+        }
+    }
+
     /// Consume this attribute and return the value
     pub fn into_value(self) -> IppValue {
         self.value
     }
 
+    /// Return a displayable value where registered enum values are rendered symbolically, e.g. `idle(3)`
+    pub fn display_value(&self) -> impl fmt::Display + '_ {
+        SymbolicValue {
+            info: registry::lookup(&self.name),
+            value: &self.value,
+        }
+    }
+
     /// Write the attribute to a byte array
     pub fn to_bytes(&self) -> Bytes {
         let mut buffer = BytesMut::new();
@@ -243,9 +326,45 @@ impl IppAttribute {
     }
 }
 
+impl fmt::Display for IppAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.display_value())
+    }
+}
+
+struct SymbolicValue<'a> {
+    info: Option<&'static AttributeInfo>,
+    value: &'a IppValue,
+}
+
+impl fmt::Display for SymbolicValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.info, self.value) {
+            (Some(info), IppValue::Enum(value)) => match info.enum_name(*value) {
+                Some(name) => write!(f, "{name}({value})"),
+                None => write!(f, "{value}"),
+            },
+            (Some(info), IppValue::Array(array)) => {
+                let s: Vec<String> = array
+                    .iter()
+                    .map(|value| {
+                        SymbolicValue {
+                            info: Some(info),
+                            value,
+                        }
+                        .to_string()
+                    })
+                    .collect();
+                write!(f, "[{}]", s.join(", "))
+            }
+            (_, value) => value.fmt(f),
+        }
+    }
+}
+
 /// Attribute group
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct IppAttributeGroup {
     tag: DelimiterTag,
     attributes: Vec<IppAttribute>,
@@ -283,6 +402,15 @@ impl IppAttributeGroup {
     pub fn get(&self, name: &str) -> Option<&IppAttribute> {
         self.attributes.iter().find(|attr| attr.name().as_str() == name)
     }
+
+    /// Return the value of a given attribute converted into a Rust type.
+    /// Returns `IppError::MissingAttribute` if the attribute is absent
+    /// and `IppError::InvalidAttributeType` if the value cannot be converted.
+    pub fn get_as<T: FromIppValue>(&self, name: &str) -> Result<T, IppError> {
+        self.get(name)
+            .ok_or(IppError::MissingAttribute)
+            .and_then(|attr| T::from_ipp_value(attr.value()))
+    }
 }
 
 impl IntoIterator for IppAttributeGroup {
@@ -314,7 +442,7 @@ impl<'a> IntoIterator for &'a mut IppAttributeGroup {
 
 /// Attribute list
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct IppAttributes {
     groups: Vec<IppAttributeGroup>,
 }
@@ -464,18 +592,30 @@ impl IppAttrWithName for Vec<IppValue> {
     }
 }
 
-impl IppAttrWithName for BTreeMap<IppName, IppValue> {
+impl IppAttrWithName for IppCollection {
     fn with_name<S: Into<String>>(self, name: S) -> Result<IppAttribute, IppParseError> {
         IppValue::Collection(self).with_name(name)
     }
 }
 
+impl IppAttrWithName for BTreeMap<IppName, IppValue> {
+    fn with_name<S: Into<String>>(self, name: S) -> Result<IppAttribute, IppParseError> {
+        IppValue::Collection(self.into()).with_name(name)
+    }
+}
+
 impl IppAttrWithName for IppDateTime {
     fn with_name<S: Into<String>>(self, name: S) -> Result<IppAttribute, IppParseError> {
         IppValue::DateTime(self).with_name(name)
     }
 }
 
+impl IppAttrWithName for std::time::SystemTime {
+    fn with_name<S: Into<String>>(self, name: S) -> Result<IppAttribute, IppParseError> {
+        IppDateTime::from(self).with_name(name)
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl<Tz: chrono::TimeZone> IppAttrWithName for chrono::DateTime<Tz> {
     fn with_name<S: Into<String>>(self, name: S) -> Result<IppAttribute, IppParseError> {
@@ -496,3 +636,37 @@ impl IppAttrWithName for &jiff::Zoned {
         IppDateTime::from(self).with_name(name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attributes_are_registered() {
+        for name in IppAttribute::RFC_8011_ATTRIBUTES
+            .iter()
+            .chain(IppAttribute::PWG_5100_ATTRIBUTES)
+        {
+            assert!(registry::lookup(name).is_some(), "{name} is not registered");
+        }
+    }
+
+    #[test]
+    fn test_symbolic_display() {
+        let attr = IppAttribute::with_name(IppAttribute::PRINTER_STATE, IppValue::Enum(3)).unwrap();
+        assert_eq!(attr.to_string(), "printer-state: idle(3)");
+
+        let attr = IppAttribute::with_name(
+            IppAttribute::OPERATIONS_SUPPORTED,
+            IppValue::Array(vec![IppValue::Enum(0x02), IppValue::Enum(0x0b), IppValue::Enum(0x7777)]),
+        )
+        .unwrap();
+        assert_eq!(
+            attr.to_string(),
+            "operations-supported: [Print-Job(2), Get-Printer-Attributes(11), 30583]"
+        );
+
+        let attr = IppAttribute::with_name(IppAttribute::COPIES, IppValue::Integer(2)).unwrap();
+        assert_eq!(attr.to_string(), "copies: 2");
+    }
+}
@@ -0,0 +1,140 @@
+//!
+//! Character sets for text and name values
+//!
+//! IPP messages declare the charset of all text and name values in the `attributes-charset`
+//! operation attribute. The parser decodes text and name values using the declared charset,
+//! [`IppRequestResponse::encode_charset`](crate::request::IppRequestResponse::encode_charset)
+//! encodes an outgoing message in a charset requested by the peer.
+//!
+use std::{fmt, str::FromStr};
+
+use crate::parser::IppParseError;
+
+/// Supported charset of text and name values
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Charset {
+    /// utf-8, the default IPP charset
+    #[default]
+    Utf8,
+    /// us-ascii
+    UsAscii,
+    /// iso-8859-1
+    Iso8859_1,
+    /// windows-1252
+    Windows1252,
+    /// shift_jis
+    ShiftJis,
+}
+
+impl Charset {
+    /// Return the IANA charset name as used in `attributes-charset`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Charset::Utf8 => "utf-8",
+            Charset::UsAscii => "us-ascii",
+            Charset::Iso8859_1 => "iso-8859-1",
+            Charset::Windows1252 => "windows-1252",
+            Charset::ShiftJis => "shift_jis",
+        }
+    }
+
+    /// Find a charset by its IANA name or a common alias, case-insensitively
+    pub fn from_name(name: &str) -> Option<Charset> {
+        let charset = match name.to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => Charset::Utf8,
+            "us-ascii" | "ascii" | "ansi_x3.4-1968" => Charset::UsAscii,
+            "iso-8859-1" | "iso_8859-1" | "latin1" | "l1" => Charset::Iso8859_1,
+            "windows-1252" | "cp1252" => Charset::Windows1252,
+            "shift_jis" | "shift-jis" | "sjis" | "ms_kanji" => Charset::ShiftJis,
+            _ => return None,
+        };
+        Some(charset)
+    }
+
+    /// Decode bytes in this charset into a string
+    pub fn decode(&self, data: &[u8]) -> Result<String, IppParseError> {
+        match self {
+            Charset::Utf8 => str::from_utf8(data).map(ToOwned::to_owned).ok(),
+            Charset::UsAscii => data.is_ascii().then(|| data.iter().map(|&b| b as char).collect()),
+            Charset::Iso8859_1 => Some(data.iter().map(|&b| b as char).collect()),
+            Charset::Windows1252 => encoding_rs::WINDOWS_1252
+                .decode_without_bom_handling_and_without_replacement(data)
+                .map(|s| s.into_owned()),
+            Charset::ShiftJis => encoding_rs::SHIFT_JIS
+                .decode_without_bom_handling_and_without_replacement(data)
+                .map(|s| s.into_owned()),
+        }
+        .ok_or(IppParseError::InvalidCharsetData(self.name()))
+    }
+
+    /// Encode a string in this charset, failing if it contains unmappable characters
+    pub fn encode(&self, s: &str) -> Result<Vec<u8>, IppParseError> {
+        match self {
+            Charset::Utf8 => Some(s.as_bytes().to_vec()),
+            Charset::UsAscii => s.is_ascii().then(|| s.as_bytes().to_vec()),
+            Charset::Iso8859_1 => s.chars().map(|c| u8::try_from(c).ok()).collect(),
+            Charset::Windows1252 => encode_with(encoding_rs::WINDOWS_1252, s),
+            Charset::ShiftJis => encode_with(encoding_rs::SHIFT_JIS, s),
+        }
+        .ok_or(IppParseError::InvalidCharsetData(self.name()))
+    }
+}
+
+fn encode_with(encoding: &'static encoding_rs::Encoding, s: &str) -> Option<Vec<u8>> {
+    let (data, _, had_errors) = encoding.encode(s);
+    (!had_errors).then(|| data.into_owned())
+}
+
+impl FromStr for Charset {
+    type Err = IppParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Charset::from_name(s).ok_or_else(|| IppParseError::UnknownCharset(s.to_owned()))
+    }
+}
+
+impl fmt::Display for Charset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!("ISO-8859-1".parse::<Charset>().unwrap(), Charset::Iso8859_1);
+        assert_eq!(Charset::from_name("Shift_JIS"), Some(Charset::ShiftJis));
+        assert!(Charset::from_name("koi8-r").is_none());
+        assert!(matches!(
+            "koi8-r".parse::<Charset>(),
+            Err(IppParseError::UnknownCharset(name)) if name == "koi8-r"
+        ));
+        assert_eq!(Charset::Windows1252.to_string(), "windows-1252");
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(Charset::Iso8859_1.decode(b"Gr\xfc\xdfe").unwrap(), "Grüße");
+        assert_eq!(Charset::Windows1252.decode(b"\x80 5").unwrap(), "€ 5");
+        assert_eq!(Charset::ShiftJis.decode(b"\x93\xfa\x96\x7b").unwrap(), "日本");
+        assert_eq!(Charset::UsAscii.decode(b"abc").unwrap(), "abc");
+        assert!(Charset::UsAscii.decode(b"\xfc").is_err());
+        assert!(Charset::Utf8.decode(b"\xfc").is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(Charset::Iso8859_1.encode("Grüße").unwrap(), b"Gr\xfc\xdfe");
+        assert_eq!(Charset::Windows1252.encode("€ 5").unwrap(), b"\x80 5");
+        assert_eq!(Charset::ShiftJis.encode("日本").unwrap(), b"\x93\xfa\x96\x7b");
+        assert!(Charset::Iso8859_1.encode("€").is_err());
+        assert!(matches!(
+            Charset::UsAscii.encode("ü"),
+            Err(IppParseError::InvalidCharsetData("us-ascii"))
+        ));
+    }
+}
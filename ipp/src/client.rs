@@ -1,11 +1,29 @@
 //!
 //! IPP client
 //!
-use std::{collections::BTreeMap, marker::PhantomData, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    marker::PhantomData,
+    sync::{
+        Mutex,
+        atomic::{AtomicI32, Ordering},
+    },
+    time::Duration,
+};
 
 use base64::Engine;
 use http::Uri;
 
+use crate::{
+    attribute::IppAttribute,
+    error::IppError,
+    model::{DelimiterTag, IppVersion, Operation, StatusCode},
+    parser::{ParseMode, ParserLimits},
+    payload::IppPayload,
+    request::IppRequestResponse,
+    value::{IppLanguage, IppName, IppValue},
+};
+
 const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 
 fn ipp_uri_to_string(uri: &Uri) -> String {
@@ -31,6 +49,171 @@ fn ipp_uri_to_string(uri: &Uri) -> String {
     format!("{scheme}://{authority}{path_and_query}")
 }
 
+/// Generator of monotonically increasing request ids.
+///
+/// RFC 8010 requires the request-id to be in the range 1..2^31-1, so the counter wraps around to 1.
+struct RequestIdGenerator(AtomicI32);
+
+impl RequestIdGenerator {
+    fn new() -> Self {
+        RequestIdGenerator(AtomicI32::new(1))
+    }
+
+    fn next_id(&self) -> i32 {
+        // the closure always returns Some, so fetch_update never fails
+        self.0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| {
+                Some(if id == i32::MAX { 1 } else { id + 1 })
+            })
+            .unwrap_or(1)
+    }
+}
+
+/// Assign the next request id to the request and return it
+fn assign_request_id(ids: &RequestIdGenerator, request: &mut IppRequestResponse) -> i32 {
+    let request_id = ids.next_id();
+    request.header_mut().request_id = request_id;
+    request_id
+}
+
+/// Make sure that the response corresponds to the request with a given id
+fn check_request_id(request_id: i32, response: IppRequestResponse) -> Result<IppRequestResponse, IppError> {
+    if response.header().request_id == request_id {
+        Ok(response)
+    } else {
+        Err(IppError::RequestIdMismatch {
+            expected: request_id,
+            actual: response.header().request_id,
+        })
+    }
+}
+
+fn operation_attribute(request: &IppRequestResponse, name: &str) -> Option<IppValue> {
+    request
+        .attributes()
+        .first_of(DelimiterTag::OperationAttributes)
+        .and_then(|group| group.get(name))
+        .map(|attr| attr.value().clone())
+}
+
+/// Apply the client-wide natural language and user name to the request.
+/// The natural language is only set if the request has none or the one assigned by the operation,
+/// the user name is only set if the operation does not specify one.
+fn apply_defaults<T>(config: &IppClientBuilder<T>, request: &mut IppRequestResponse) {
+    if let Some(ref language) = config.natural_language {
+        let is_default = match operation_attribute(request, IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE) {
+            Some(value) => value
+                .as_natural_language()
+                .is_some_and(|language| request.default_language.as_ref() == Some(language)),
+            None => true,
+        };
+        if is_default {
+            request.set_natural_language(language.clone());
+        }
+    }
+
+    if let Some(ref user_name) = config.user_name
+        && operation_attribute(request, IppAttribute::REQUESTING_USER_NAME).is_none()
+    {
+        request.set_user_name(user_name.clone());
+    }
+}
+
+/// Per-printer cache of IPP versions negotiated with the server
+#[derive(Default)]
+struct VersionCache(Mutex<HashMap<String, IppVersion>>);
+
+impl VersionCache {
+    fn get(&self, printer: &str) -> Option<IppVersion> {
+        self.0.lock().ok().and_then(|versions| versions.get(printer).copied())
+    }
+
+    fn set(&self, printer: String, version: IppVersion) {
+        if let Ok(mut versions) = self.0.lock() {
+            versions.insert(printer, version);
+        }
+    }
+}
+
+/// Return the key identifying the target printer of the request: printer-uri attribute or the client URI
+fn printer_key(uri: &Uri, request: &IppRequestResponse) -> String {
+    request
+        .attributes()
+        .first_of(DelimiterTag::OperationAttributes)
+        .and_then(|group| group.get(IppAttribute::PRINTER_URI))
+        .and_then(|attr| attr.value().as_uri())
+        .map(|uri| uri.to_string())
+        .unwrap_or_else(|| uri.to_string())
+}
+
+/// Return the version to use instead of the request version. The negotiated or configured version
+/// only replaces the default version of the operations, a version set explicitly by the caller is kept.
+fn preferred_version(
+    request: &IppRequestResponse,
+    negotiated: Option<IppVersion>,
+    configured: Option<IppVersion>,
+) -> Option<IppVersion> {
+    if request.is_default_version() {
+        negotiated.or(configured)
+    } else {
+        None
+    }
+}
+
+/// Return true if the version of the request must be negotiated before sending it.
+/// Requests with a payload cannot be resent with a lower version.
+fn needs_negotiation(request: &IppRequestResponse, negotiated: Option<IppVersion>) -> bool {
+    negotiated.is_none() && request.is_default_version() && !request.payload().is_empty()
+}
+
+/// Make a copy of the request which can be resent. Requests with a payload cannot be replayed.
+fn replayable_copy(request: &IppRequestResponse) -> Option<IppRequestResponse> {
+    request.payload().is_empty().then(|| IppRequestResponse {
+        header: request.header,
+        attributes: request.attributes.clone(),
+        payload: IppPayload::empty(),
+        default_version: request.default_version,
+        default_language: request.default_language.clone(),
+    })
+}
+
+/// Create a Get-Printer-Attributes request for the printer of the request, used to negotiate the version
+fn version_probe(request: &IppRequestResponse, printer: &str, version: IppVersion) -> Option<IppRequestResponse> {
+    let uri: Uri = printer.parse().ok()?;
+    let mut probe = IppRequestResponse::new(version, Operation::GetPrinterAttributes, Some(uri)).ok()?;
+    for name in [
+        IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE,
+        IppAttribute::REQUESTING_USER_NAME,
+    ] {
+        if let Some(value) = operation_attribute(request, name) {
+            probe.set_operation_attribute(IppAttribute::new(name.try_into().ok()?, value));
+        }
+    }
+    probe.attributes_mut().add(
+        DelimiterTag::OperationAttributes,
+        IppAttribute::new(
+            IppAttribute::REQUESTED_ATTRIBUTES.try_into().ok()?,
+            IppValue::Keyword(IppAttribute::IPP_VERSIONS_SUPPORTED.try_into().ok()?),
+        ),
+    );
+    Some(probe)
+}
+
+/// Return a lower IPP version to use if the server has rejected the request version
+fn fallback_version(request_version: IppVersion, response: &IppRequestResponse) -> Option<IppVersion> {
+    if response.header().status_code() != StatusCode::ServerErrorVersionNotSupported {
+        return None;
+    }
+
+    // RFC 8011, section 4.1.8: the response carries the closest version supported by the server
+    let version = response.header().version;
+    if version < request_version && version >= IppVersion::v1_0() {
+        Some(version)
+    } else {
+        request_version.downgrade()
+    }
+}
+
 #[cfg(feature = "__tls")]
 /// TLS backend selection for the IPP client
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -51,6 +234,11 @@ pub struct IppClientBuilder<T> {
     ca_certs: Vec<Vec<u8>>,
     #[cfg(feature = "__tls")]
     tls_backend: Option<TlsBackend>,
+    ipp_version: Option<IppVersion>,
+    natural_language: Option<IppLanguage>,
+    user_name: Option<IppName>,
+    limits: ParserLimits,
+    parse_mode: ParseMode,
     _phantom_data: PhantomData<T>,
 }
 
@@ -66,6 +254,11 @@ impl<T> IppClientBuilder<T> {
             ca_certs: Vec::new(),
             #[cfg(feature = "__tls")]
             tls_backend: None,
+            ipp_version: None,
+            natural_language: None,
+            user_name: None,
+            limits: ParserLimits::default(),
+            parse_mode: ParseMode::default(),
             _phantom_data: PhantomData,
         }
     }
@@ -119,13 +312,53 @@ impl<T> IppClientBuilder<T> {
         self.tls_backend = Some(backend);
         self
     }
+
+    /// Set the preferred IPP version for requests using the default version 1.1 of the operations.
+    /// Requests with an explicitly set version are sent as is. If the printer responds with
+    /// `server-error-version-not-supported` the client retries requests without a payload with a lower version
+    /// and remembers it for subsequent requests to the same printer once the retry succeeds.
+    pub fn ipp_version(mut self, version: IppVersion) -> Self {
+        self.ipp_version = Some(version);
+        self
+    }
+
+    /// Set the attributes-natural-language for all requests, e.g. "de" or "ja". Default is "en".
+    /// Printers use it to localize text values such as printer-state-message and status-message.
+    /// The language set explicitly on a request is kept.
+    pub fn natural_language(mut self, language: IppLanguage) -> Self {
+        self.natural_language = Some(language);
+        self
+    }
+
+    /// Set the default requesting-user-name attribute, which is used unless the operation specifies one
+    pub fn user_name(mut self, user_name: IppName) -> Self {
+        self.user_name = Some(user_name);
+        self
+    }
+
+    /// Set the resource limits for parsing responses. Default is `ParserLimits::default()`.
+    pub fn limits(mut self, limits: ParserLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Set the mode for parsing responses. Default is `ParseMode::Strict` which rejects malformed responses,
+    /// use `ParseMode::Lenient` to accept responses from printers which violate the encoding rules.
+    pub fn parse_mode(mut self, mode: ParseMode) -> Self {
+        self.parse_mode = mode;
+        self
+    }
 }
 
 #[cfg(feature = "async-client")]
 impl IppClientBuilder<non_blocking::AsyncIppClient> {
     /// Build the async client
     pub fn build(self) -> non_blocking::AsyncIppClient {
-        non_blocking::AsyncIppClient(self)
+        non_blocking::AsyncIppClient {
+            config: self,
+            request_ids: RequestIdGenerator::new(),
+            versions: VersionCache::default(),
+        }
     }
 }
 
@@ -133,7 +366,11 @@ impl IppClientBuilder<non_blocking::AsyncIppClient> {
 impl IppClientBuilder<blocking::IppClient> {
     /// Build the blocking client
     pub fn build(self) -> blocking::IppClient {
-        blocking::IppClient(self)
+        blocking::IppClient {
+            config: self,
+            request_ids: RequestIdGenerator::new(),
+            versions: VersionCache::default(),
+        }
     }
 }
 
@@ -148,20 +385,29 @@ pub mod non_blocking {
 
     #[cfg(feature = "__tls")]
     use super::TlsBackend;
-    use super::{CONNECT_TIMEOUT, IppClientBuilder, ipp_uri_to_string};
-    use crate::{error::IppError, parser::AsyncIppParser, request::IppRequestResponse};
+    use super::{
+        CONNECT_TIMEOUT, IppClientBuilder, RequestIdGenerator, VersionCache, apply_defaults, assign_request_id,
+        check_request_id, fallback_version, ipp_uri_to_string, needs_negotiation, preferred_version, printer_key,
+        replayable_copy, version_probe,
+    };
+    use crate::{error::IppError, model::IppVersion, parser::AsyncIppParser, request::IppRequestResponse};
 
     const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"), ";reqwest");
 
     /// Asynchronous IPP client.
     ///
     /// IPP client is responsible for sending requests to an IPP server.
-    pub struct AsyncIppClient(pub(super) IppClientBuilder<Self>);
+    /// Each request gets a unique request id which is verified against the response.
+    pub struct AsyncIppClient {
+        pub(super) config: IppClientBuilder<Self>,
+        pub(super) request_ids: RequestIdGenerator,
+        pub(super) versions: VersionCache,
+    }
 
     impl AsyncIppClient {
         /// Create an IPP client with default options
         pub fn new(uri: Uri) -> Self {
-            AsyncIppClient(AsyncIppClient::builder(uri))
+            AsyncIppClient::builder(uri).build()
         }
 
         /// Create an IPP client builder for setting extra options
@@ -171,7 +417,7 @@ pub mod non_blocking {
 
         /// Return the client URI
         pub fn uri(&self) -> &Uri {
-            &self.0.uri
+            &self.config.uri
         }
 
         /// Send an IPP request to the server
@@ -179,20 +425,85 @@ pub mod non_blocking {
         where
             R: Into<IppRequestResponse>,
         {
+            let mut request = request.into();
+            apply_defaults(&self.config, &mut request);
+
+            let printer = printer_key(&self.config.uri, &request);
+
+            let mut negotiated = self.versions.get(&printer);
+            if needs_negotiation(&request, negotiated) {
+                negotiated = self.negotiate_version(&request, &printer).await;
+            }
+
+            if let Some(version) = preferred_version(&request, negotiated, self.config.ipp_version) {
+                request.header_mut().version = version;
+            }
+
+            self.send_with_fallback(request, printer).await
+        }
+
+        // send a Get-Printer-Attributes request to find the version accepted by the printer and remember it
+        async fn negotiate_version(&self, request: &IppRequestResponse, printer: &str) -> Option<IppVersion> {
+            let version = preferred_version(request, None, self.config.ipp_version).unwrap_or(request.header().version);
+            let probe = version_probe(request, printer, version)?;
+            let response = self.send_with_fallback(probe, printer.to_owned()).await.ok()?;
+            if !response.header().status_code().is_success() {
+                return None;
+            }
+            let version = self.versions.get(printer).unwrap_or(version);
+            self.versions.set(printer.to_owned(), version);
+            Some(version)
+        }
+
+        // resend requests without a payload with a lower version if the printer rejects the version
+        async fn send_with_fallback(
+            &self,
+            mut request: IppRequestResponse,
+            printer: String,
+        ) -> Result<IppRequestResponse, IppError> {
+            let mut downgraded = None;
+            loop {
+                let version = request.header().version;
+                let replay = replayable_copy(&request);
+                let response = self.send_request(request).await?;
+
+                match (fallback_version(version, &response), replay) {
+                    (Some(fallback), Some(replay)) => {
+                        request = replay;
+                        request.header_mut().version = fallback;
+                        downgraded = Some(fallback);
+                    }
+                    (Some(_), None) => return Ok(response),
+                    (None, _) => {
+                        // remember the lower version only once the printer has accepted it
+                        if let Some(version) = downgraded
+                            && response.header().status_code().is_success()
+                        {
+                            self.versions.set(printer, version);
+                        }
+                        return Ok(response);
+                    }
+                }
+            }
+        }
+
+        async fn send_request(&self, mut request: IppRequestResponse) -> Result<IppRequestResponse, IppError> {
+            let request_id = assign_request_id(&self.request_ids, &mut request);
+
             let mut builder = ClientBuilder::new().connect_timeout(CONNECT_TIMEOUT);
 
-            if let Some(timeout) = self.0.request_timeout {
+            if let Some(timeout) = self.config.request_timeout {
                 builder = builder.timeout(timeout);
             }
 
             #[cfg(feature = "__tls")]
             {
-                if self.0.ignore_tls_errors {
+                if self.config.ignore_tls_errors {
                     builder = builder
                         .danger_accept_invalid_hostnames(true)
                         .danger_accept_invalid_certs(true);
                 }
-                for data in &self.0.ca_certs {
+                for data in &self.config.ca_certs {
                     let cert =
                         reqwest::Certificate::from_pem(data).or_else(|_| reqwest::Certificate::from_der(data))?;
                     builder = builder.tls_certs_merge(Some(cert));
@@ -200,28 +511,28 @@ pub mod non_blocking {
             }
 
             #[cfg(feature = "async-client-rustls")]
-            if self.0.tls_backend != Some(TlsBackend::Native) {
+            if self.config.tls_backend != Some(TlsBackend::Native) {
                 builder = builder.tls_backend_rustls();
             }
 
             #[cfg(feature = "async-client-tls")]
-            if self.0.tls_backend == Some(TlsBackend::Native) {
+            if self.config.tls_backend == Some(TlsBackend::Native) {
                 builder = builder.tls_backend_native();
             }
 
             let mut req_builder = builder
                 .user_agent(USER_AGENT)
                 .build()?
-                .post(ipp_uri_to_string(&self.0.uri));
+                .post(ipp_uri_to_string(&self.config.uri));
 
-            for (k, v) in &self.0.headers {
+            for (k, v) in &self.config.headers {
                 req_builder = req_builder.header(k, v);
             }
 
             let response = req_builder
                 .header("content-type", "application/ipp")
                 .body(Body::wrap_stream(tokio_util::io::ReaderStream::new(
-                    request.into().into_async_read().compat(),
+                    request.into_async_read().compat(),
                 )))
                 .send()
                 .await?;
@@ -229,8 +540,10 @@ pub mod non_blocking {
             if response.status().is_success() {
                 let parser = AsyncIppParser::new(BufReader::new(
                     response.bytes_stream().map_err(io::Error::other).into_async_read(),
-                ));
-                parser.parse().await.map_err(IppError::from)
+                ))
+                .limits(self.config.limits)
+                .mode(self.config.parse_mode);
+                check_request_id(request_id, parser.parse().await?)
             } else {
                 Err(IppError::RequestError(response.status().as_u16()))
             }
@@ -243,20 +556,31 @@ pub mod blocking {
     use http::Uri;
     use ureq::{Agent, SendBody};
 
-    use super::{CONNECT_TIMEOUT, IppClientBuilder, ipp_uri_to_string};
-    use crate::{error::IppError, parser::IppParser, reader::IppReader, request::IppRequestResponse};
+    use super::{
+        CONNECT_TIMEOUT, IppClientBuilder, RequestIdGenerator, VersionCache, apply_defaults, assign_request_id,
+        check_request_id, fallback_version, ipp_uri_to_string, needs_negotiation, preferred_version, printer_key,
+        replayable_copy, version_probe,
+    };
+    use crate::{
+        error::IppError, model::IppVersion, parser::IppParser, reader::IppReader, request::IppRequestResponse,
+    };
 
     const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"), ";ureq");
 
     /// Blocking IPP client.
     ///
     /// IPP client is responsible for sending requests to an IPP server.
-    pub struct IppClient(pub(super) IppClientBuilder<Self>);
+    /// Each request gets a unique request id which is verified against the response.
+    pub struct IppClient {
+        pub(super) config: IppClientBuilder<Self>,
+        pub(super) request_ids: RequestIdGenerator,
+        pub(super) versions: VersionCache,
+    }
 
     impl IppClient {
         /// Create an IPP client with default options
         pub fn new(uri: Uri) -> Self {
-            IppClient(IppClient::builder(uri))
+            IppClient::builder(uri).build()
         }
 
         /// Create an IPP client builder for setting extra options
@@ -266,7 +590,7 @@ pub mod blocking {
 
         /// Return the client URI
         pub fn uri(&self) -> &Uri {
-            &self.0.uri
+            &self.config.uri
         }
 
         /// Send an IPP request to the server
@@ -274,9 +598,74 @@ pub mod blocking {
         where
             R: Into<IppRequestResponse>,
         {
+            let mut request = request.into();
+            apply_defaults(&self.config, &mut request);
+
+            let printer = printer_key(&self.config.uri, &request);
+
+            let mut negotiated = self.versions.get(&printer);
+            if needs_negotiation(&request, negotiated) {
+                negotiated = self.negotiate_version(&request, &printer);
+            }
+
+            if let Some(version) = preferred_version(&request, negotiated, self.config.ipp_version) {
+                request.header_mut().version = version;
+            }
+
+            self.send_with_fallback(request, printer)
+        }
+
+        // send a Get-Printer-Attributes request to find the version accepted by the printer and remember it
+        fn negotiate_version(&self, request: &IppRequestResponse, printer: &str) -> Option<IppVersion> {
+            let version = preferred_version(request, None, self.config.ipp_version).unwrap_or(request.header().version);
+            let probe = version_probe(request, printer, version)?;
+            let response = self.send_with_fallback(probe, printer.to_owned()).ok()?;
+            if !response.header().status_code().is_success() {
+                return None;
+            }
+            let version = self.versions.get(printer).unwrap_or(version);
+            self.versions.set(printer.to_owned(), version);
+            Some(version)
+        }
+
+        // resend requests without a payload with a lower version if the printer rejects the version
+        fn send_with_fallback(
+            &self,
+            mut request: IppRequestResponse,
+            printer: String,
+        ) -> Result<IppRequestResponse, IppError> {
+            let mut downgraded = None;
+            loop {
+                let version = request.header().version;
+                let replay = replayable_copy(&request);
+                let response = self.send_request(request)?;
+
+                match (fallback_version(version, &response), replay) {
+                    (Some(fallback), Some(replay)) => {
+                        request = replay;
+                        request.header_mut().version = fallback;
+                        downgraded = Some(fallback);
+                    }
+                    (Some(_), None) => return Ok(response),
+                    (None, _) => {
+                        // remember the lower version only once the printer has accepted it
+                        if let Some(version) = downgraded
+                            && response.header().status_code().is_success()
+                        {
+                            self.versions.set(printer, version);
+                        }
+                        return Ok(response);
+                    }
+                }
+            }
+        }
+
+        fn send_request(&self, mut request: IppRequestResponse) -> Result<IppRequestResponse, IppError> {
+            let request_id = assign_request_id(&self.request_ids, &mut request);
+
             let mut builder = Agent::config_builder().timeout_connect(Some(CONNECT_TIMEOUT));
 
-            if let Some(timeout) = self.0.request_timeout {
+            if let Some(timeout) = self.config.request_timeout {
                 builder = builder.timeout_global(Some(timeout));
             }
 
@@ -290,17 +679,17 @@ pub mod blocking {
                 use super::TlsBackend;
 
                 let mut tls_config = TlsConfig::builder();
-                if self.0.ignore_tls_errors {
+                if self.config.ignore_tls_errors {
                     tls_config = tls_config.disable_verification(true);
                 }
 
                 #[cfg(feature = "client-rustls")]
-                if self.0.tls_backend != Some(TlsBackend::Native) {
+                if self.config.tls_backend != Some(TlsBackend::Native) {
                     tls_config = tls_config.provider(TlsProvider::Rustls);
                 }
 
                 #[cfg(feature = "client-tls")]
-                if self.0.tls_backend == Some(TlsBackend::Native) {
+                if self.config.tls_backend == Some(TlsBackend::Native) {
                     tls_config = tls_config.provider(TlsProvider::NativeTls);
                 }
 
@@ -318,7 +707,7 @@ pub mod blocking {
 
                 let mut roots = (**ROOTS).clone();
 
-                for data in &self.0.ca_certs {
+                for data in &self.config.ca_certs {
                     roots.push(ureq::tls::Certificate::from_der(data).to_owned());
                 }
 
@@ -329,27 +718,391 @@ pub mod blocking {
             let agent: Agent = builder.user_agent(USER_AGENT).build().into();
 
             let mut req = agent
-                .post(&ipp_uri_to_string(&self.0.uri))
+                .post(&ipp_uri_to_string(&self.config.uri))
                 .header("content-type", "application/ipp");
 
-            for (k, v) in &self.0.headers {
+            for (k, v) in &self.config.headers {
                 req = req.header(k, v);
             }
 
-            let response = req.send(SendBody::from_reader(&mut request.into().into_read()))?;
+            let response = req.send(SendBody::from_reader(&mut request.into_read()))?;
             let reader = response.into_body().into_reader();
-            let parser = IppParser::new(IppReader::new(reader));
+            let parser = IppParser::new(IppReader::new(reader))
+                .limits(self.config.limits)
+                .mode(self.config.parse_mode);
 
-            parser.parse().map_err(IppError::from)
+            check_request_id(request_id, parser.parse()?)
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        io::{self, BufRead, BufReader, Read, Write},
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
     use http::Uri;
 
-    use crate::client::ipp_uri_to_string;
+    use super::*;
+    use crate::{
+        operation::{IppOperation, builder::IppOperationBuilder},
+        parser::IppBytesParser,
+    };
+
+    #[test]
+    fn test_request_ids_increase() {
+        let ids = RequestIdGenerator::new();
+        assert_eq!(ids.next_id(), 1);
+        assert_eq!(ids.next_id(), 2);
+        assert_eq!(ids.next_id(), 3);
+    }
+
+    #[test]
+    fn test_request_ids_wrap_around() {
+        let ids = RequestIdGenerator(AtomicI32::new(i32::MAX));
+        assert_eq!(ids.next_id(), i32::MAX);
+        assert_eq!(ids.next_id(), 1);
+    }
+
+    #[test]
+    fn test_assign_request_id() {
+        let ids = RequestIdGenerator::new();
+        let mut request = IppRequestResponse::new(IppVersion::v1_1(), Operation::GetJobs, None).unwrap();
+        assert_eq!(assign_request_id(&ids, &mut request), 1);
+        assert_eq!(assign_request_id(&ids, &mut request), 2);
+        assert_eq!(request.header().request_id, 2);
+    }
+
+    #[test]
+    fn test_check_request_id() {
+        let response = IppRequestResponse::new_response(IppVersion::v1_1(), StatusCode::SuccessfulOk, 5).unwrap();
+        assert!(check_request_id(5, response).is_ok());
+
+        let response = IppRequestResponse::new_response(IppVersion::v1_1(), StatusCode::SuccessfulOk, 6).unwrap();
+        assert!(matches!(
+            check_request_id(5, response),
+            Err(IppError::RequestIdMismatch { expected: 5, actual: 6 })
+        ));
+    }
+
+    #[test]
+    fn test_printer_key() {
+        let uri: Uri = "ipp://host/printers/test".parse().unwrap();
+        let request = IppRequestResponse::new(IppVersion::v1_1(), Operation::GetJobs, Some(uri.clone())).unwrap();
+        assert_eq!(
+            printer_key(&"ipp://host".parse().unwrap(), &request),
+            "ipp://host/printers/test"
+        );
+
+        let request = IppRequestResponse::new(IppVersion::v1_1(), Operation::CupsGetPrinters, None).unwrap();
+        assert_eq!(printer_key(&uri, &request), "ipp://host/printers/test");
+    }
+
+    #[test]
+    fn test_fallback_version() {
+        let ok = IppRequestResponse::new_response(IppVersion::v2_0(), StatusCode::SuccessfulOk, 1).unwrap();
+        assert_eq!(fallback_version(IppVersion::v2_0(), &ok), None);
+
+        let rejected =
+            IppRequestResponse::new_response(IppVersion::v2_0(), StatusCode::ServerErrorVersionNotSupported, 1)
+                .unwrap();
+        assert_eq!(
+            fallback_version(IppVersion::v2_0(), &rejected),
+            Some(IppVersion::v1_1())
+        );
+
+        let closest =
+            IppRequestResponse::new_response(IppVersion::v1_0(), StatusCode::ServerErrorVersionNotSupported, 1)
+                .unwrap();
+        assert_eq!(fallback_version(IppVersion::v2_0(), &closest), Some(IppVersion::v1_0()));
+        assert_eq!(fallback_version(IppVersion::v1_0(), &closest), None);
+    }
+
+    fn get_jobs() -> IppRequestResponse {
+        IppOperationBuilder::get_jobs("ipp://host".parse().unwrap())
+            .build()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn test_preferred_version() {
+        let v1_1 = IppVersion::v1_1();
+        let v2_0 = IppVersion::v2_0();
+        let request = get_jobs();
+        assert_eq!(preferred_version(&request, None, None), None);
+        assert_eq!(preferred_version(&request, None, Some(v2_0)), Some(v2_0));
+        assert_eq!(
+            preferred_version(&request, Some(IppVersion::v1_0()), Some(v2_0)),
+            Some(IppVersion::v1_0())
+        );
+
+        // explicit version set by the caller, including the default one
+        let mut request = get_jobs();
+        request.set_version(v1_1);
+        assert_eq!(preferred_version(&request, Some(IppVersion::v1_0()), Some(v2_0)), None);
+        let request = IppRequestResponse::new(v1_1, Operation::GetJobs, None).unwrap();
+        assert_eq!(preferred_version(&request, None, Some(v2_0)), None);
+        let mut request = get_jobs();
+        request.header_mut().version = v2_0;
+        assert_eq!(preferred_version(&request, Some(IppVersion::v1_0()), None), None);
+    }
+
+    #[test]
+    fn test_needs_negotiation() {
+        assert!(!needs_negotiation(&get_jobs(), None));
+
+        let payload = || IppPayload::new(io::empty());
+        let print_job = |payload| -> IppRequestResponse {
+            IppOperationBuilder::print_job("ipp://host".parse().unwrap(), payload)
+                .build()
+                .unwrap()
+                .into()
+        };
+        assert!(needs_negotiation(&print_job(payload()), None));
+        assert!(!needs_negotiation(&print_job(payload()), Some(IppVersion::v1_1())));
+
+        let mut request = print_job(payload());
+        request.set_version(IppVersion::v2_0());
+        assert!(!needs_negotiation(&request, None));
+    }
+
+    #[test]
+    fn test_version_probe() {
+        let mut request = get_jobs();
+        request.set_user_name("user".try_into().unwrap());
+        let probe = version_probe(&request, "ipp://host/printers/test", IppVersion::v2_0()).unwrap();
+        assert_eq!(probe.header().version, IppVersion::v2_0());
+        assert_eq!(
+            probe.header().operation_or_status,
+            Operation::GetPrinterAttributes as i16
+        );
+        assert_eq!(
+            operation_attribute(&probe, IppAttribute::PRINTER_URI),
+            Some(IppValue::Uri("ipp://host/printers/test".try_into().unwrap()))
+        );
+        assert_eq!(
+            operation_attribute(&probe, IppAttribute::REQUESTING_USER_NAME),
+            Some(IppValue::new_name_without_language("user").unwrap())
+        );
+        assert_eq!(
+            operation_attribute(&probe, IppAttribute::REQUESTED_ATTRIBUTES),
+            Some(IppValue::Keyword("ipp-versions-supported".try_into().unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_replayable_copy() {
+        let request = IppRequestResponse::new(IppVersion::v2_0(), Operation::GetJobs, None).unwrap();
+        let copy = replayable_copy(&request).unwrap();
+        assert_eq!(copy.header(), request.header());
+        assert_eq!(copy.to_bytes(), request.to_bytes());
+        assert!(!copy.is_default_version());
+        assert!(replayable_copy(&get_jobs()).unwrap().is_default_version());
+
+        let mut request = IppRequestResponse::new(IppVersion::v2_0(), Operation::PrintJob, None).unwrap();
+        *request.payload_mut() = IppPayload::new(io::empty());
+        assert!(replayable_copy(&request).is_none());
+    }
+
+    #[test]
+    fn test_version_cache() {
+        let cache = VersionCache::default();
+        assert_eq!(cache.get("ipp://host"), None);
+        cache.set("ipp://host".to_owned(), IppVersion::v1_1());
+        assert_eq!(cache.get("ipp://host"), Some(IppVersion::v1_1()));
+    }
+
+    #[test]
+    fn test_apply_defaults() {
+        let config = IppClientBuilder::<()>::new("ipp://host".parse().unwrap())
+            .natural_language("de".try_into().unwrap())
+            .user_name("user".try_into().unwrap());
+
+        let mut request = IppRequestResponse::new(IppVersion::v1_1(), Operation::GetJobs, None).unwrap();
+        apply_defaults(&config, &mut request);
+        assert_eq!(
+            operation_attribute(&request, IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE),
+            Some(IppValue::new_natural_language("de").unwrap())
+        );
+        assert_eq!(
+            operation_attribute(&request, IppAttribute::REQUESTING_USER_NAME),
+            Some(IppValue::new_name_without_language("user").unwrap())
+        );
+
+        let group = request
+            .attributes()
+            .first_of(DelimiterTag::OperationAttributes)
+            .unwrap();
+        assert_eq!(group.attributes().len(), 3);
+    }
+
+    #[test]
+    fn test_apply_defaults_keeps_operation_user_name() {
+        let config =
+            IppClientBuilder::<()>::new("ipp://host".parse().unwrap()).user_name("default".try_into().unwrap());
+
+        let mut request = IppRequestResponse::new(IppVersion::v1_1(), Operation::GetJobs, None).unwrap();
+        request.set_user_name("explicit".try_into().unwrap());
+        apply_defaults(&config, &mut request);
+        assert_eq!(
+            operation_attribute(&request, IppAttribute::REQUESTING_USER_NAME),
+            Some(IppValue::new_name_without_language("explicit").unwrap())
+        );
+        assert_eq!(
+            operation_attribute(&request, IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE),
+            Some(IppValue::new_natural_language("en").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_apply_defaults_keeps_request_language() {
+        let config =
+            IppClientBuilder::<()>::new("ipp://host".parse().unwrap()).natural_language("de".try_into().unwrap());
+
+        let mut request = IppRequestResponse::new(IppVersion::v1_1(), Operation::GetJobs, None).unwrap();
+        request.set_natural_language("fr".try_into().unwrap());
+        apply_defaults(&config, &mut request);
+        assert_eq!(
+            operation_attribute(&request, IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE),
+            Some(IppValue::new_natural_language("fr").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_apply_defaults_keeps_explicit_default_language() {
+        let config =
+            IppClientBuilder::<()>::new("ipp://host".parse().unwrap()).natural_language("de".try_into().unwrap());
+
+        let mut request = get_jobs();
+        request.set_natural_language("en".try_into().unwrap());
+        apply_defaults(&config, &mut request);
+        assert_eq!(
+            operation_attribute(&request, IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE),
+            Some(IppValue::new_natural_language("en").unwrap())
+        );
+    }
+
+    // HTTP server of a printer which supports IPP 1.1 only, returns the operations and versions of the requests
+    fn ipp_1_1_printer(requests: usize) -> (Uri, thread::JoinHandle<Vec<(i16, IppVersion)>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("ipp://{}/ipp/print", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let handle = thread::spawn(move || {
+            let mut received = Vec::new();
+            for stream in listener.incoming().take(requests) {
+                let stream = stream.unwrap();
+                let body = read_http_body(&stream);
+                let request = IppBytesParser::new(body).parse().unwrap();
+                let header = request.header();
+                received.push((header.operation_or_status, header.version));
+
+                let (version, status) = if header.version > IppVersion::v1_1() {
+                    (IppVersion::v1_1(), StatusCode::ServerErrorVersionNotSupported)
+                } else {
+                    (header.version, StatusCode::SuccessfulOk)
+                };
+                let response = IppRequestResponse::new_response(version, status, header.request_id)
+                    .unwrap()
+                    .to_bytes();
+                let mut stream = &stream;
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/ipp\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                    response.len()
+                )
+                .unwrap();
+                stream.write_all(&response).unwrap();
+            }
+            received
+        });
+        (uri, handle)
+    }
+
+    fn read_http_body(stream: &TcpStream) -> Vec<u8> {
+        let mut reader = BufReader::new(stream);
+        let mut chunked = false;
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end().to_ascii_lowercase();
+            if line.is_empty() {
+                break;
+            } else if line == "transfer-encoding: chunked" {
+                chunked = true;
+            } else if let Some(value) = line.strip_prefix("content-length: ") {
+                length = value.parse().unwrap();
+            }
+        }
+
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        while chunked {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let size = usize::from_str_radix(line.trim_end(), 16).unwrap();
+            let mut chunk = vec![0; size + 2];
+            reader.read_exact(&mut chunk).unwrap();
+            body.extend_from_slice(&chunk[..size]);
+            chunked = size > 0;
+        }
+        body
+    }
+
+    fn print_job(uri: &Uri) -> impl IppOperation {
+        let payload = IppPayload::new(io::Cursor::new(b"document".to_vec()));
+        IppOperationBuilder::print_job(uri.clone(), payload).build().unwrap()
+    }
+
+    // the version is negotiated once with Get-Printer-Attributes because Print-Job cannot be resent
+    fn assert_negotiated(server: thread::JoinHandle<Vec<(i16, IppVersion)>>) {
+        let get_printer_attributes = Operation::GetPrinterAttributes as i16;
+        let print_job = Operation::PrintJob as i16;
+        assert_eq!(
+            server.join().unwrap(),
+            vec![
+                (get_printer_attributes, IppVersion::v2_0()),
+                (get_printer_attributes, IppVersion::v1_1()),
+                (print_job, IppVersion::v1_1()),
+                (print_job, IppVersion::v1_1()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "async-client")]
+    #[tokio::test]
+    async fn test_print_job_version_fallback() {
+        let (uri, server) = ipp_1_1_printer(4);
+        let client = non_blocking::AsyncIppClient::builder(uri.clone())
+            .ipp_version(IppVersion::v2_0())
+            .build();
+
+        for _ in 0..2 {
+            let response = client.send(print_job(&uri)).await.unwrap();
+            assert_eq!(response.header().status_code(), StatusCode::SuccessfulOk);
+        }
+        assert_negotiated(server);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_blocking_print_job_version_fallback() {
+        let (uri, server) = ipp_1_1_printer(4);
+        let client = blocking::IppClient::builder(uri.clone())
+            .ipp_version(IppVersion::v2_0())
+            .build();
+
+        for _ in 0..2 {
+            let response = client.send(print_job(&uri)).unwrap();
+            assert_eq!(response.header().status_code(), StatusCode::SuccessfulOk);
+        }
+        assert_negotiated(server);
+    }
 
     #[test]
     fn test_ipp_uri_no_port() {
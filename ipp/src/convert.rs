@@ -0,0 +1,555 @@
+//!
+//! Typed conversions between IPP values and Rust types
+//!
+use std::{ops::RangeInclusive, time::SystemTime};
+
+use crate::{
+    FromPrimitive as _,
+    attribute::{IppAttribute, IppAttributeGroup},
+    error::IppError,
+    model::{
+        DelimiterTag, Finishings, JobState, JobStateReason, Operation, Orientation, PrintQuality, PrinterState,
+        PrinterStateReason, PrinterStateReasonValue,
+    },
+    registry::Syntax,
+    value::{IppCollection, IppDateTime, IppResolution, IppValue},
+};
+
+/// Conversion from an IPP value into a Rust type
+pub trait FromIppValue: Sized {
+    /// Convert the value, returning `IppError::InvalidAttributeType` on type mismatch
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError>;
+}
+
+/// Conversion from a Rust type into an IPP value.
+/// Strings and reason keywords are converted into keywords, failing if they are too long.
+pub trait ToIppValue {
+    /// Convert into an IPP value
+    fn to_ipp_value(&self) -> Result<IppValue, IppError>;
+
+    /// Convert into an IPP value of a given syntax, e.g. a string into a name instead of a keyword.
+    /// The default implementation returns `IppError::InvalidAttributeType` if the converted value
+    /// does not conform to the syntax.
+    fn to_ipp_value_as(&self, syntax: Syntax) -> Result<IppValue, IppError> {
+        let value = self.to_ipp_value()?;
+        if (&value).into_iter().all(|v| syntax.matches(v)) {
+            Ok(value)
+        } else {
+            Err(IppError::InvalidAttributeType)
+        }
+    }
+}
+
+/// Conversion from an attribute group into a struct, usually derived with `#[derive(IppAttributes)]`
+pub trait FromAttributeGroup: Sized {
+    /// Extract the struct fields from the attribute group
+    fn from_attribute_group(group: &IppAttributeGroup) -> Result<Self, IppError>;
+}
+
+/// Conversion from a struct into attributes, usually derived with `#[derive(IppAttributes)]`
+pub trait ToAttributeGroup {
+    /// Convert the struct fields into attributes
+    fn to_attributes(&self) -> Result<Vec<IppAttribute>, IppError>;
+
+    /// Convert the struct fields into an attribute group of a given type
+    fn to_attribute_group(&self, tag: DelimiterTag) -> Result<IppAttributeGroup, IppError> {
+        let mut group = IppAttributeGroup::new(tag);
+        group.attributes_mut().extend(self.to_attributes()?);
+        Ok(group)
+    }
+}
+
+impl FromIppValue for IppValue {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        Ok(value.clone())
+    }
+}
+
+impl FromIppValue for i32 {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        match value {
+            IppValue::Integer(v) | IppValue::Enum(v) => Ok(*v),
+            _ => Err(IppError::InvalidAttributeType),
+        }
+    }
+}
+
+impl FromIppValue for bool {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        value.as_boolean().copied().ok_or(IppError::InvalidAttributeType)
+    }
+}
+
+impl FromIppValue for String {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        match value {
+            IppValue::Keyword(s) | IppValue::NameWithoutLanguage(s) | IppValue::MemberAttrName(s) => Ok(s.to_string()),
+            IppValue::TextWithoutLanguage(s) | IppValue::TextWithLanguage { text: s, .. } => Ok(s.to_string()),
+            IppValue::NameWithLanguage { name, .. } => Ok(name.to_string()),
+            IppValue::Charset(s) | IppValue::NaturalLanguage(s) => Ok(s.to_string()),
+            IppValue::Uri(s) | IppValue::UriScheme(s) => Ok(s.to_string()),
+            IppValue::MimeMediaType(s) => Ok(s.to_string()),
+            _ => Err(IppError::InvalidAttributeType),
+        }
+    }
+}
+
+impl FromIppValue for RangeInclusive<i32> {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        match value {
+            IppValue::RangeOfInteger { min, max } => Ok(*min..=*max),
+            _ => Err(IppError::InvalidAttributeType),
+        }
+    }
+}
+
+impl FromIppValue for IppResolution {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        match value {
+            IppValue::Resolution {
+                cross_feed,
+                feed,
+                units,
+            } => Ok(IppResolution {
+                cross_feed: *cross_feed,
+                feed: *feed,
+                units: *units,
+            }),
+            _ => Err(IppError::InvalidAttributeType),
+        }
+    }
+}
+
+impl FromIppValue for IppDateTime {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        value.as_date_time().cloned().ok_or(IppError::InvalidAttributeType)
+    }
+}
+
+impl FromIppValue for SystemTime {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        let datetime = value.as_date_time().ok_or(IppError::InvalidAttributeType)?;
+        datetime.clone().try_into()
+    }
+}
+
+impl FromIppValue for IppCollection {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        value.as_collection().cloned().ok_or(IppError::InvalidAttributeType)
+    }
+}
+
+/// A single value is converted into a one-element vector
+impl<T: FromIppValue> FromIppValue for Vec<T> {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        match value {
+            IppValue::Array(values) => values.iter().map(T::from_ipp_value).collect(),
+            value => Ok(vec![T::from_ipp_value(value)?]),
+        }
+    }
+}
+
+/// The `no-value` out-of-band value is converted into `None`
+impl<T: FromIppValue> FromIppValue for Option<T> {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        match value {
+            IppValue::NoValue => Ok(None),
+            value => T::from_ipp_value(value).map(Some),
+        }
+    }
+}
+
+macro_rules! enum_conversions {
+    ($($enum:ty),* $(,)?) => {
+        $(
+            impl FromIppValue for $enum {
+                fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+                    value
+                        .as_enum()
+                        .and_then(|v| <$enum>::from_i32(*v))
+                        .ok_or(IppError::InvalidAttributeType)
+                }
+            }
+
+            impl ToIppValue for $enum {
+                fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+                    Ok(IppValue::Enum(*self as i32))
+                }
+            }
+        )*
+    };
+}
+
+enum_conversions!(PrinterState, JobState, Finishings, Orientation, PrintQuality, Operation);
+
+macro_rules! keyword_conversions {
+    ($($enum:ty),* $(,)?) => {
+        $(
+            impl FromIppValue for $enum {
+                fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+                    value
+                        .as_keyword()
+                        .map(|keyword| <$enum>::from(keyword.as_str()))
+                        .ok_or(IppError::InvalidAttributeType)
+                }
+            }
+
+            impl ToIppValue for $enum {
+                fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+                    self.as_str().to_ipp_value()
+                }
+            }
+        )*
+    };
+}
+
+keyword_conversions!(JobStateReason);
+
+// the severity suffix is dropped, use `PrinterStateReasonValue` to keep it
+impl FromIppValue for PrinterStateReason {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        PrinterStateReasonValue::from_ipp_value(value).map(|value| value.reason)
+    }
+}
+
+impl ToIppValue for PrinterStateReason {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        self.as_str().to_ipp_value()
+    }
+}
+
+impl FromIppValue for PrinterStateReasonValue {
+    fn from_ipp_value(value: &IppValue) -> Result<Self, IppError> {
+        value
+            .as_keyword()
+            .map(|keyword| PrinterStateReasonValue::parse(keyword))
+            .ok_or(IppError::InvalidAttributeType)
+    }
+}
+
+impl ToIppValue for PrinterStateReasonValue {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        self.to_string().to_ipp_value()
+    }
+}
+
+impl ToIppValue for i32 {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        Ok(IppValue::Integer(*self))
+    }
+
+    fn to_ipp_value_as(&self, syntax: Syntax) -> Result<IppValue, IppError> {
+        match syntax {
+            Syntax::Enum => Ok(IppValue::Enum(*self)),
+            Syntax::Integer { min, max } if (min..=max).contains(self) => Ok(IppValue::Integer(*self)),
+            _ => Err(IppError::InvalidAttributeType),
+        }
+    }
+}
+
+impl ToIppValue for bool {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        Ok(IppValue::Boolean(*self))
+    }
+}
+
+impl ToIppValue for RangeInclusive<i32> {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        Ok(IppValue::RangeOfInteger {
+            min: *self.start(),
+            max: *self.end(),
+        })
+    }
+}
+
+impl ToIppValue for str {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        Ok(IppValue::new_keyword(self)?)
+    }
+
+    fn to_ipp_value_as(&self, syntax: Syntax) -> Result<IppValue, IppError> {
+        Ok(match syntax {
+            Syntax::Keyword => IppValue::new_keyword(self)?,
+            Syntax::Name(_) => IppValue::new_name_without_language(self)?,
+            Syntax::Text(_) => IppValue::new_text_without_language(self)?,
+            Syntax::Uri => IppValue::new_uri(self)?,
+            Syntax::UriScheme => IppValue::new_uri_scheme(self)?,
+            Syntax::Charset => IppValue::new_charset(self)?,
+            Syntax::NaturalLanguage => IppValue::new_natural_language(self)?,
+            Syntax::MimeMediaType => IppValue::new_mime_media_type(self)?,
+            _ => return Err(IppError::InvalidAttributeType),
+        })
+    }
+}
+
+impl ToIppValue for String {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        self.as_str().to_ipp_value()
+    }
+
+    fn to_ipp_value_as(&self, syntax: Syntax) -> Result<IppValue, IppError> {
+        self.as_str().to_ipp_value_as(syntax)
+    }
+}
+
+impl ToIppValue for IppResolution {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        Ok((*self).into())
+    }
+}
+
+impl ToIppValue for IppDateTime {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        Ok(IppValue::DateTime(self.clone()))
+    }
+}
+
+impl ToIppValue for SystemTime {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        Ok(IppValue::DateTime((*self).into()))
+    }
+}
+
+impl ToIppValue for IppCollection {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        Ok(IppValue::Collection(self.clone()))
+    }
+}
+
+impl<T: ToIppValue> ToIppValue for Vec<T> {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        self.iter()
+            .map(ToIppValue::to_ipp_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map(IppValue::Array)
+    }
+
+    fn to_ipp_value_as(&self, syntax: Syntax) -> Result<IppValue, IppError> {
+        self.iter()
+            .map(|v| v.to_ipp_value_as(syntax))
+            .collect::<Result<Vec<_>, _>>()
+            .map(IppValue::Array)
+    }
+}
+
+impl<T: ToIppValue> ToIppValue for Option<T> {
+    fn to_ipp_value(&self) -> Result<IppValue, IppError> {
+        match self {
+            Some(value) => value.to_ipp_value(),
+            None => Ok(IppValue::NoValue),
+        }
+    }
+
+    fn to_ipp_value_as(&self, syntax: Syntax) -> Result<IppValue, IppError> {
+        match self {
+            Some(value) => value.to_ipp_value_as(syntax),
+            None => Ok(IppValue::NoValue),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    //! Helpers used by the code generated with `ipp-derive`
+    use super::*;
+
+    // add the attribute or member name to the errors which do not carry one
+    fn named(name: &str, error: IppError) -> IppError {
+        match error {
+            IppError::MissingAttribute => IppError::MissingNamedAttribute(name.to_owned()),
+            IppError::InvalidAttributeType => IppError::InvalidNamedAttributeType(name.to_owned()),
+            error => error,
+        }
+    }
+
+    fn convert<T: FromIppValue>(value: &IppValue, syntax: Option<Syntax>) -> Result<T, IppError> {
+        let conforms = match syntax {
+            Some(syntax) => value.into_iter().all(|v| syntax.matches(v)),
+            None => true,
+        };
+        if !conforms {
+            return Err(IppError::InvalidAttributeType);
+        }
+        T::from_ipp_value(value)
+    }
+
+    pub fn required<T: FromIppValue>(
+        name: &str,
+        value: Option<&IppValue>,
+        syntax: Option<Syntax>,
+    ) -> Result<T, IppError> {
+        let value = value.ok_or_else(|| IppError::MissingNamedAttribute(name.to_owned()))?;
+        convert(value, syntax).map_err(|e| named(name, e))
+    }
+
+    pub fn optional<T: FromIppValue>(
+        name: &str,
+        value: Option<&IppValue>,
+        syntax: Option<Syntax>,
+    ) -> Result<Option<T>, IppError> {
+        match value {
+            None | Some(IppValue::NoValue) => Ok(None),
+            Some(value) => convert(value, syntax).map(Some).map_err(|e| named(name, e)),
+        }
+    }
+
+    pub fn collection(value: &IppValue) -> Result<&IppCollection, IppError> {
+        value.as_collection().ok_or(IppError::InvalidAttributeType)
+    }
+
+    fn value<T: ToIppValue + ?Sized>(value: &T, syntax: Option<Syntax>) -> Result<IppValue, IppError> {
+        match syntax {
+            Some(syntax) => value.to_ipp_value_as(syntax),
+            None => value.to_ipp_value(),
+        }
+    }
+
+    pub fn attribute<T: ToIppValue + ?Sized>(
+        name: &str,
+        value: &T,
+        syntax: Option<Syntax>,
+    ) -> Result<IppAttribute, IppError> {
+        let value = self::value(value, syntax).map_err(|e| named(name, e))?;
+        Ok(IppAttribute::with_name(name, value)?)
+    }
+
+    pub fn member<T: ToIppValue + ?Sized>(
+        collection: &mut IppCollection,
+        name: &str,
+        value: &T,
+        syntax: Option<Syntax>,
+    ) -> Result<(), IppError> {
+        let value = self::value(value, syntax).map_err(|e| named(name, e))?;
+        collection.push(name.try_into()?, value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        attribute::{IppAttribute, IppAttributeGroup},
+        model::DelimiterTag,
+    };
+
+    #[test]
+    fn test_scalar_conversions() {
+        assert_eq!(i32::from_ipp_value(&IppValue::Integer(5)).unwrap(), 5);
+        assert!(bool::from_ipp_value(&IppValue::Integer(5)).is_err());
+        assert_eq!(
+            String::from_ipp_value(&IppValue::new_keyword("one-sided").unwrap()).unwrap(),
+            "one-sided"
+        );
+        assert_eq!(
+            RangeInclusive::<i32>::from_ipp_value(&IppValue::new_range_of_integer(1, 9)).unwrap(),
+            1..=9
+        );
+        assert_eq!(
+            IppResolution::from_ipp_value(&IppValue::new_resolution(300, 600, 3)).unwrap(),
+            IppResolution {
+                cross_feed: 300,
+                feed: 600,
+                units: 3
+            }
+        );
+        assert_eq!(Option::<i32>::from_ipp_value(&IppValue::NoValue).unwrap(), None);
+        assert_eq!(
+            PrinterState::from_ipp_value(&IppValue::Enum(5)).unwrap(),
+            PrinterState::Stopped
+        );
+        assert!(PrinterState::from_ipp_value(&IppValue::Enum(42)).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let value = vec![JobState::Pending, JobState::Completed].to_ipp_value().unwrap();
+        assert_eq!(value, IppValue::Array(vec![IppValue::Enum(3), IppValue::Enum(9)]));
+        assert_eq!(
+            Vec::<JobState>::from_ipp_value(&value).unwrap(),
+            vec![JobState::Pending, JobState::Completed]
+        );
+        assert_eq!((1..=5).to_ipp_value().unwrap(), IppValue::new_range_of_integer(1, 5));
+
+        let value = vec![
+            PrinterStateReason::MediaEmpty,
+            PrinterStateReason::Other("x-vendor".to_owned()),
+        ]
+        .to_ipp_value()
+        .unwrap();
+        assert_eq!(
+            Vec::<PrinterStateReason>::from_ipp_value(&value).unwrap()[0],
+            PrinterStateReason::MediaEmpty
+        );
+        assert_eq!(
+            PrinterStateReason::from_ipp_value(&IppValue::new_keyword("media-empty-error").unwrap()).unwrap(),
+            PrinterStateReason::MediaEmpty
+        );
+        assert_eq!(
+            PrinterStateReasonValue::parse("toner-low-warning")
+                .to_ipp_value()
+                .unwrap(),
+            IppValue::new_keyword("toner-low-warning").unwrap()
+        );
+        assert_eq!(
+            JobStateReason::JobPrinting.to_ipp_value().unwrap(),
+            IppValue::new_keyword("job-printing").unwrap()
+        );
+        assert_eq!(
+            "one-sided".to_ipp_value().unwrap(),
+            IppValue::new_keyword("one-sided").unwrap()
+        );
+        assert!("x".repeat(300).to_ipp_value().is_err());
+    }
+
+    #[test]
+    fn test_to_ipp_value_as() {
+        assert_eq!(
+            "office".to_ipp_value_as(Syntax::Name(255)).unwrap(),
+            IppValue::new_name_without_language("office").unwrap()
+        );
+        assert_eq!(3.to_ipp_value_as(Syntax::Enum).unwrap(), IppValue::Enum(3));
+        assert!(0.to_ipp_value_as(Syntax::Integer { min: 1, max: 9 }).is_err());
+        assert!(true.to_ipp_value_as(Syntax::Keyword).is_err());
+        assert_eq!(
+            vec![Some(1)].to_ipp_value_as(Syntax::Enum).unwrap(),
+            IppValue::Array(vec![IppValue::Enum(1)])
+        );
+    }
+
+    #[test]
+    fn test_get_as() {
+        let mut group = IppAttributeGroup::new(DelimiterTag::PrinterAttributes);
+        group.attributes_mut().push(IppAttribute::new(
+            IppAttribute::PRINTER_STATE.try_into().unwrap(),
+            IppValue::Enum(3),
+        ));
+        group.attributes_mut().push(IppAttribute::new(
+            IppAttribute::PRINTER_STATE_REASONS.try_into().unwrap(),
+            IppValue::Array(vec![
+                IppValue::new_keyword("toner-low-warning").unwrap(),
+                IppValue::new_keyword("media-empty-error").unwrap(),
+            ]),
+        ));
+
+        assert_eq!(
+            group.get_as::<PrinterState>(IppAttribute::PRINTER_STATE).unwrap(),
+            PrinterState::Idle
+        );
+        assert_eq!(
+            group
+                .get_as::<Vec<PrinterStateReasonValue>>(IppAttribute::PRINTER_STATE_REASONS)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(matches!(
+            group.get_as::<Vec<JobState>>(IppAttribute::PRINTER_STATE_REASONS),
+            Err(IppError::InvalidAttributeType)
+        ));
+        assert!(matches!(
+            group.get_as::<i32>(IppAttribute::JOB_ID),
+            Err(IppError::MissingAttribute)
+        ));
+    }
+}
@@ -32,6 +32,10 @@ pub enum IppError {
     /// IPP status error
     StatusError(StatusCode),
 
+    #[error("IPP request id mismatch: expected {expected}, got {actual}")]
+    /// Response request id does not match the request id
+    RequestIdMismatch { expected: i32, actual: i32 },
+
     #[error("Printer not ready")]
     PrinterNotReady,
 
@@ -47,6 +51,14 @@ pub enum IppError {
     /// Invalid attribute type
     InvalidAttributeType,
 
+    #[error("Missing attribute: {0}")]
+    /// Missing required attribute, reported by the derived conversions
+    MissingNamedAttribute(String),
+
+    #[error("Invalid attribute type: {0}")]
+    /// Invalid type or syntax of an attribute value, reported by the derived conversions
+    InvalidNamedAttributeType(String),
+
     #[error(transparent)]
     /// Invalid URI
     InvalidUri(#[from] InvalidUri),
@@ -0,0 +1,772 @@
+//!
+//! PWG IPP JSON encoding of attribute groups
+//!
+//! A message is encoded as a JSON object with one member per group, named after the group delimiter,
+//! e.g. `operation-attributes-tag` or `printer-attributes-tag`. Repeated groups such as the job groups
+//! of a Get-Jobs response become an array of objects. Each group object maps attribute names to values,
+//! 1setOf values are encoded as arrays.
+//!
+//! Values are typed by their syntax:
+//! * integer and enum values are numbers, booleans are `true`/`false`, no-value is `null`
+//! * keywords, names, texts, URIs, charsets, natural languages and MIME media types are strings
+//! * dateTime values are RFC 3339 strings, octetString values are base64 strings
+//! * rangeOfInteger is `{"lower": 1, "upper": 5}`, resolution is `{"xres": 600, "yres": 600, "units": "dpi"}`
+//! * textWithLanguage and nameWithLanguage are `{"value": "...", "lang": "en"}`
+//! * collections are objects
+//!
+//! The decoder uses the attribute registry to recover the value syntax of plain strings and numbers,
+//! including the members of registered collections such as `media-col`.
+//! Values whose syntax cannot be recovered this way, e.g. a name value of an unregistered attribute,
+//! are encoded as `{"type": "nameWithoutLanguage", "value": "..."}` so that every message round-trips exactly.
+//!
+//!```rust
+//! use ipp::{attribute::{IppAttribute, IppAttributes}, json, model::DelimiterTag, value::IppValue};
+//!
+//! let mut attributes = IppAttributes::new();
+//! attributes.add(
+//!     DelimiterTag::PrinterAttributes,
+//!     IppAttribute::with_name("printer-state", IppValue::Enum(3)).unwrap(),
+//! );
+//! let value = json::to_json(&attributes);
+//! assert_eq!(value["printer-attributes-tag"]["printer-state"], 3);
+//! assert_eq!(json::from_json(&value).unwrap().groups(), attributes.groups());
+//!```
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde_json::{Map, Value, json};
+
+use crate::{
+    FromPrimitive as _,
+    attribute::{IppAttribute, IppAttributeGroup, IppAttributes},
+    model::{DelimiterTag, ValueTag},
+    parser::IppParseError,
+    registry::{self, AttributeInfo, Syntax},
+    value::{IppCollection, IppDateTime, IppValue},
+};
+
+const GROUPS: [(DelimiterTag, &str); 9] = [
+    (DelimiterTag::OperationAttributes, "operation-attributes-tag"),
+    (DelimiterTag::JobAttributes, "job-attributes-tag"),
+    (DelimiterTag::PrinterAttributes, "printer-attributes-tag"),
+    (DelimiterTag::UnsupportedAttributes, "unsupported-attributes-tag"),
+    (DelimiterTag::SubscriptionAttributes, "subscription-attributes-tag"),
+    (
+        DelimiterTag::EventNotificationAttributes,
+        "event-notification-attributes-tag",
+    ),
+    (DelimiterTag::ResourceAttributes, "resource-attributes-tag"),
+    (DelimiterTag::DocumentAttributes, "document-attributes-tag"),
+    (DelimiterTag::SystemAttributes, "system-attributes-tag"),
+];
+
+/// Encode attribute groups into a JSON value
+pub fn to_json(attributes: &IppAttributes) -> Value {
+    let mut groups = Map::new();
+    for group in attributes.groups() {
+        let Some((_, name)) = GROUPS.iter().find(|(tag, _)| *tag == group.tag()) else {
+            continue;
+        };
+        let object = Value::Object(
+            group
+                .attributes()
+                .iter()
+                .map(|attr| (attr.name().to_string(), encode_attribute(attr)))
+                .collect(),
+        );
+        match groups.get_mut(*name) {
+            None => {
+                groups.insert((*name).to_owned(), object);
+            }
+            Some(Value::Array(list)) => list.push(object),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, object]);
+            }
+        }
+    }
+    Value::Object(groups)
+}
+
+/// Encode attribute groups into a JSON string
+pub fn to_json_string(attributes: &IppAttributes) -> String {
+    to_json(attributes).to_string()
+}
+
+/// Decode attribute groups from a JSON value.
+/// Groups and attributes keep the order of the JSON members, repeated groups encoded as an array stay adjacent.
+/// The operation group starts with `attributes-charset` and `attributes-natural-language`.
+pub fn from_json(value: &Value) -> Result<IppAttributes, IppParseError> {
+    let object = value.as_object().ok_or_else(|| invalid("message must be an object"))?;
+
+    let mut groups = Vec::new();
+    for (name, value) in object {
+        let (tag, _) = GROUPS
+            .iter()
+            .find(|(_, n)| n == name)
+            .ok_or_else(|| invalid(format!("unknown group: {name}")))?;
+        match value {
+            Value::Array(list) => {
+                for group in list {
+                    groups.push(decode_group(*tag, group)?);
+                }
+            }
+            group => groups.push(decode_group(*tag, group)?),
+        }
+    }
+
+    let mut attributes = IppAttributes::new();
+    *attributes.groups_mut() = groups;
+    Ok(attributes)
+}
+
+/// Decode attribute groups from a JSON string
+pub fn from_json_str(s: &str) -> Result<IppAttributes, IppParseError> {
+    from_json(&serde_json::from_str(s).map_err(|e| invalid(e.to_string()))?)
+}
+
+fn invalid(message: impl Into<String>) -> IppParseError {
+    IppParseError::InvalidJson(message.into())
+}
+
+fn decode_group(tag: DelimiterTag, value: &Value) -> Result<IppAttributeGroup, IppParseError> {
+    let object = value.as_object().ok_or_else(|| invalid("group must be an object"))?;
+    let mut group = IppAttributeGroup::new(tag);
+    for (name, value) in object {
+        group.attributes_mut().push(IppAttribute::new(
+            name.as_str().try_into()?,
+            decode_values(value, registry::lookup(name))?,
+        ));
+    }
+    if tag == DelimiterTag::OperationAttributes {
+        // the natural language is moved first, then the charset before it
+        for name in [
+            IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE,
+            IppAttribute::ATTRIBUTES_CHARSET,
+        ] {
+            if let Some(index) = group.attributes().iter().position(|a| a.name().as_str() == name) {
+                let attr = group.attributes_mut().remove(index);
+                group.attributes_mut().insert(0, attr);
+            }
+        }
+    }
+    Ok(group)
+}
+
+fn encode_attribute(attr: &IppAttribute) -> Value {
+    encode_values(attr.value(), registry::lookup(attr.name()))
+}
+
+// registry entry of a collection member
+fn member_info(info: Option<&AttributeInfo>, name: &str) -> Option<&'static AttributeInfo> {
+    info.and_then(|info| info.member(name))
+}
+
+fn encode_values(value: &IppValue, info: Option<&AttributeInfo>) -> Value {
+    match value {
+        IppValue::Array(values) => Value::Array(values.iter().map(|v| encode_value(v, info)).collect()),
+        value => encode_value(value, info),
+    }
+}
+
+fn decode_values(value: &Value, info: Option<&AttributeInfo>) -> Result<IppValue, IppParseError> {
+    match value {
+        Value::Array(values) => values
+            .iter()
+            .map(|v| decode_value(v, info))
+            .collect::<Result<_, _>>()
+            .map(IppValue::Array),
+        value => decode_value(value, info),
+    }
+}
+
+// use the plain form if it decodes back to the same value, the typed form otherwise
+fn encode_value(value: &IppValue, info: Option<&AttributeInfo>) -> Value {
+    if let IppValue::Encoded { .. } = value {
+        return encode_value(&value.decode_charset(), info);
+    }
+    match plain(value, info) {
+        Some(plain) if decode_value(&plain, info).is_ok_and(|decoded| decoded == *value) => plain,
+        _ => typed(value, info),
+    }
+}
+
+fn resolution_units(units: i8) -> Value {
+    match units {
+        3 => json!("dpi"),
+        4 => json!("dpcm"),
+        units => json!(units),
+    }
+}
+
+fn datetime_value(datetime: &IppDateTime) -> Value {
+    match datetime.validate() {
+        Ok(()) => json!(datetime.to_rfc3339()),
+        // keep the raw octets of invalid values
+        Err(_) => json!(STANDARD.encode(datetime.to_bytes())),
+    }
+}
+
+fn plain(value: &IppValue, info: Option<&AttributeInfo>) -> Option<Value> {
+    let value = match value {
+        IppValue::Integer(i) | IppValue::Enum(i) => json!(i),
+        IppValue::Boolean(b) => json!(b),
+        IppValue::NoValue => Value::Null,
+        IppValue::Keyword(s)
+        | IppValue::NameWithoutLanguage(s)
+        | IppValue::MimeMediaType(s)
+        | IppValue::MemberAttrName(s) => json!(s.as_str()),
+        IppValue::TextWithoutLanguage(text) => json!(text.as_ref()),
+        IppValue::Uri(s) | IppValue::UriScheme(s) => json!(s.as_str()),
+        IppValue::Charset(s) | IppValue::NaturalLanguage(s) => json!(s.as_str()),
+        IppValue::TextWithLanguage { language, text } => json!({"value": text.as_ref(), "lang": language.as_str()}),
+        IppValue::NameWithLanguage { language, name } => json!({"value": name.as_str(), "lang": language.as_str()}),
+        IppValue::RangeOfInteger { min, max } => json!({"lower": min, "upper": max}),
+        IppValue::Resolution {
+            cross_feed,
+            feed,
+            units,
+        } => json!({"xres": cross_feed, "yres": feed, "units": resolution_units(*units)}),
+        IppValue::DateTime(datetime) if datetime.validate().is_ok() => datetime_value(datetime),
+        IppValue::OctetString(data) => json!(STANDARD.encode(data)),
+        IppValue::Collection(collection) => Value::Object(
+            collection
+                .iter()
+                .map(|(name, value)| (name.to_string(), encode_values(value, member_info(info, name))))
+                .collect(),
+        ),
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn typed(value: &IppValue, info: Option<&AttributeInfo>) -> Value {
+    let (name, value) = match value {
+        IppValue::Integer(i) => ("integer", json!(i)),
+        IppValue::Enum(i) => ("enum", json!(i)),
+        IppValue::Boolean(b) => ("boolean", json!(b)),
+        IppValue::Keyword(s) => ("keyword", json!(s.as_str())),
+        IppValue::NameWithoutLanguage(s) => ("nameWithoutLanguage", json!(s.as_str())),
+        IppValue::TextWithoutLanguage(text) => ("textWithoutLanguage", json!(text.as_ref())),
+        IppValue::NameWithLanguage { language, name } => {
+            return json!({"type": "nameWithLanguage", "value": name.as_str(), "lang": language.as_str()});
+        }
+        IppValue::TextWithLanguage { language, text } => {
+            return json!({"type": "textWithLanguage", "value": text.as_ref(), "lang": language.as_str()});
+        }
+        IppValue::Uri(s) => ("uri", json!(s.as_str())),
+        IppValue::UriScheme(s) => ("uriScheme", json!(s.as_str())),
+        IppValue::Charset(s) => ("charset", json!(s.as_str())),
+        IppValue::NaturalLanguage(s) => ("naturalLanguage", json!(s.as_str())),
+        IppValue::MimeMediaType(s) => ("mimeMediaType", json!(s.as_str())),
+        IppValue::MemberAttrName(s) => ("memberAttrName", json!(s.as_str())),
+        IppValue::RangeOfInteger { min, max } => ("rangeOfInteger", json!({"lower": min, "upper": max})),
+        IppValue::Resolution {
+            cross_feed,
+            feed,
+            units,
+        } => (
+            "resolution",
+            json!({"xres": cross_feed, "yres": feed, "units": resolution_units(*units)}),
+        ),
+        IppValue::DateTime(datetime) => ("dateTime", datetime_value(datetime)),
+        IppValue::OctetString(data) => ("octetString", json!(STANDARD.encode(data))),
+        // a list of pairs keeps duplicate member names
+        IppValue::Collection(collection) => (
+            "collection",
+            Value::Array(
+                collection
+                    .iter()
+                    .map(|(name, value)| json!([name.as_str(), encode_values(value, member_info(info, name))]))
+                    .collect(),
+            ),
+        ),
+        IppValue::Array(_) => return encode_values(value, info),
+        IppValue::NoValue => return json!({"type": "no-value"}),
+        IppValue::NotSettable => return json!({"type": "not-settable"}),
+        IppValue::DeleteAttribute => return json!({"type": "delete-attribute"}),
+        IppValue::AdminDefine => return json!({"type": "admin-define"}),
+        IppValue::Other { tag, data } => {
+            return json!({"type": "other", "tag": tag, "value": STANDARD.encode(data)});
+        }
+        IppValue::Extension { tag, data } => {
+            return json!({"type": "extension", "tag": tag, "value": STANDARD.encode(data)});
+        }
+        IppValue::NonUtf8 { tag, data } => {
+            return json!({"type": "nonutf8", "tag": *tag as u8, "value": STANDARD.encode(data)});
+        }
+        IppValue::Encoded { .. } => return typed(&value.decode_charset(), info),
+    };
+    json!({"type": name, "value": value})
+}
+
+// string syntaxes in the order of preference for plain strings
+fn string_syntax(syntax: Option<&[Syntax]>) -> Syntax {
+    syntax
+        .unwrap_or_default()
+        .iter()
+        .find(|s| {
+            matches!(
+                s,
+                Syntax::Keyword
+                    | Syntax::Name(_)
+                    | Syntax::Text(_)
+                    | Syntax::Uri
+                    | Syntax::UriScheme
+                    | Syntax::Charset
+                    | Syntax::NaturalLanguage
+                    | Syntax::MimeMediaType
+                    | Syntax::DateTime
+                    | Syntax::OctetString(_)
+            )
+        })
+        .copied()
+        .unwrap_or(Syntax::Keyword)
+}
+
+const TYPES: [&str; 26] = [
+    "integer",
+    "enum",
+    "boolean",
+    "keyword",
+    "nameWithoutLanguage",
+    "textWithoutLanguage",
+    "nameWithLanguage",
+    "textWithLanguage",
+    "uri",
+    "uriScheme",
+    "charset",
+    "naturalLanguage",
+    "mimeMediaType",
+    "memberAttrName",
+    "rangeOfInteger",
+    "resolution",
+    "dateTime",
+    "octetString",
+    "collection",
+    "no-value",
+    "not-settable",
+    "delete-attribute",
+    "admin-define",
+    "other",
+    "extension",
+    "nonutf8",
+];
+
+// a typed value, as opposed to a collection which has a "type" member
+fn is_typed(object: &Map<String, Value>) -> bool {
+    object
+        .get("type")
+        .and_then(Value::as_str)
+        .is_some_and(|name| TYPES.contains(&name))
+        && object
+            .keys()
+            .all(|key| matches!(key.as_str(), "type" | "value" | "lang" | "tag"))
+}
+
+fn has_keys(object: &Map<String, Value>, keys: &[&str]) -> bool {
+    object.len() == keys.len() && keys.iter().all(|key| object.contains_key(*key))
+}
+
+fn as_i32(value: Option<&Value>) -> Result<i32, IppParseError> {
+    value
+        .and_then(Value::as_i64)
+        .and_then(|v| i32::try_from(v).ok())
+        .ok_or_else(|| invalid(format!("expected a 32-bit integer: {value:?}")))
+}
+
+fn as_str(value: Option<&Value>) -> Result<&str, IppParseError> {
+    value
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid(format!("expected a string: {value:?}")))
+}
+
+fn as_bytes(value: Option<&Value>) -> Result<bytes::Bytes, IppParseError> {
+    STANDARD
+        .decode(as_str(value)?)
+        .map(Into::into)
+        .map_err(|e| invalid(e.to_string()))
+}
+
+fn as_tag<T: TryFrom<u64>>(value: Option<&Value>) -> Result<T, IppParseError> {
+    value
+        .and_then(Value::as_u64)
+        .and_then(|v| T::try_from(v).ok())
+        .ok_or_else(|| invalid(format!("invalid tag: {value:?}")))
+}
+
+fn decode_datetime(value: Option<&Value>) -> Result<IppValue, IppParseError> {
+    let s = as_str(value)?;
+    match IppDateTime::parse_rfc3339(s) {
+        Ok(datetime) => Ok(IppValue::DateTime(datetime)),
+        Err(_) => IppValue::parse(ValueTag::DateTime as u8, as_bytes(value)?),
+    }
+}
+
+fn decode_range(object: &Map<String, Value>) -> Result<IppValue, IppParseError> {
+    Ok(IppValue::RangeOfInteger {
+        min: as_i32(object.get("lower"))?,
+        max: as_i32(object.get("upper"))?,
+    })
+}
+
+fn decode_resolution(object: &Map<String, Value>) -> Result<IppValue, IppParseError> {
+    let units = match object.get("units") {
+        Some(Value::String(units)) if units == "dpi" => 3,
+        Some(Value::String(units)) if units == "dpcm" => 4,
+        units => i8::try_from(as_i32(units)?).map_err(|e| invalid(e.to_string()))?,
+    };
+    Ok(IppValue::Resolution {
+        cross_feed: as_i32(object.get("xres"))?,
+        feed: as_i32(object.get("yres"))?,
+        units,
+    })
+}
+
+fn decode_value(value: &Value, info: Option<&AttributeInfo>) -> Result<IppValue, IppParseError> {
+    let syntax = info.map(|info| info.syntax);
+    let syntax_of = |wanted: fn(&Syntax) -> bool| syntax.unwrap_or_default().iter().any(wanted);
+
+    match value {
+        Value::Null => Ok(IppValue::NoValue),
+        Value::Bool(b) => Ok(IppValue::Boolean(*b)),
+        Value::Number(_) => {
+            let i = as_i32(Some(value))?;
+            if syntax_of(|s| *s == Syntax::Enum) && !syntax_of(|s| matches!(s, Syntax::Integer { .. })) {
+                Ok(IppValue::Enum(i))
+            } else {
+                Ok(IppValue::Integer(i))
+            }
+        }
+        Value::String(s) => match string_syntax(syntax) {
+            Syntax::Name(_) => IppValue::new_name_without_language(s),
+            Syntax::Text(_) => IppValue::new_text_without_language(s),
+            Syntax::Uri => IppValue::new_uri(s),
+            Syntax::UriScheme => IppValue::new_uri_scheme(s),
+            Syntax::Charset => IppValue::new_charset(s),
+            Syntax::NaturalLanguage => IppValue::new_natural_language(s),
+            Syntax::MimeMediaType => IppValue::new_mime_media_type(s),
+            Syntax::DateTime => decode_datetime(Some(value)),
+            Syntax::OctetString(_) => Ok(IppValue::OctetString(as_bytes(Some(value))?)),
+            _ => IppValue::new_keyword(s),
+        },
+        Value::Array(_) => decode_values(value, info),
+        Value::Object(object) if is_typed(object) => decode_typed(object, info),
+        Value::Object(object) if has_keys(object, &["lower", "upper"]) => decode_range(object),
+        Value::Object(object) if has_keys(object, &["xres", "yres", "units"]) => decode_resolution(object),
+        Value::Object(object) if has_keys(object, &["value", "lang"]) => {
+            let value = as_str(object.get("value"))?;
+            let language = as_str(object.get("lang"))?;
+            if matches!(string_syntax(syntax), Syntax::Name(_)) {
+                IppValue::new_name_with_language(language, value)
+            } else {
+                IppValue::new_text_with_language(language, value)
+            }
+        }
+        Value::Object(object) => object
+            .iter()
+            .map(|(name, value)| {
+                Ok((
+                    name.as_str().try_into()?,
+                    decode_values(value, member_info(info, name))?,
+                ))
+            })
+            .collect::<Result<IppCollection, IppParseError>>()
+            .map(IppValue::Collection),
+    }
+}
+
+fn decode_typed(object: &Map<String, Value>, info: Option<&AttributeInfo>) -> Result<IppValue, IppParseError> {
+    let value = object.get("value");
+    let inner = || {
+        value
+            .and_then(Value::as_object)
+            .ok_or_else(|| invalid("expected an object"))
+    };
+
+    match as_str(object.get("type"))? {
+        "integer" => Ok(IppValue::Integer(as_i32(value)?)),
+        "enum" => Ok(IppValue::Enum(as_i32(value)?)),
+        "boolean" => value
+            .and_then(Value::as_bool)
+            .map(IppValue::Boolean)
+            .ok_or_else(|| invalid("expected a boolean")),
+        "keyword" => IppValue::new_keyword(as_str(value)?),
+        "nameWithoutLanguage" => IppValue::new_name_without_language(as_str(value)?),
+        "textWithoutLanguage" => IppValue::new_text_without_language(as_str(value)?),
+        "nameWithLanguage" => IppValue::new_name_with_language(as_str(object.get("lang"))?, as_str(value)?),
+        "textWithLanguage" => IppValue::new_text_with_language(as_str(object.get("lang"))?, as_str(value)?),
+        "uri" => IppValue::new_uri(as_str(value)?),
+        "uriScheme" => IppValue::new_uri_scheme(as_str(value)?),
+        "charset" => IppValue::new_charset(as_str(value)?),
+        "naturalLanguage" => IppValue::new_natural_language(as_str(value)?),
+        "mimeMediaType" => IppValue::new_mime_media_type(as_str(value)?),
+        "memberAttrName" => IppValue::new_member_attr_name(as_str(value)?),
+        "rangeOfInteger" => decode_range(inner()?),
+        "resolution" => decode_resolution(inner()?),
+        "dateTime" => decode_datetime(value),
+        "octetString" => Ok(IppValue::OctetString(as_bytes(value)?)),
+        "collection" => value
+            .and_then(Value::as_array)
+            .ok_or_else(|| invalid("expected a list of members"))?
+            .iter()
+            .map(|member| match member.as_array().map(Vec::as_slice) {
+                Some([name, value]) => {
+                    let name = as_str(Some(name))?;
+                    Ok((name.try_into()?, decode_values(value, member_info(info, name))?))
+                }
+                _ => Err(invalid("expected a [name, value] member")),
+            })
+            .collect::<Result<IppCollection, IppParseError>>()
+            .map(IppValue::Collection),
+        "no-value" => Ok(IppValue::NoValue),
+        "not-settable" => Ok(IppValue::NotSettable),
+        "delete-attribute" => Ok(IppValue::DeleteAttribute),
+        "admin-define" => Ok(IppValue::AdminDefine),
+        "other" => Ok(IppValue::Other {
+            tag: as_tag(object.get("tag"))?,
+            data: as_bytes(value)?,
+        }),
+        "extension" => Ok(IppValue::Extension {
+            tag: as_tag(object.get("tag"))?,
+            data: as_bytes(value)?,
+        }),
+        "nonutf8" => Ok(IppValue::NonUtf8 {
+            tag: ValueTag::from_u8(as_tag(object.get("tag"))?).ok_or_else(|| invalid("invalid value tag"))?,
+            data: as_bytes(value)?,
+        }),
+        other => Err(invalid(format!("unknown value type: {other}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+
+    fn attribute(name: &str, value: IppValue) -> IppAttribute {
+        IppAttribute::with_name(name, value).unwrap()
+    }
+
+    fn printer_attributes() -> IppAttributes {
+        let mut attributes = IppAttributes::new();
+        for attr in [
+            attribute(
+                IppAttribute::ATTRIBUTES_CHARSET,
+                IppValue::new_charset("utf-8").unwrap(),
+            ),
+            attribute(
+                IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE,
+                IppValue::new_natural_language("en").unwrap(),
+            ),
+        ] {
+            attributes.add(DelimiterTag::OperationAttributes, attr);
+        }
+        let media_col = IppCollection::from([(
+            "media-size".try_into().unwrap(),
+            IppValue::Collection(IppCollection::from([
+                ("x-dimension".try_into().unwrap(), IppValue::Integer(21000)),
+                ("y-dimension".try_into().unwrap(), IppValue::Integer(29700)),
+            ])),
+        )]);
+        for attr in [
+            attribute("printer-name", IppValue::new_name_without_language("office").unwrap()),
+            attribute(
+                "printer-location",
+                IppValue::new_text_with_language("de", "Büro").unwrap(),
+            ),
+            attribute("printer-state", IppValue::Enum(3)),
+            attribute(
+                "printer-state-reasons",
+                IppValue::Array(vec![IppValue::new_keyword("none").unwrap()]),
+            ),
+            attribute(
+                "printer-uri-supported",
+                IppValue::Array(vec![IppValue::new_uri("ipp://host/printer").unwrap()]),
+            ),
+            attribute("copies-supported", IppValue::new_range_of_integer(1, 99)),
+            attribute("printer-resolution-default", IppValue::new_resolution(600, 600, 3)),
+            attribute(
+                "printer-current-time",
+                IppValue::DateTime("2024-05-01T10:30:00.5+02:00".parse().unwrap()),
+            ),
+            attribute("media-col-default", IppValue::Collection(media_col)),
+            attribute("printer-is-accepting-jobs", IppValue::Boolean(true)),
+        ] {
+            attributes.add(DelimiterTag::PrinterAttributes, attr);
+        }
+        attributes
+    }
+
+    #[test]
+    fn test_encode_plain_values() {
+        let value = to_json(&printer_attributes());
+        assert_eq!(
+            value,
+            json!({
+                "operation-attributes-tag": {
+                    "attributes-charset": "utf-8",
+                    "attributes-natural-language": "en",
+                },
+                "printer-attributes-tag": {
+                    "printer-name": "office",
+                    "printer-location": {"value": "Büro", "lang": "de"},
+                    "printer-state": 3,
+                    "printer-state-reasons": ["none"],
+                    "printer-uri-supported": ["ipp://host/printer"],
+                    "copies-supported": {"lower": 1, "upper": 99},
+                    "printer-resolution-default": {"xres": 600, "yres": 600, "units": "dpi"},
+                    "printer-current-time": "2024-05-01T10:30:00.5+02:00",
+                    "media-col-default": {"media-size": {"x-dimension": 21000, "y-dimension": 29700}},
+                    "printer-is-accepting-jobs": true,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let attributes = printer_attributes();
+        assert_eq!(from_json_str(&to_json_string(&attributes)).unwrap(), attributes);
+    }
+
+    #[test]
+    fn test_typed_values() {
+        let duplicates = IppCollection::from([
+            ("type".try_into().unwrap(), IppValue::new_keyword("a").unwrap()),
+            ("type".try_into().unwrap(), IppValue::new_keyword("b").unwrap()),
+        ]);
+        let values = [
+            (IppValue::Enum(5), json!({"type": "enum", "value": 5})),
+            (
+                IppValue::new_name_without_language("x").unwrap(),
+                json!({"type": "nameWithoutLanguage", "value": "x"}),
+            ),
+            (
+                IppValue::new_name_with_language("en", "x").unwrap(),
+                json!({"type": "nameWithLanguage", "value": "x", "lang": "en"}),
+            ),
+            (
+                IppValue::new_mime_media_type("application/pdf").unwrap(),
+                json!({"type": "mimeMediaType", "value": "application/pdf"}),
+            ),
+            (
+                IppValue::new_resolution(1, 2, 7),
+                json!({"xres": 1, "yres": 2, "units": 7}),
+            ),
+            (IppValue::NotSettable, json!({"type": "not-settable"})),
+            (IppValue::NoValue, Value::Null),
+            (
+                IppValue::OctetString(Bytes::from_static(b"\x00\x01")),
+                json!({"type": "octetString", "value": "AAE="}),
+            ),
+            (
+                IppValue::Other {
+                    tag: 0x12,
+                    data: Bytes::new(),
+                },
+                json!({"type": "other", "tag": 0x12, "value": ""}),
+            ),
+            (
+                IppValue::Collection(duplicates),
+                json!({"type": "collection", "value": [["type", "a"], ["type", "b"]]}),
+            ),
+            (
+                IppValue::DateTime(IppDateTime {
+                    month: 13,
+                    ..IppDateTime::new(2024, 1, 1, 0, 0, 0, 0).unwrap()
+                }),
+                json!({"type": "dateTime", "value": "B+gNAQAAAAArAAA="}),
+            ),
+            (IppValue::Array(vec![]), json!([])),
+        ];
+
+        for (value, expected) in values {
+            let mut attributes = IppAttributes::new();
+            attributes.add(DelimiterTag::JobAttributes, attribute("x-vendor", value));
+            let encoded = to_json(&attributes);
+            assert_eq!(encoded["job-attributes-tag"]["x-vendor"], expected);
+            assert_eq!(from_json(&encoded).unwrap().groups(), attributes.groups());
+        }
+    }
+
+    #[test]
+    fn test_collection_members() {
+        // plain PWG JSON as sent by a printer, the member syntax comes from the registry
+        let json = json!({
+            "printer-attributes-tag": {
+                "media-col-database": [{
+                    "media-key": "iso_a4_210x297mm_plain",
+                    "media-info": "Plain paper",
+                    "media-size": {"x-dimension": 21000, "y-dimension": 29700},
+                    "media-source": "tray-1",
+                    "media-source-properties": {
+                        "media-source-feed-direction": "short-edge-first",
+                        "media-source-feed-orientation": 3,
+                    },
+                    "media-top-margin": 423,
+                }],
+            },
+        });
+        let media_size = IppCollection::from([
+            ("x-dimension".try_into().unwrap(), IppValue::Integer(21000)),
+            ("y-dimension".try_into().unwrap(), IppValue::Integer(29700)),
+        ]);
+        let properties = IppCollection::from([
+            (
+                "media-source-feed-direction".try_into().unwrap(),
+                IppValue::new_keyword("short-edge-first").unwrap(),
+            ),
+            ("media-source-feed-orientation".try_into().unwrap(), IppValue::Enum(3)),
+        ]);
+        let media_col = IppCollection::from([
+            (
+                "media-key".try_into().unwrap(),
+                IppValue::new_keyword("iso_a4_210x297mm_plain").unwrap(),
+            ),
+            (
+                "media-info".try_into().unwrap(),
+                IppValue::new_text_without_language("Plain paper").unwrap(),
+            ),
+            ("media-size".try_into().unwrap(), IppValue::Collection(media_size)),
+            (
+                "media-source".try_into().unwrap(),
+                IppValue::new_keyword("tray-1").unwrap(),
+            ),
+            (
+                "media-source-properties".try_into().unwrap(),
+                IppValue::Collection(properties),
+            ),
+            ("media-top-margin".try_into().unwrap(), IppValue::Integer(423)),
+        ]);
+
+        let attributes = from_json(&json).unwrap();
+        let group = attributes.first_of(DelimiterTag::PrinterAttributes).unwrap();
+        assert_eq!(
+            group.get("media-col-database").unwrap().value(),
+            &IppValue::Array(vec![IppValue::Collection(media_col)])
+        );
+        assert_eq!(to_json(&attributes), json);
+    }
+
+    #[test]
+    fn test_repeated_groups() {
+        let mut attributes = IppAttributes::new();
+        for id in [1, 2] {
+            let mut group = IppAttributeGroup::new(DelimiterTag::JobAttributes);
+            group.attributes_mut().push(attribute("job-id", IppValue::Integer(id)));
+            attributes.groups_mut().push(group);
+        }
+        let encoded = to_json(&attributes);
+        assert_eq!(encoded, json!({"job-attributes-tag": [{"job-id": 1}, {"job-id": 2}]}));
+        assert_eq!(from_json(&encoded).unwrap().groups(), attributes.groups());
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert!(from_json(&json!([])).is_err());
+        assert!(from_json(&json!({"bogus-tag": {}})).is_err());
+        assert!(from_json(&json!({"job-attributes-tag": {"copies": 1.5}})).is_err());
+        assert!(from_json(&json!({"job-attributes-tag": {"copies": {"type": "integer", "value": "x"}}})).is_err());
+        assert!(from_json_str("{").is_err());
+    }
+}
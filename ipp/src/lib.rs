@@ -13,6 +13,7 @@
 //! * `client` - enables a blocking IPP client based on `ureq` crate.
 //! * `client-rustls` - enables a blocking IPP client with TLS, using `rustls` backend. Implies `client` feature.
 //! * `client-tls` - enables a blocking IPP client with TLS, using `native-tls` backend. Implies `client` feature.
+//! * `derive` - enables `#[derive(IppAttributes)]` and `#[derive(IppCollection)]` macros from the `ipp-derive` crate.
 //!
 //! By default, the `async-client-rustls` feature is enabled. Some old printers may not support the latest TLS standards;
 //! in that case you can choose to use `async-client-tls` or `client-tls`, which will use platform-specific `native-tls`.
@@ -69,18 +70,28 @@ use serde::{Deserialize, Serialize};
 use crate::model::{IppVersion, StatusCode};
 
 pub mod attribute;
+pub mod charset;
 #[cfg(any(feature = "client", feature = "async-client"))]
 pub mod client;
+pub mod convert;
 pub mod error;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod model;
+pub mod notation;
 pub mod operation;
 pub mod parser;
 pub mod payload;
 pub mod reader;
+pub mod registry;
 pub mod request;
 pub mod util;
+pub mod validate;
 pub mod value;
 
+#[cfg(feature = "derive")]
+pub use ipp_derive::{IppAttributes, IppCollection};
+
 pub mod prelude {
     //!
     //! Common imports
@@ -95,6 +106,7 @@ pub mod prelude {
     pub use super::{IppHeader, error::IppError};
     pub use crate::{
         attribute::{IppAttribute, IppAttributeGroup, IppAttributes},
+        convert::{FromAttributeGroup, FromIppValue, ToAttributeGroup, ToIppValue},
         model::*,
         operation::builder::IppOperationBuilder,
         payload::IppPayload,
@@ -137,7 +149,7 @@ impl IppHeader {
 
     /// Decode and get the IPP status code from the header
     pub fn status_code(&self) -> StatusCode {
-        StatusCode::from_i16(self.operation_or_status).unwrap_or(StatusCode::UnknownStatusCode)
+        StatusCode::from_code(self.operation_or_status)
     }
 }
 
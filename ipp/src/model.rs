@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 
 /// IPP protocol version
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct IppVersion(pub u16);
 
 impl IppVersion {
@@ -33,6 +33,19 @@ impl IppVersion {
     pub const fn v2_2() -> Self {
         IppVersion(0x0202)
     }
+
+    /// Return the closest well-known IPP version which is lower than this one
+    pub fn downgrade(&self) -> Option<IppVersion> {
+        [
+            IppVersion::v2_2(),
+            IppVersion::v2_1(),
+            IppVersion::v2_0(),
+            IppVersion::v1_1(),
+            IppVersion::v1_0(),
+        ]
+        .into_iter()
+        .find(|version| version < self)
+    }
 }
 
 impl fmt::Display for IppVersion {
@@ -47,6 +60,14 @@ fn test_version_display() {
     assert_eq!(&IppVersion(0x0305).to_string(), "IPP v3.5");
 }
 
+#[test]
+fn test_version_downgrade() {
+    assert_eq!(IppVersion::v2_0().downgrade(), Some(IppVersion::v1_1()));
+    assert_eq!(IppVersion::v1_1().downgrade(), Some(IppVersion::v1_0()));
+    assert_eq!(IppVersion(0x0300).downgrade(), Some(IppVersion::v2_2()));
+    assert_eq!(IppVersion::v1_0().downgrade(), None);
+}
+
 /// IPP operation constants
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Primitive, Debug, Copy, Clone, Eq, PartialEq)]
@@ -169,6 +190,385 @@ pub enum JobState {
     Completed = 9,
 }
 
+macro_rules! keyword_enum {
+    ($(#[$meta:meta])* $enum:ident { $($name:ident => $keyword:literal),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $enum {
+            $($name,)*
+            /// Keyword which is not known to this crate
+            Other(String),
+        }
+
+        impl $enum {
+            /// Return the IPP keyword
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($enum::$name => $keyword,)*
+                    $enum::Other(keyword) => keyword,
+                }
+            }
+        }
+
+        impl From<&str> for $enum {
+            fn from(keyword: &str) -> Self {
+                match keyword {
+                    $($keyword => $enum::$name,)*
+                    other => $enum::Other(other.to_owned()),
+                }
+            }
+        }
+
+        impl fmt::Display for $enum {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+keyword_enum! {
+    /// printer-state-reasons keywords without the severity suffix (RFC 8011 and PWG 5100.9)
+    PrinterStateReason {
+        None => "none",
+        AlertRemovalOfBinaryChangeEntry => "alert-removal-of-binary-change-entry",
+        BinderAdded => "binder-added",
+        BinderAlmostEmpty => "binder-almost-empty",
+        BinderAlmostFull => "binder-almost-full",
+        BinderAtLimit => "binder-at-limit",
+        BinderClosed => "binder-closed",
+        BinderConfigurationChange => "binder-configuration-change",
+        BinderCoverClosed => "binder-cover-closed",
+        BinderCoverOpen => "binder-cover-open",
+        BinderEmpty => "binder-empty",
+        BinderFull => "binder-full",
+        BinderInterlockClosed => "binder-interlock-closed",
+        BinderInterlockOpen => "binder-interlock-open",
+        BinderJam => "binder-jam",
+        BinderLifeAlmostOver => "binder-life-almost-over",
+        BinderLifeOver => "binder-life-over",
+        BinderMemoryExhausted => "binder-memory-exhausted",
+        BinderMissing => "binder-missing",
+        BinderMotorFailure => "binder-motor-failure",
+        BinderNearLimit => "binder-near-limit",
+        BinderOffline => "binder-offline",
+        BinderOpened => "binder-opened",
+        BinderOverTemperature => "binder-over-temperature",
+        BinderPowerSaver => "binder-power-saver",
+        BinderRecoverableFailure => "binder-recoverable-failure",
+        BinderRecoverableStorage => "binder-recoverable-storage",
+        BinderRemoved => "binder-removed",
+        BinderResourceAdded => "binder-resource-added",
+        BinderResourceRemoved => "binder-resource-removed",
+        BinderThermistorFailure => "binder-thermistor-failure",
+        BinderTimingFailure => "binder-timing-failure",
+        BinderTurnedOff => "binder-turned-off",
+        BinderTurnedOn => "binder-turned-on",
+        BinderUnderTemperature => "binder-under-temperature",
+        BinderUnrecoverableFailure => "binder-unrecoverable-failure",
+        BinderUnrecoverableStorageError => "binder-unrecoverable-storage-error",
+        BinderWarmingUp => "binder-warming-up",
+        CleanerLifeAlmostOver => "cleaner-life-almost-over",
+        CleanerLifeOver => "cleaner-life-over",
+        ConfigurationChange => "configuration-change",
+        ConnectingToDevice => "connecting-to-device",
+        CoverOpen => "cover-open",
+        Deactivated => "deactivated",
+        Deleted => "deleted",
+        DeveloperEmpty => "developer-empty",
+        DeveloperLow => "developer-low",
+        DoorOpen => "door-open",
+        FuserOverTemp => "fuser-over-temp",
+        FuserUnderTemp => "fuser-under-temp",
+        HoldNewJobs => "hold-new-jobs",
+        IdentifyPrinterRequested => "identify-printer-requested",
+        InputTrayMissing => "input-tray-missing",
+        InterlockOpen => "interlock-open",
+        InterpreterResourceUnavailable => "interpreter-resource-unavailable",
+        MarkerSupplyEmpty => "marker-supply-empty",
+        MarkerSupplyLow => "marker-supply-low",
+        MarkerWasteAlmostFull => "marker-waste-almost-full",
+        MarkerWasteFull => "marker-waste-full",
+        MediaDrying => "media-drying",
+        MediaEmpty => "media-empty",
+        MediaJam => "media-jam",
+        MediaLow => "media-low",
+        MediaNeeded => "media-needed",
+        MovingToPaused => "moving-to-paused",
+        Offline => "offline",
+        OpcLifeOver => "opc-life-over",
+        OpcNearEol => "opc-near-eol",
+        OtherReason => "other",
+        OutputAreaAlmostFull => "output-area-almost-full",
+        OutputAreaFull => "output-area-full",
+        OutputTrayMissing => "output-tray-missing",
+        Paused => "paused",
+        PowerDown => "power-down",
+        PowerUp => "power-up",
+        PrinterManualReset => "printer-manual-reset",
+        PrinterNmsReset => "printer-nms-reset",
+        PrinterReadyToPrint => "printer-ready-to-print",
+        Shutdown => "shutdown",
+        SpoolAreaFull => "spool-area-full",
+        StackerAdded => "stacker-added",
+        StackerAlmostEmpty => "stacker-almost-empty",
+        StackerAlmostFull => "stacker-almost-full",
+        StackerAtLimit => "stacker-at-limit",
+        StackerClosed => "stacker-closed",
+        StackerConfigurationChange => "stacker-configuration-change",
+        StackerCoverClosed => "stacker-cover-closed",
+        StackerCoverOpen => "stacker-cover-open",
+        StackerEmpty => "stacker-empty",
+        StackerFull => "stacker-full",
+        StackerInterlockClosed => "stacker-interlock-closed",
+        StackerInterlockOpen => "stacker-interlock-open",
+        StackerJam => "stacker-jam",
+        StackerLifeAlmostOver => "stacker-life-almost-over",
+        StackerLifeOver => "stacker-life-over",
+        StackerMemoryExhausted => "stacker-memory-exhausted",
+        StackerMissing => "stacker-missing",
+        StackerMotorFailure => "stacker-motor-failure",
+        StackerNearLimit => "stacker-near-limit",
+        StackerOffline => "stacker-offline",
+        StackerOpened => "stacker-opened",
+        StackerOverTemperature => "stacker-over-temperature",
+        StackerPowerSaver => "stacker-power-saver",
+        StackerRecoverableFailure => "stacker-recoverable-failure",
+        StackerRecoverableStorage => "stacker-recoverable-storage",
+        StackerRemoved => "stacker-removed",
+        StackerResourceAdded => "stacker-resource-added",
+        StackerResourceRemoved => "stacker-resource-removed",
+        StackerThermistorFailure => "stacker-thermistor-failure",
+        StackerTimingFailure => "stacker-timing-failure",
+        StackerTurnedOff => "stacker-turned-off",
+        StackerTurnedOn => "stacker-turned-on",
+        StackerUnderTemperature => "stacker-under-temperature",
+        StackerUnrecoverableFailure => "stacker-unrecoverable-failure",
+        StackerUnrecoverableStorageError => "stacker-unrecoverable-storage-error",
+        StackerWarmingUp => "stacker-warming-up",
+        StaplerAdded => "stapler-added",
+        StaplerAlmostEmpty => "stapler-almost-empty",
+        StaplerAlmostFull => "stapler-almost-full",
+        StaplerAtLimit => "stapler-at-limit",
+        StaplerClosed => "stapler-closed",
+        StaplerConfigurationChange => "stapler-configuration-change",
+        StaplerCoverClosed => "stapler-cover-closed",
+        StaplerCoverOpen => "stapler-cover-open",
+        StaplerEmpty => "stapler-empty",
+        StaplerFull => "stapler-full",
+        StaplerInterlockClosed => "stapler-interlock-closed",
+        StaplerInterlockOpen => "stapler-interlock-open",
+        StaplerJam => "stapler-jam",
+        StaplerLifeAlmostOver => "stapler-life-almost-over",
+        StaplerLifeOver => "stapler-life-over",
+        StaplerMemoryExhausted => "stapler-memory-exhausted",
+        StaplerMissing => "stapler-missing",
+        StaplerMotorFailure => "stapler-motor-failure",
+        StaplerNearLimit => "stapler-near-limit",
+        StaplerOffline => "stapler-offline",
+        StaplerOpened => "stapler-opened",
+        StaplerOverTemperature => "stapler-over-temperature",
+        StaplerPowerSaver => "stapler-power-saver",
+        StaplerRecoverableFailure => "stapler-recoverable-failure",
+        StaplerRecoverableStorage => "stapler-recoverable-storage",
+        StaplerRemoved => "stapler-removed",
+        StaplerResourceAdded => "stapler-resource-added",
+        StaplerResourceRemoved => "stapler-resource-removed",
+        StaplerThermistorFailure => "stapler-thermistor-failure",
+        StaplerTimingFailure => "stapler-timing-failure",
+        StaplerTurnedOff => "stapler-turned-off",
+        StaplerTurnedOn => "stapler-turned-on",
+        StaplerUnderTemperature => "stapler-under-temperature",
+        StaplerUnrecoverableFailure => "stapler-unrecoverable-failure",
+        StaplerUnrecoverableStorageError => "stapler-unrecoverable-storage-error",
+        StaplerWarmingUp => "stapler-warming-up",
+        StoppedPartly => "stopped-partly",
+        Stopping => "stopping",
+        TimedOut => "timed-out",
+        TonerEmpty => "toner-empty",
+        TonerLow => "toner-low",
+    }
+}
+
+keyword_enum! {
+    /// job-state-reasons keywords
+    JobStateReason {
+        None => "none",
+        AbortedBySystem => "aborted-by-system",
+        AccountAuthorizationFailed => "account-authorization-failed",
+        AccountClosed => "account-closed",
+        AccountInfoNeeded => "account-info-needed",
+        AccountLimitReached => "account-limit-reached",
+        CompressionError => "compression-error",
+        ConflictingAttributes => "conflicting-attributes",
+        DigitalSignatureDidNotVerify => "digital-signature-did-not-verify",
+        DigitalSignatureTypeNotSupported => "digital-signature-type-not-supported",
+        DocumentAccessError => "document-access-error",
+        DocumentFormatError => "document-format-error",
+        DocumentPasswordError => "document-password-error",
+        DocumentPermissionError => "document-permission-error",
+        DocumentSecurityError => "document-security-error",
+        DocumentUnprintableError => "document-unprintable-error",
+        ErrorsDetected => "errors-detected",
+        JobCanceledAfterTimeout => "job-canceled-after-timeout",
+        JobCanceledAtDevice => "job-canceled-at-device",
+        JobCanceledByOperator => "job-canceled-by-operator",
+        JobCanceledByUser => "job-canceled-by-user",
+        JobCompletedSuccessfully => "job-completed-successfully",
+        JobCompletedWithErrors => "job-completed-with-errors",
+        JobCompletedWithWarnings => "job-completed-with-warnings",
+        JobDataInsufficient => "job-data-insufficient",
+        JobDelayOutputUntilSpecified => "job-delay-output-until-specified",
+        JobDigitalSignatureWait => "job-digital-signature-wait",
+        JobFetchable => "job-fetchable",
+        JobHoldUntilSpecified => "job-hold-until-specified",
+        JobIncoming => "job-incoming",
+        JobInterpreting => "job-interpreting",
+        JobOutgoing => "job-outgoing",
+        JobPasswordWait => "job-password-wait",
+        JobPrintedSuccessfully => "job-printed-successfully",
+        JobPrintedWithErrors => "job-printed-with-errors",
+        JobPrintedWithWarnings => "job-printed-with-warnings",
+        JobPrinting => "job-printing",
+        JobQueued => "job-queued",
+        JobQueuedForMarker => "job-queued-for-marker",
+        JobReleaseWait => "job-release-wait",
+        JobRestartable => "job-restartable",
+        JobResuming => "job-resuming",
+        JobSavedSuccessfully => "job-saved-successfully",
+        JobSavedWithErrors => "job-saved-with-errors",
+        JobSavedWithWarnings => "job-saved-with-warnings",
+        JobSaving => "job-saving",
+        JobSpooling => "job-spooling",
+        JobStreaming => "job-streaming",
+        JobSuspended => "job-suspended",
+        JobSuspendedByOperator => "job-suspended-by-operator",
+        JobSuspendedBySystem => "job-suspended-by-system",
+        JobSuspendedByUser => "job-suspended-by-user",
+        JobSuspending => "job-suspending",
+        JobTransferring => "job-transferring",
+        JobTransforming => "job-transforming",
+        PrinterStopped => "printer-stopped",
+        PrinterStoppedPartly => "printer-stopped-partly",
+        ProcessingToStopPoint => "processing-to-stop-point",
+        QueuedInDevice => "queued-in-device",
+        ResourcesAreNotReady => "resources-are-not-ready",
+        ResourcesAreNotSupported => "resources-are-not-supported",
+        ServiceOffLine => "service-off-line",
+        SubmissionInterrupted => "submission-interrupted",
+        UnsupportedAttributesOrValues => "unsupported-attributes-or-values",
+        UnsupportedCompression => "unsupported-compression",
+        UnsupportedDocumentFormat => "unsupported-document-format",
+        WarningsDetected => "warnings-detected",
+    }
+}
+
+/// Severity of a printer-state-reasons value, ordered from the least to the most severe
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Report,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Return the keyword suffix for this severity
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Severity::Report => "-report",
+            Severity::Warning => "-warning",
+            Severity::Error => "-error",
+        }
+    }
+}
+
+/// A single printer-state-reasons value: the reason and the optional severity suffix
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrinterStateReasonValue {
+    /// Reason keyword without the suffix
+    pub reason: PrinterStateReason,
+    /// Severity suffix as found in the keyword
+    pub suffix: Option<Severity>,
+}
+
+impl PrinterStateReasonValue {
+    /// Parse a printer-state-reasons keyword, e.g. "media-empty-error"
+    pub fn parse(keyword: &str) -> Self {
+        [Severity::Report, Severity::Warning, Severity::Error]
+            .into_iter()
+            .find_map(|severity| {
+                keyword
+                    .strip_suffix(severity.suffix())
+                    .map(|reason| PrinterStateReasonValue {
+                        reason: reason.into(),
+                        suffix: Some(severity),
+                    })
+            })
+            .unwrap_or_else(|| PrinterStateReasonValue {
+                reason: keyword.into(),
+                suffix: None,
+            })
+    }
+
+    /// Return the severity of the reason.
+    /// Per RFC 8011 a reason without a suffix is an error, except for "none" which is reported only.
+    pub fn severity(&self) -> Severity {
+        match (self.suffix, &self.reason) {
+            (Some(severity), _) => severity,
+            (None, PrinterStateReason::None) => Severity::Report,
+            (None, _) => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for PrinterStateReasonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            self.reason,
+            self.suffix.map(|severity| severity.suffix()).unwrap_or_default()
+        )
+    }
+}
+
+#[test]
+fn test_printer_state_reason() {
+    let value = PrinterStateReasonValue::parse("toner-low-warning");
+    assert_eq!(value.reason, PrinterStateReason::TonerLow);
+    assert_eq!(value.severity(), Severity::Warning);
+    assert_eq!(value.to_string(), "toner-low-warning");
+
+    let value = PrinterStateReasonValue::parse("paused");
+    assert_eq!(value.reason, PrinterStateReason::Paused);
+    assert_eq!(value.suffix, None);
+    assert_eq!(value.severity(), Severity::Error);
+
+    let value = PrinterStateReasonValue::parse("none");
+    assert_eq!(value.severity(), Severity::Report);
+
+    let value = PrinterStateReasonValue::parse("cups-waiting-for-job-completed");
+    assert_eq!(value.suffix, None);
+    assert_eq!(value.severity(), Severity::Error);
+
+    let value = PrinterStateReasonValue::parse("binder-jam-error");
+    assert_eq!(value.reason, PrinterStateReason::BinderJam);
+    assert_eq!(value.severity(), Severity::Error);
+
+    let value = PrinterStateReasonValue::parse("com.vendor-thing-report");
+    assert_eq!(value.reason, PrinterStateReason::Other("com.vendor-thing".to_owned()));
+    assert_eq!(value.to_string(), "com.vendor-thing-report");
+}
+
+#[test]
+fn test_job_state_reason() {
+    assert_eq!(
+        JobStateReason::from("job-completed-successfully"),
+        JobStateReason::JobCompletedSuccessfully
+    );
+    assert_eq!(JobStateReason::from("x-custom").as_str(), "x-custom");
+}
+
 /// group delimiter tags
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Primitive, Debug, Copy, Clone, PartialEq, Hash, Eq)]
@@ -178,6 +578,11 @@ pub enum DelimiterTag {
     EndOfAttributes = 0x03,
     PrinterAttributes = 0x04,
     UnsupportedAttributes = 0x05,
+    SubscriptionAttributes = 0x06,
+    EventNotificationAttributes = 0x07,
+    ResourceAttributes = 0x08,
+    DocumentAttributes = 0x09,
+    SystemAttributes = 0x0A,
 }
 
 /// IPP value tags
@@ -187,6 +592,9 @@ pub enum ValueTag {
     Unsupported = 0x10,
     Unknown = 0x12,
     NoValue = 0x13,
+    NotSettable = 0x15,
+    DeleteAttribute = 0x16,
+    AdminDefine = 0x17,
     Integer = 0x21,
     Boolean = 0x22,
     Enum = 0x23,
@@ -207,96 +615,188 @@ pub enum ValueTag {
     NaturalLanguage = 0x48,
     MimeMediaType = 0x49,
     MemberAttrName = 0x4a,
+    Extension = 0x7f,
 }
 
-/// IPP status codes
-#[derive(Primitive, Debug, Copy, Clone, Eq, PartialEq)]
-pub enum StatusCode {
-    SuccessfulOk = 0x0000,
-    SuccessfulOkIgnoredOrSubstitutedAttributes = 0x0001,
-    SuccessfulOkConflictingAttributes = 0x0002,
-    ClientErrorBadRequest = 0x0400,
-    ClientErrorForbidden = 0x0401,
-    ClientErrorNotAuthenticated = 0x0402,
-    ClientErrorNotAuthorized = 0x0403,
-    ClientErrorNotPossible = 0x0404,
-    ClientErrorTimeout = 0x0405,
-    ClientErrorNotFound = 0x0406,
-    ClientErrorGone = 0x0407,
-    ClientErrorRequestEntityTooLong = 0x0408,
-    ClientErrorRequestValueTooLong = 0x0409,
-    ClientErrorDocumentFormatNotSupported = 0x040A,
-    ClientErrorAttributesOrValuesNotSupported = 0x040B,
-    ClientErrorUriSchemeNotSupported = 0x040C,
-    ClientErrorCharsetNotSupported = 0x040D,
-    ClientErrorConflictingAttributes = 0x040E,
-    ClientErrorCompressionNotSupported = 0x040F,
-    ClientErrorCompressionError = 0x0410,
-    ClientErrorDocumentFormatError = 0x0411,
-    ClientErrorDocumentAccessError = 0x0412,
-    ServerErrorInternalError = 0x0500,
-    ServerErrorOperationNotSupported = 0x0501,
-    ServerErrorServiceUnavailable = 0x0502,
-    ServerErrorVersionNotSupported = 0x0503,
-    ServerErrorDeviceError = 0x0504,
-    ServerErrorTemporaryError = 0x0505,
-    ServerErrorNotAcceptingJobs = 0x0506,
-    ServerErrorBusy = 0x0507,
-    ServerErrorJobCanceled = 0x0508,
-    ServerErrorMultipleDocumentJobsNotSupported = 0x0509,
-    UnknownStatusCode = 0xffff,
+macro_rules! status_codes {
+    ($($name:ident = $code:literal => $text:literal),* $(,)?) => {
+        /// IPP status codes as registered by IANA.
+        /// Codes which are not known to this crate are kept as `Unknown` with the raw value.
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+        pub enum StatusCode {
+            $($name,)*
+            /// Unregistered or vendor-specific status code
+            Unknown(i16),
+        }
+
+        impl StatusCode {
+            /// Create a status code from the raw value
+            pub const fn from_code(code: i16) -> StatusCode {
+                match code {
+                    $($code => StatusCode::$name,)*
+                    other => StatusCode::Unknown(other),
+                }
+            }
+
+            /// Return the raw status code value
+            pub const fn code(&self) -> i16 {
+                match self {
+                    $(StatusCode::$name => $code,)*
+                    StatusCode::Unknown(code) => *code,
+                }
+            }
+
+            fn description(&self) -> Option<&'static str> {
+                match self {
+                    $(StatusCode::$name => Some($text),)*
+                    StatusCode::Unknown(_) => None,
+                }
+            }
+        }
+    };
+}
+
+status_codes! {
+    SuccessfulOk = 0x0000 => "No error",
+    SuccessfulOkIgnoredOrSubstitutedAttributes = 0x0001 => "Ignored or substituted attributes",
+    SuccessfulOkConflictingAttributes = 0x0002 => "Conflicting attributes",
+    SuccessfulOkIgnoredSubscriptions = 0x0003 => "Ignored subscriptions",
+    SuccessfulOkIgnoredNotifications = 0x0004 => "Ignored notifications",
+    SuccessfulOkTooManyEvents = 0x0005 => "Too many events",
+    SuccessfulOkButCannotSendNotification = 0x0006 => "Cannot send notification",
+    SuccessfulOkEventsComplete = 0x0007 => "Events complete",
+    ClientErrorBadRequest = 0x0400 => "Bad request",
+    ClientErrorForbidden = 0x0401 => "Forbidden",
+    ClientErrorNotAuthenticated = 0x0402 => "Not authenticated",
+    ClientErrorNotAuthorized = 0x0403 => "Not authorized",
+    ClientErrorNotPossible = 0x0404 => "Not possible",
+    ClientErrorTimeout = 0x0405 => "Timeout",
+    ClientErrorNotFound = 0x0406 => "Not found",
+    ClientErrorGone = 0x0407 => "Gone",
+    ClientErrorRequestEntityTooLong = 0x0408 => "Entity too long",
+    ClientErrorRequestValueTooLong = 0x0409 => "Request value too long",
+    ClientErrorDocumentFormatNotSupported = 0x040A => "Document format not supported",
+    ClientErrorAttributesOrValuesNotSupported = 0x040B => "Attributes or values not supported",
+    ClientErrorUriSchemeNotSupported = 0x040C => "Uri scheme not supported",
+    ClientErrorCharsetNotSupported = 0x040D => "Charset not supported",
+    ClientErrorConflictingAttributes = 0x040E => "Conflicting attributes",
+    ClientErrorCompressionNotSupported = 0x040F => "Compression not supported",
+    ClientErrorCompressionError = 0x0410 => "Compression error",
+    ClientErrorDocumentFormatError = 0x0411 => "Document format error",
+    ClientErrorDocumentAccessError = 0x0412 => "Document access error",
+    ClientErrorAttributesNotSettable = 0x0413 => "Attributes not settable",
+    ClientErrorIgnoredAllSubscriptions = 0x0414 => "Ignored all subscriptions",
+    ClientErrorTooManySubscriptions = 0x0415 => "Too many subscriptions",
+    ClientErrorIgnoredAllNotifications = 0x0416 => "Ignored all notifications",
+    ClientErrorPrintSupportFileNotFound = 0x0417 => "Print support file not found",
+    ClientErrorDocumentPasswordError = 0x0418 => "Document password error",
+    ClientErrorDocumentPermissionError = 0x0419 => "Document permission error",
+    ClientErrorDocumentSecurityError = 0x041A => "Document security error",
+    ClientErrorDocumentUnprintableError = 0x041B => "Document unprintable",
+    ClientErrorAccountInfoNeeded = 0x041C => "Account info needed",
+    ClientErrorAccountClosed = 0x041D => "Account closed",
+    ClientErrorAccountLimitReached = 0x041E => "Account limit reached",
+    ClientErrorAccountAuthorizationFailed = 0x041F => "Account authorization failed",
+    ClientErrorNotFetchable = 0x0420 => "Not fetchable",
+    ServerErrorInternalError = 0x0500 => "Internal error",
+    ServerErrorOperationNotSupported = 0x0501 => "Operation not supported",
+    ServerErrorServiceUnavailable = 0x0502 => "Service unavailable",
+    ServerErrorVersionNotSupported = 0x0503 => "Version not supported",
+    ServerErrorDeviceError = 0x0504 => "Device error",
+    ServerErrorTemporaryError = 0x0505 => "Temporary error",
+    ServerErrorNotAcceptingJobs = 0x0506 => "Not accepting jobs",
+    ServerErrorBusy = 0x0507 => "Busy",
+    ServerErrorJobCanceled = 0x0508 => "Job canceled",
+    ServerErrorMultipleDocumentJobsNotSupported = 0x0509 => "Multiple document jobs not supported",
+    ServerErrorPrinterIsDeactivated = 0x050A => "Printer is deactivated",
+    ServerErrorTooManyJobs = 0x050B => "Too many jobs",
+    ServerErrorTooManyDocuments = 0x050C => "Too many documents",
 }
 
 impl StatusCode {
-    /// Return true if the status code indicates a successful response
+    /// Return true if the status code indicates a successful response (0x0000..0x00FF)
     pub fn is_success(&self) -> bool {
+        (0x0000..=0x00FF).contains(&self.code())
+    }
+
+    /// Return true if the status code is a client error (0x0400..0x04FF)
+    pub fn is_client_error(&self) -> bool {
+        (0x0400..=0x04FF).contains(&self.code())
+    }
+
+    /// Return true if the status code is a server error (0x0500..0x05FF)
+    pub fn is_server_error(&self) -> bool {
+        (0x0500..=0x05FF).contains(&self.code())
+    }
+
+    /// Return true if the request may succeed when retried later without changes
+    pub fn is_retryable(&self) -> bool {
         matches!(
             self,
-            StatusCode::SuccessfulOk
-                | StatusCode::SuccessfulOkIgnoredOrSubstitutedAttributes
-                | StatusCode::SuccessfulOkConflictingAttributes
+            StatusCode::ServerErrorServiceUnavailable
+                | StatusCode::ServerErrorTemporaryError
+                | StatusCode::ServerErrorNotAcceptingJobs
+                | StatusCode::ServerErrorBusy
+                | StatusCode::ServerErrorTooManyJobs
         )
     }
 }
 
+impl From<i16> for StatusCode {
+    fn from(code: i16) -> Self {
+        StatusCode::from_code(code)
+    }
+}
+
+impl From<StatusCode> for i16 {
+    fn from(status: StatusCode) -> Self {
+        status.code()
+    }
+}
+
+impl num_traits::FromPrimitive for StatusCode {
+    fn from_i64(n: i64) -> Option<Self> {
+        i16::try_from(n).ok().map(StatusCode::from_code)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        i16::try_from(n).ok().map(StatusCode::from_code)
+    }
+}
+
 impl fmt::Display for StatusCode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            StatusCode::SuccessfulOk => write!(f, "No error"),
-            StatusCode::SuccessfulOkIgnoredOrSubstitutedAttributes => write!(f, "Ignored or substituted attributes"),
-            StatusCode::SuccessfulOkConflictingAttributes => write!(f, "Conflicting attributes"),
-            StatusCode::ClientErrorBadRequest => write!(f, "Bad request"),
-            StatusCode::ClientErrorForbidden => write!(f, "Forbidden"),
-            StatusCode::ClientErrorNotAuthenticated => write!(f, "Not authenticated"),
-            StatusCode::ClientErrorNotAuthorized => write!(f, "Not authorized"),
-            StatusCode::ClientErrorNotPossible => write!(f, "Not possible"),
-            StatusCode::ClientErrorTimeout => write!(f, "Timeout"),
-            StatusCode::ClientErrorNotFound => write!(f, "Not found"),
-            StatusCode::ClientErrorGone => write!(f, "Gone"),
-            StatusCode::ClientErrorRequestEntityTooLong => write!(f, "Entity too long"),
-            StatusCode::ClientErrorRequestValueTooLong => write!(f, "Request value too long"),
-            StatusCode::ClientErrorDocumentFormatNotSupported => write!(f, "Document format not supported"),
-            StatusCode::ClientErrorAttributesOrValuesNotSupported => write!(f, "Attributes or values not supported"),
-            StatusCode::ClientErrorUriSchemeNotSupported => write!(f, "Uri scheme not supported"),
-            StatusCode::ClientErrorCharsetNotSupported => write!(f, "Charset not supported"),
-            StatusCode::ClientErrorConflictingAttributes => write!(f, "Conflicting attributes"),
-            StatusCode::ClientErrorCompressionNotSupported => write!(f, "Compression not supported"),
-            StatusCode::ClientErrorCompressionError => write!(f, "Compression error"),
-            StatusCode::ClientErrorDocumentFormatError => write!(f, "Document format error"),
-            StatusCode::ClientErrorDocumentAccessError => write!(f, "Document access error"),
-            StatusCode::ServerErrorInternalError => write!(f, "Internal error"),
-            StatusCode::ServerErrorOperationNotSupported => write!(f, "Operation not supported"),
-            StatusCode::ServerErrorServiceUnavailable => write!(f, "Service unavailable"),
-            StatusCode::ServerErrorVersionNotSupported => write!(f, "Version not supported"),
-            StatusCode::ServerErrorDeviceError => write!(f, "Device error"),
-            StatusCode::ServerErrorTemporaryError => write!(f, "Temporary error"),
-            StatusCode::ServerErrorNotAcceptingJobs => write!(f, "Not accepting jobs"),
-            StatusCode::ServerErrorBusy => write!(f, "Busy"),
-            StatusCode::ServerErrorJobCanceled => write!(f, "Job canceled"),
-            StatusCode::ServerErrorMultipleDocumentJobsNotSupported => {
-                write!(f, "Multiple document jobs not supported")
-            }
-            StatusCode::UnknownStatusCode => write!(f, "Unknown status code"),
+        match self.description() {
+            Some(description) => write!(f, "{description}"),
+            None => write!(f, "Unknown status code 0x{:04x}", self.code()),
         }
     }
 }
+
+#[test]
+fn test_status_code() {
+    assert_eq!(StatusCode::from_code(0x0420), StatusCode::ClientErrorNotFetchable);
+    assert_eq!(
+        StatusCode::from_code(0x0004),
+        StatusCode::SuccessfulOkIgnoredNotifications
+    );
+    assert_eq!(
+        StatusCode::from_code(0x0006),
+        StatusCode::SuccessfulOkButCannotSendNotification
+    );
+    assert_eq!(
+        StatusCode::from_code(0x0416),
+        StatusCode::ClientErrorIgnoredAllNotifications
+    );
+    assert_eq!(StatusCode::from_code(0x04ff), StatusCode::Unknown(0x04ff));
+    assert_eq!(StatusCode::Unknown(0x04ff).code(), 0x04ff);
+    assert_eq!(StatusCode::ServerErrorTooManyDocuments.code(), 0x050c);
+    assert_eq!(StatusCode::Unknown(0x0480).to_string(), "Unknown status code 0x0480");
+
+    assert!(StatusCode::SuccessfulOkEventsComplete.is_success());
+    assert!(StatusCode::Unknown(0x0480).is_client_error());
+    assert!(!StatusCode::Unknown(0x0480).is_server_error());
+    assert!(StatusCode::ServerErrorBusy.is_server_error());
+    assert!(StatusCode::ServerErrorBusy.is_retryable());
+    assert!(!StatusCode::ClientErrorNotFound.is_retryable());
+}
@@ -0,0 +1,790 @@
+//!
+//! Textual notation of IPP values, compatible with the CUPS/ipptool option syntax.
+//!
+//! Grammar:
+//! * `a,b,c` - an array of values, a trailing comma (`a,`) denotes a one-element array
+//! * `{name=value name2=a,b}` - a collection, members are separated by whitespace
+//! * `"quoted value"` - a keyword which may contain special characters, `\` escapes the next character
+//! * `tag:payload` - a value with an explicit syntax, the payload may be quoted.
+//!   Supported tags: `integer`, `boolean`, `enum`, `rangeOfInteger`, `resolution`, `keyword`, `name`, `name(lang)`,
+//!   `text`, `text(lang)`, `uri`, `uriScheme`, `charset`, `naturalLanguage`, `mimeMediaType`, `memberAttrName`,
+//!   `dateTime` (`2024-01-02T03:04:05.6+01:00`), `octetString` (hex), `other(0xNN)` (hex),
+//!   `extension(0xNNNNNNNN)` (hex) and `nonutf8(0xNN)` (hex).
+//! * bare values are inferred: `true`/`false` are booleans, `5` is an integer, `1-5` is a range,
+//!   `600x600dpi`, `300dpi` or `118x118dpcm` is a resolution, `no-value`, `not-settable`, `delete-attribute` and
+//!   `admin-define` are out-of-band values, strings containing `://` are URIs, anything else is a keyword.
+//! * an empty value, e.g. `job-name=` or `{media-type=}`, is an empty string like `""`.
+//!
+//! [`format_value`] is the exact inverse of [`parse_value`]: it uses the bare form whenever it parses
+//! back to the same value and the tagged form otherwise. The only exception is an empty array, which cannot be
+//! encoded in IPP: it is formatted as an empty string.
+//!
+//!```rust
+//! use ipp::{notation, value::IppValue};
+//!
+//! let value = notation::parse_value("{media-size={x-dimension=21000 y-dimension=29700}}").unwrap();
+//! assert!(matches!(value, IppValue::Collection(_)));
+//! assert_eq!(notation::format_value(&value), "{media-size={x-dimension=21000 y-dimension=29700}}");
+//!
+//! let value = notation::parse_value("name:foo").unwrap();
+//! assert_eq!(value, IppValue::new_name_without_language("foo").unwrap());
+//!```
+use std::fmt::Write as _;
+
+use bytes::Bytes;
+
+use crate::{
+    FromPrimitive as _,
+    model::ValueTag,
+    parser::IppParseError,
+    registry::AttributeInfo,
+    value::{IppCollection, IppDateTime, IppValue},
+};
+
+const OUT_OF_BAND: [(&str, IppValue); 4] = [
+    ("no-value", IppValue::NoValue),
+    ("not-settable", IppValue::NotSettable),
+    ("delete-attribute", IppValue::DeleteAttribute),
+    ("admin-define", IppValue::AdminDefine),
+];
+
+/// Parse a textual representation of an IPP value
+pub fn parse_value(s: &str) -> Result<IppValue, IppParseError> {
+    parse_attribute_value(s, None)
+}
+
+// untagged top-level values are parsed according to the attribute syntax if it is known
+pub(crate) fn parse_attribute_value(s: &str, info: Option<&AttributeInfo>) -> Result<IppValue, IppParseError> {
+    let mut parser = Parser { input: s, pos: 0 };
+    let value = parser.parse_list(info)?;
+    if parser.pos < s.len() {
+        return Err(parser.error());
+    }
+    Ok(value)
+}
+
+/// Format an IPP value into a textual representation accepted by [`parse_value`]
+pub fn format_value(value: &IppValue) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, ',' | '{' | '}' | '"' | '\\')
+}
+
+// quoted or empty value, a keyword unless the attribute syntax is known
+fn untagged(info: Option<&AttributeInfo>, s: &str) -> Result<IppValue, IppParseError> {
+    match info {
+        Some(info) => info.parse_single(s),
+        None => Ok(IppValue::new_keyword(s)?),
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self) -> IppParseError {
+        IppParseError::InvalidNotation {
+            input: self.input.to_owned(),
+            offset: self.pos,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn at_terminator(&self) -> bool {
+        self.peek().is_none_or(|c| c.is_whitespace() || c == '}')
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn parse_list(&mut self, info: Option<&AttributeInfo>) -> Result<IppValue, IppParseError> {
+        let mut items = Vec::new();
+        if self.at_terminator() {
+            // an empty array has no IPP encoding
+            return untagged(info, "");
+        }
+        loop {
+            items.push(self.parse_item(info)?);
+            if self.peek() != Some(',') {
+                break;
+            }
+            self.bump();
+            if self.at_terminator() {
+                return Ok(IppValue::Array(items));
+            }
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            IppValue::Array(items)
+        })
+    }
+
+    fn parse_item(&mut self, info: Option<&AttributeInfo>) -> Result<IppValue, IppParseError> {
+        match self.peek() {
+            Some('{') => self.parse_collection(),
+            Some('"') => {
+                let quoted = self.parse_quoted()?;
+                untagged(info, &quoted)
+            }
+            _ => {
+                let start = self.pos;
+                let raw = self.take_while(|c| !is_delimiter(c));
+                if raw.is_empty() {
+                    return Err(self.error());
+                }
+                match raw.split_once(':') {
+                    Some((tag, "")) if self.peek() == Some('"') => {
+                        let payload = self.parse_quoted()?;
+                        self.tagged(tag, &payload, start)
+                    }
+                    Some((tag, payload)) if is_tag(tag) => self.tagged(tag, payload, start),
+                    _ => match (info, out_of_band(raw)) {
+                        (Some(info), None) => info.parse_single(raw),
+                        _ => infer(raw),
+                    },
+                }
+            }
+        }
+    }
+
+    fn parse_quoted(&mut self) -> Result<String, IppParseError> {
+        self.bump();
+        let mut s = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error()),
+                Some('"') => {
+                    self.bump();
+                    return Ok(s);
+                }
+                Some('\\') => {
+                    self.bump();
+                    s.push(self.peek().ok_or_else(|| self.error())?);
+                    self.bump();
+                }
+                Some(c) => {
+                    s.push(c);
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    fn parse_collection(&mut self) -> Result<IppValue, IppParseError> {
+        self.bump();
+        let mut collection = IppCollection::new();
+        loop {
+            self.take_while(char::is_whitespace);
+            match self.peek() {
+                None => return Err(self.error()),
+                Some('}') => {
+                    self.bump();
+                    return Ok(IppValue::Collection(collection));
+                }
+                _ => {
+                    let name = self.take_while(|c| c != '=' && !is_delimiter(c));
+                    if name.is_empty() || self.peek() != Some('=') {
+                        return Err(self.error());
+                    }
+                    self.bump();
+                    let value = self.parse_list(None)?;
+                    if !self.at_terminator() {
+                        return Err(self.error());
+                    }
+                    collection.push(name.try_into()?, value);
+                }
+            }
+        }
+    }
+
+    fn tagged(&self, tag: &str, payload: &str, start: usize) -> Result<IppValue, IppParseError> {
+        parse_tagged(tag, payload)?.ok_or(IppParseError::InvalidNotation {
+            input: self.input.to_owned(),
+            offset: start,
+        })
+    }
+}
+
+// split `name(param)` into the name and an optional parameter
+fn split_tag(tag: &str) -> Option<(&str, Option<&str>)> {
+    match tag.split_once('(') {
+        Some((name, rest)) => Some((name, Some(rest.strip_suffix(')')?))),
+        None => Some((tag, None)),
+    }
+}
+
+fn is_tag(tag: &str) -> bool {
+    matches!(
+        split_tag(tag),
+        Some((
+            "integer"
+                | "boolean"
+                | "enum"
+                | "rangeOfInteger"
+                | "resolution"
+                | "keyword"
+                | "name"
+                | "text"
+                | "uri"
+                | "uriScheme"
+                | "charset"
+                | "naturalLanguage"
+                | "mimeMediaType"
+                | "memberAttrName"
+                | "dateTime"
+                | "octetString"
+                | "other"
+                | "extension"
+                | "nonutf8",
+            _
+        ))
+    )
+}
+
+fn parse_number<T: TryFrom<u64>>(s: &str) -> Option<T> {
+    let n = match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+        None => s.parse().ok()?,
+    };
+    n.try_into().ok()
+}
+
+fn parse_hex(s: &str) -> Option<Bytes> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()
+        .map(Bytes::from)
+}
+
+pub(crate) fn parse_range(s: &str) -> Option<IppValue> {
+    // skip the first character to allow a negative lower bound
+    let split = s.char_indices().skip(1).find(|(_, c)| *c == '-')?.0;
+    Some(IppValue::RangeOfInteger {
+        min: s[..split].parse().ok()?,
+        max: s[split + 1..].parse().ok()?,
+    })
+}
+
+// "600x300dpi", "300dpi" for equal resolutions, "118x118dpcm" or "600x600u5" for other units
+pub(crate) fn parse_resolution(s: &str) -> Option<IppValue> {
+    let digits = s.find(|c: char| !c.is_ascii_digit() && c != '-' && c != 'x')?;
+    let (size, units) = s.split_at(digits);
+    let units = match units {
+        "dpi" => 3,
+        "dpcm" => 4,
+        other => other.strip_prefix('u')?.parse().ok()?,
+    };
+    let (cross_feed, feed) = size.split_once('x').unwrap_or((size, size));
+    Some(IppValue::Resolution {
+        cross_feed: cross_feed.parse().ok()?,
+        feed: feed.parse().ok()?,
+        units,
+    })
+}
+
+fn parse_date_time(s: &str) -> Option<IppDateTime> {
+    fn field<T: std::str::FromStr>(s: &str, range: std::ops::Range<usize>) -> Option<T> {
+        s.get(range)?.parse().ok()
+    }
+
+    let (date, time) = s.split_once('T')?;
+    let mut date = date.splitn(3, '-');
+    let (year, month, day) = (date.next()?, date.next()?, date.next()?);
+    let deci_end = time.get(9..)?.find(|c: char| !c.is_ascii_digit())? + 9;
+    let utc_dir = time[deci_end..].chars().next()?;
+    let offset = &time[deci_end + utc_dir.len_utf8()..];
+    if time.get(2..3)? != ":" || time.get(5..6)? != ":" || time.get(8..9)? != "." || offset.get(2..3)? != ":" {
+        return None;
+    }
+    Some(IppDateTime {
+        year: year.parse().ok()?,
+        month: month.parse().ok()?,
+        day: day.parse().ok()?,
+        hour: field(time, 0..2)?,
+        minutes: field(time, 3..5)?,
+        seconds: field(time, 6..8)?,
+        deci_seconds: field(time, 9..deci_end)?,
+        utc_dir,
+        utc_hours: field(offset, 0..2)?,
+        utc_mins: offset.get(3..)?.parse().ok()?,
+    })
+}
+
+// returns Ok(None) if the payload does not conform to the tag syntax
+fn parse_tagged(tag: &str, payload: &str) -> Result<Option<IppValue>, IppParseError> {
+    let Some((name, param)) = split_tag(tag) else {
+        return Ok(None);
+    };
+    let value = match (name, param) {
+        ("integer", None) => payload.parse().ok().map(IppValue::Integer),
+        ("enum", None) => payload.parse().ok().map(IppValue::Enum),
+        ("boolean", None) => payload.parse().ok().map(IppValue::Boolean),
+        ("rangeOfInteger", None) => parse_range(payload),
+        ("resolution", None) => parse_resolution(payload),
+        ("keyword", None) => Some(IppValue::new_keyword(payload)?),
+        ("name", None) => Some(IppValue::new_name_without_language(payload)?),
+        ("name", Some(language)) => Some(IppValue::new_name_with_language(language, payload)?),
+        ("text", None) => Some(IppValue::new_text_without_language(payload)?),
+        ("text", Some(language)) => Some(IppValue::new_text_with_language(language, payload)?),
+        ("uri", None) => Some(IppValue::new_uri(payload)?),
+        ("uriScheme", None) => Some(IppValue::new_uri_scheme(payload)?),
+        ("charset", None) => Some(IppValue::new_charset(payload)?),
+        ("naturalLanguage", None) => Some(IppValue::new_natural_language(payload)?),
+        ("mimeMediaType", None) => Some(IppValue::new_mime_media_type(payload)?),
+        ("memberAttrName", None) => Some(IppValue::new_member_attr_name(payload)?),
+        ("dateTime", None) => parse_date_time(payload).map(IppValue::DateTime),
+        ("octetString", None) => parse_hex(payload).map(IppValue::OctetString),
+        ("other", Some(tag)) => parse_number(tag)
+            .zip(parse_hex(payload))
+            .map(|(tag, data)| IppValue::Other { tag, data }),
+        ("extension", Some(tag)) => parse_number(tag)
+            .zip(parse_hex(payload))
+            .map(|(tag, data)| IppValue::Extension { tag, data }),
+        ("nonutf8", Some(tag)) => parse_number(tag)
+            .and_then(ValueTag::from_u8)
+            .zip(parse_hex(payload))
+            .map(|(tag, data)| IppValue::NonUtf8 { tag, data }),
+        _ => None,
+    };
+    Ok(value)
+}
+
+// infer the syntax of a bare value
+fn infer(raw: &str) -> Result<IppValue, IppParseError> {
+    if let Ok(b) = raw.parse() {
+        return Ok(IppValue::Boolean(b));
+    }
+    if let Ok(i) = raw.parse() {
+        return Ok(IppValue::Integer(i));
+    }
+    if let Some(value) = parse_range(raw).or_else(|| parse_resolution(raw).filter(is_standard_resolution)) {
+        return Ok(value);
+    }
+    if let Some(value) = out_of_band(raw) {
+        return Ok(value);
+    }
+    if raw.contains("://") {
+        return IppValue::new_uri(raw);
+    }
+    IppValue::new_keyword(raw)
+}
+
+fn out_of_band(raw: &str) -> Option<IppValue> {
+    OUT_OF_BAND
+        .iter()
+        .find(|(name, _)| *name == raw)
+        .map(|(_, value)| value.clone())
+}
+
+fn is_standard_resolution(value: &IppValue) -> bool {
+    matches!(value, IppValue::Resolution { units: 3 | 4, .. })
+}
+
+fn hex(data: &[u8]) -> String {
+    data.iter().fold(String::new(), |mut out, b| {
+        let _ = write!(out, "{b:02x}");
+        out
+    })
+}
+
+fn write_quoted(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        if matches!(c, '"' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+}
+
+fn write_payload(out: &mut String, payload: &str) {
+    if !payload.is_empty() && !payload.contains(is_delimiter) {
+        out.push_str(payload);
+    } else {
+        write_quoted(out, payload);
+    }
+}
+
+fn write_value(out: &mut String, value: &IppValue) {
+    match value {
+        IppValue::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_item(out, item);
+            }
+            if items.len() == 1 {
+                out.push(',');
+            }
+        }
+        other => write_item(out, other),
+    }
+}
+
+// bare representation of a scalar value, used only if it parses back to the same value
+fn bare_form(value: &IppValue) -> Option<String> {
+    let bare = match value {
+        IppValue::Integer(i) => i.to_string(),
+        IppValue::Boolean(b) => b.to_string(),
+        IppValue::RangeOfInteger { min, max } => format!("{min}-{max}"),
+        IppValue::Resolution {
+            cross_feed,
+            feed,
+            units: 3,
+        } => format!("{cross_feed}x{feed}dpi"),
+        IppValue::Resolution {
+            cross_feed,
+            feed,
+            units: 4,
+        } => format!("{cross_feed}x{feed}dpcm"),
+        IppValue::Keyword(s) => s.to_string(),
+        IppValue::Uri(s) => s.to_string(),
+        IppValue::NoValue | IppValue::NotSettable | IppValue::DeleteAttribute | IppValue::AdminDefine => {
+            OUT_OF_BAND.iter().find(|(_, v)| v == value)?.0.to_owned()
+        }
+        _ => return None,
+    };
+    let mut parser = Parser { input: &bare, pos: 0 };
+    match parser.parse_item(None) {
+        Ok(ref parsed) if parser.pos == bare.len() && parsed == value => Some(bare),
+        _ => None,
+    }
+}
+
+fn write_item(out: &mut String, value: &IppValue) {
+    if let Some(bare) = bare_form(value) {
+        out.push_str(&bare);
+        return;
+    }
+    let (tag, payload) = match value {
+        IppValue::Collection(collection) => {
+            out.push('{');
+            for (i, (name, value)) in collection.iter().enumerate() {
+                if i > 0 {
+                    out.push(' ');
+                }
+                let _ = write!(out, "{name}=");
+                write_value(out, value);
+            }
+            out.push('}');
+            return;
+        }
+        IppValue::Array(_) => {
+            // nested arrays cannot be represented, flatten them
+            write_value(out, value);
+            return;
+        }
+        IppValue::Keyword(s) => {
+            // the bare form is ambiguous, a quoted string without a tag is a keyword
+            write_quoted(out, s);
+            return;
+        }
+        IppValue::Integer(i) => ("integer".to_owned(), i.to_string()),
+        IppValue::Enum(i) => ("enum".to_owned(), i.to_string()),
+        IppValue::Boolean(b) => ("boolean".to_owned(), b.to_string()),
+        IppValue::RangeOfInteger { min, max } => ("rangeOfInteger".to_owned(), format!("{min}-{max}")),
+        IppValue::Resolution {
+            cross_feed,
+            feed,
+            units,
+        } => ("resolution".to_owned(), format!("{cross_feed}x{feed}u{units}")),
+        IppValue::NameWithoutLanguage(s) => ("name".to_owned(), s.to_string()),
+        IppValue::NameWithLanguage { language, name } => (format!("name({language})"), name.to_string()),
+        IppValue::TextWithoutLanguage(s) => ("text".to_owned(), s.as_ref().to_owned()),
+        IppValue::TextWithLanguage { language, text } => (format!("text({language})"), text.as_ref().to_owned()),
+        IppValue::Uri(s) => ("uri".to_owned(), s.to_string()),
+        IppValue::UriScheme(s) => ("uriScheme".to_owned(), s.to_string()),
+        IppValue::Charset(s) => ("charset".to_owned(), s.to_string()),
+        IppValue::NaturalLanguage(s) => ("naturalLanguage".to_owned(), s.to_string()),
+        IppValue::MimeMediaType(s) => ("mimeMediaType".to_owned(), s.to_string()),
+        IppValue::MemberAttrName(s) => ("memberAttrName".to_owned(), s.to_string()),
+        IppValue::DateTime(dt) => (
+            "dateTime".to_owned(),
+            format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{}{}{:02}:{:02}",
+                dt.year,
+                dt.month,
+                dt.day,
+                dt.hour,
+                dt.minutes,
+                dt.seconds,
+                dt.deci_seconds,
+                dt.utc_dir,
+                dt.utc_hours,
+                dt.utc_mins
+            ),
+        ),
+        IppValue::OctetString(data) => ("octetString".to_owned(), hex(data)),
+        IppValue::Other { tag, data } => (format!("other(0x{tag:02x})"), hex(data)),
+        IppValue::Extension { tag, data } => (format!("extension(0x{tag:08x})"), hex(data)),
+        IppValue::NonUtf8 { tag, data } => (format!("nonutf8(0x{:02x})", *tag as u8), hex(data)),
+        IppValue::Encoded { .. } => return write_item(out, &value.decode_charset()),
+        IppValue::NoValue | IppValue::NotSettable | IppValue::DeleteAttribute | IppValue::AdminDefine => {
+            unreachable!("out-of-band values always have a bare form")
+        }
+    };
+    out.push_str(&tag);
+    out.push(':');
+    write_payload(out, &payload);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{attribute::IppAttribute, registry};
+
+    fn round_trip(value: IppValue) {
+        let text = format_value(&value);
+        assert_eq!(parse_value(&text).unwrap(), value, "{text}");
+    }
+
+    #[test]
+    fn test_parse_bare_values() {
+        assert_eq!(parse_value("true").unwrap(), IppValue::Boolean(true));
+        assert_eq!(parse_value("-5").unwrap(), IppValue::Integer(-5));
+        assert_eq!(parse_value("1-5").unwrap(), IppValue::RangeOfInteger { min: 1, max: 5 });
+        assert_eq!(
+            parse_value("-5--1").unwrap(),
+            IppValue::RangeOfInteger { min: -5, max: -1 }
+        );
+        assert_eq!(
+            parse_value("600x600dpi").unwrap(),
+            IppValue::new_resolution(600, 600, 3)
+        );
+        assert_eq!(
+            parse_value("118x236dpcm").unwrap(),
+            IppValue::new_resolution(118, 236, 4)
+        );
+        assert_eq!(parse_value("300dpi").unwrap(), IppValue::new_resolution(300, 300, 3));
+        assert_eq!(parse_value("not-settable").unwrap(), IppValue::NotSettable);
+        assert_eq!(
+            parse_value("ipp://host/printer").unwrap(),
+            IppValue::new_uri("ipp://host/printer").unwrap()
+        );
+        assert_eq!(
+            parse_value("iso_a4_210x297mm").unwrap(),
+            IppValue::new_keyword("iso_a4_210x297mm").unwrap()
+        );
+        assert_eq!(parse_value("\"a b\"").unwrap(), IppValue::new_keyword("a b").unwrap());
+    }
+
+    #[test]
+    fn test_parse_lists_and_collections() {
+        assert_eq!(
+            parse_value("1,2,3").unwrap(),
+            IppValue::Array(vec![IppValue::Integer(1), IppValue::Integer(2), IppValue::Integer(3)])
+        );
+        assert_eq!(parse_value("1,").unwrap(), IppValue::Array(vec![IppValue::Integer(1)]));
+        assert_eq!(parse_value("").unwrap(), IppValue::new_keyword("").unwrap());
+        assert_eq!(
+            parse_value("{media-type=}").unwrap(),
+            IppValue::Collection(IppCollection::from([(
+                "media-type".try_into().unwrap(),
+                IppValue::new_keyword("").unwrap()
+            )]))
+        );
+
+        let value = parse_value("{media-size={x-dimension=21000 y-dimension=29700} media-type=stationery}").unwrap();
+        let media_col = value.as_collection().unwrap();
+        let size = media_col.get("media-size").unwrap().as_collection().unwrap();
+        assert_eq!(size.get("x-dimension"), Some(&IppValue::Integer(21000)));
+        assert_eq!(size.get("y-dimension"), Some(&IppValue::Integer(29700)));
+        assert_eq!(
+            media_col.get("media-type"),
+            Some(&IppValue::new_keyword("stationery").unwrap())
+        );
+
+        let value = parse_value("{stitching-locations=20,80 finishing-template=staple},{}").unwrap();
+        let array = value.as_array().unwrap();
+        assert_eq!(
+            array[0].as_collection().unwrap().get("stitching-locations"),
+            Some(&IppValue::Array(vec![IppValue::Integer(20), IppValue::Integer(80)]))
+        );
+        assert_eq!(array[1], IppValue::Collection(IppCollection::new()));
+    }
+
+    #[test]
+    fn test_parse_tagged_values() {
+        assert_eq!(
+            parse_value("name:foo").unwrap(),
+            IppValue::new_name_without_language("foo").unwrap()
+        );
+        assert_eq!(
+            parse_value("text(en):\"Hello, world\"").unwrap(),
+            IppValue::new_text_with_language("en", "Hello, world").unwrap()
+        );
+        assert_eq!(
+            parse_value("mimeMediaType:application/pdf").unwrap(),
+            IppValue::new_mime_media_type("application/pdf").unwrap()
+        );
+        assert_eq!(parse_value("enum:3").unwrap(), IppValue::Enum(3));
+        assert_eq!(
+            parse_value("integer:x").unwrap_err().to_string(),
+            "Invalid value notation at offset 0: integer:x"
+        );
+        assert_eq!(
+            parse_value("other(0x10):\"\"").unwrap(),
+            IppValue::Other {
+                tag: 0x10,
+                data: Bytes::new()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_value("integer:abc").is_err());
+        assert!(parse_value("{a=1").is_err());
+        assert!(parse_value("{=1}").is_err());
+        assert!(parse_value("\"abc").is_err());
+        assert!(parse_value("a b").is_err());
+        assert!(parse_value(",").is_err());
+    }
+
+    #[test]
+    fn test_format_values() {
+        assert_eq!(format_value(&IppValue::RangeOfInteger { min: 1, max: 5 }), "1-5");
+        assert_eq!(format_value(&IppValue::new_resolution(600, 300, 3)), "600x300dpi");
+        assert_eq!(
+            format_value(&IppValue::new_resolution(600, 300, 7)),
+            "resolution:600x300u7"
+        );
+        assert_eq!(format_value(&IppValue::Enum(3)), "enum:3");
+        assert_eq!(format_value(&IppValue::new_keyword("true").unwrap()), "\"true\"");
+        assert_eq!(format_value(&IppValue::new_keyword("a\"b").unwrap()), "\"a\\\"b\"");
+        assert_eq!(
+            format_value(&IppValue::new_uri("urn:uuid:1").unwrap()),
+            "uri:urn:uuid:1"
+        );
+        assert_eq!(format_value(&IppValue::Array(vec![IppValue::Integer(1)])), "1,");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let collection = IppCollection::from([
+            (
+                "media-size-name".try_into().unwrap(),
+                IppValue::new_keyword("iso_a4_210x297mm").unwrap(),
+            ),
+            (
+                "media-source".try_into().unwrap(),
+                IppValue::new_name_with_language("en", "Manual Feed").unwrap(),
+            ),
+            (
+                "stitching-locations".try_into().unwrap(),
+                IppValue::Array(vec![IppValue::Integer(20)]),
+            ),
+        ]);
+        let values = vec![
+            IppValue::Integer(i32::MIN),
+            IppValue::Enum(-1),
+            IppValue::Boolean(false),
+            IppValue::RangeOfInteger { min: -10, max: 10 },
+            IppValue::new_resolution(-1, 2, 3),
+            IppValue::new_resolution(1, 2, -4),
+            IppValue::new_keyword("").unwrap(),
+            IppValue::new_keyword("1-5").unwrap(),
+            IppValue::new_keyword("name:x").unwrap(),
+            IppValue::new_keyword("a\\b c").unwrap(),
+            IppValue::new_name_without_language("").unwrap(),
+            IppValue::new_text_without_language("Multi word {text}, \"quoted\"").unwrap(),
+            IppValue::new_text_with_language("de", "Grüße").unwrap(),
+            IppValue::new_uri("ipp://host:631/printers/p").unwrap(),
+            IppValue::new_uri("no-scheme").unwrap(),
+            IppValue::new_uri_scheme("ipps").unwrap(),
+            IppValue::new_charset("utf-8").unwrap(),
+            IppValue::new_natural_language("en-us").unwrap(),
+            IppValue::new_mime_media_type("image/pwg-raster").unwrap(),
+            IppValue::new_member_attr_name("media-col").unwrap(),
+            IppValue::DateTime(IppDateTime {
+                year: 2024,
+                month: 1,
+                day: 2,
+                hour: 3,
+                minutes: 4,
+                seconds: 5,
+                deci_seconds: 6,
+                utc_dir: '-',
+                utc_hours: 7,
+                utc_mins: 30,
+            }),
+            IppValue::OctetString(Bytes::from_static(b"\x00\xff")),
+            IppValue::Other {
+                tag: 0x12,
+                data: Bytes::new(),
+            },
+            IppValue::Extension {
+                tag: 0x4000_0001,
+                data: Bytes::from_static(b"ext"),
+            },
+            IppValue::NonUtf8 {
+                tag: ValueTag::TextWithoutLanguage,
+                data: Bytes::from_static(b"\xe9t\xe9"),
+            },
+            IppValue::NoValue,
+            IppValue::NotSettable,
+            IppValue::DeleteAttribute,
+            IppValue::AdminDefine,
+            IppValue::Collection(IppCollection::new()),
+            IppValue::Collection(collection.clone()),
+            IppValue::Array(vec![IppValue::Collection(collection)]),
+            IppValue::Array(vec![IppValue::new_keyword("a").unwrap(), IppValue::NoValue]),
+        ];
+        for value in values {
+            round_trip(value);
+        }
+    }
+
+    #[test]
+    fn test_empty_value() {
+        assert_eq!(format_value(&IppValue::Array(vec![])), "");
+        assert_eq!(format_value(&IppValue::new_keyword("").unwrap()), "\"\"");
+
+        let value = "".parse::<IppValue>().unwrap();
+        assert_eq!(value, IppValue::new_keyword("").unwrap());
+        let attr = IppAttribute::new(IppAttribute::JOB_NAME.try_into().unwrap(), value);
+        assert_eq!(attr.to_bytes(), [&[0x44, 0, 8][..], b"job-name", &[0, 0]].concat());
+
+        let info = registry::lookup(IppAttribute::JOB_NAME).unwrap();
+        let attr = IppAttribute::new(
+            IppAttribute::JOB_NAME.try_into().unwrap(),
+            info.parse_value("").unwrap(),
+        );
+        assert_eq!(attr.to_bytes(), [&[0x42, 0, 8][..], b"job-name", &[0, 0]].concat());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "1-5".parse::<IppValue>().unwrap(),
+            IppValue::RangeOfInteger { min: 1, max: 5 }
+        );
+    }
+}
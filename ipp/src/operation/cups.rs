@@ -21,8 +21,7 @@ impl CupsGetPrinters {
 
 impl IppOperation for CupsGetPrinters {
     fn into_ipp_request(self) -> IppRequestResponse {
-        IppRequestResponse::new(self.version(), Operation::CupsGetPrinters, None)
-            .expect("cups list printers URI length check missing")
+        IppRequestResponse::new_internal(self.version(), Operation::CupsGetPrinters, None)
     }
 }
 
@@ -2,25 +2,33 @@
 //! IPP stream parser
 //!
 use std::{
-    collections::BTreeMap,
+    collections::VecDeque,
     convert::Infallible,
+    fmt,
     io::{self, Read},
+    iter,
     num::TryFromIntError,
 };
 
-use bytes::{Bytes, TryGetError};
+use bytes::{Buf, Bytes, BytesMut, TryGetError};
 #[cfg(feature = "log")]
-use log::{error, trace};
+use log::{error, trace, warn};
 #[cfg(feature = "async")]
-use {crate::reader::AsyncIppReader, futures_util::io::AsyncRead};
+use {
+    crate::reader::AsyncIppReader,
+    futures_util::{Stream, io::AsyncRead},
+};
 
 use crate::{
     FromPrimitive as _, IppHeader,
     attribute::{IppAttribute, IppAttributeGroup, IppAttributes},
-    model::{DelimiterTag, ValueTag},
+    charset::Charset,
+    model::{DelimiterTag, IppVersion, ValueTag},
+    payload::IppPayload,
     reader::IppReader,
+    registry::{self, AttributeInfo},
     request::IppRequestResponse,
-    value::{IppName, IppValue},
+    value::{IppCollection, IppName, IppValue},
 };
 
 /// Parse error enum
@@ -53,11 +61,100 @@ pub enum IppParseError {
     #[error("Found a non-utf-8 string in a context that currently only supports utf-8")]
     UnsupportedCharset,
 
+    /// The charset is not supported by the [`charset`](crate::charset) module
+    #[error("Unsupported charset: {0}")]
+    UnknownCharset(String),
+
+    /// The data cannot be decoded from or the string cannot be encoded into the charset
+    #[error("Value is not representable in charset {0}")]
+    InvalidCharsetData(&'static str),
+
     #[error("Invalid datetime value")]
     InvalidDateTime,
 
     #[error("Invalid value length")]
     InvalidValueLength(#[from] TryGetError),
+
+    /// The textual value does not conform to the attribute syntax
+    #[error("Invalid value for attribute {0}: {1}")]
+    InvalidValue(String, String),
+
+    /// The textual value notation is malformed
+    #[error("Invalid value notation at offset {offset}: {input}")]
+    InvalidNotation { input: String, offset: usize },
+
+    /// The message has more attributes and collection members than allowed by the parser limits
+    #[error("Too many attributes, max: {0}")]
+    TooManyAttributes(usize),
+
+    /// An attribute has more values than allowed by the parser limits
+    #[error("Too many attribute values, max: {0}")]
+    TooManyValues(usize),
+
+    /// Collections are nested deeper than allowed by the parser limits
+    #[error("Collection nesting is too deep, max: {0}")]
+    CollectionTooDeep(usize),
+
+    /// The IPP header with the attributes is larger than allowed by the parser limits
+    #[error("IPP header is too large, max: {0} bytes")]
+    HeaderTooLarge(usize),
+
+    /// The message violates the encoding rules
+    #[error("Malformed IPP message: {0}")]
+    Malformed(ParseProblem),
+
+    /// Error with the position in the message where it occurred
+    #[error("{source} at {position}")]
+    Positioned {
+        position: ParsePosition,
+        source: Box<IppParseError>,
+    },
+
+    /// The JSON document does not conform to the IPP JSON encoding
+    #[error("Invalid IPP JSON: {0}")]
+    InvalidJson(String),
+}
+
+impl IppParseError {
+    /// Return the position in the message where the error occurred, if known
+    pub fn position(&self) -> Option<&ParsePosition> {
+        match self {
+            IppParseError::Positioned { position, .. } => Some(position),
+            _ => None,
+        }
+    }
+
+    /// Return the error without the position
+    pub fn inner(&self) -> &IppParseError {
+        match self {
+            IppParseError::Positioned { source, .. } => source.inner(),
+            error => error,
+        }
+    }
+}
+
+/// Position in the message where a parse error occurred
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePosition {
+    /// Offset of the offending tag from the start of the message
+    pub offset: usize,
+    /// Current attribute group, `None` before the first group
+    pub group: Option<DelimiterTag>,
+    /// Name of the attribute being parsed or the last parsed attribute, empty if there is none
+    pub attribute: String,
+}
+
+impl fmt::Display for ParsePosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "offset {}", self.offset)?;
+        if let Some(group) = self.group {
+            write!(f, " in {group:?} group")?;
+        }
+        if !self.attribute.is_empty() {
+            write!(f, ", attribute: {}", self.attribute)?;
+        }
+        Ok(())
+    }
 }
 
 // create a single value from one-element list, list otherwise
@@ -69,11 +166,200 @@ fn list_or_value(mut list: Vec<IppValue>) -> IppValue {
     }
 }
 
+// keep the values of a registered 1setOf attribute or collection member as an array, even a single one.
+// The wire format does not mark 1setOf values, so attributes unknown to the registry fall back to a scalar
+// for a single value and to an array for several values; `IppAttribute::values` handles both shapes.
+fn attribute_value(info: Option<&AttributeInfo>, list: Vec<IppValue>) -> IppValue {
+    match info {
+        Some(info) if info.set_of && list.len() == 1 => IppValue::Array(list),
+        _ => list_or_value(list),
+    }
+}
+
+// name of the last member in the flattened list of a collection being parsed
+fn last_member(list: &[IppValue]) -> Option<&str> {
+    list.iter().rev().find_map(|value| match value {
+        IppValue::MemberAttrName(name) => Some(name.as_str()),
+        _ => None,
+    })
+}
+
+// build collection members from the flattened list: each memberAttrName is followed by one or more values.
+// Members without a value are dropped, their names are returned along with the collection
+fn collect_members(info: Option<&AttributeInfo>, list: Vec<IppValue>) -> (IppCollection, Vec<IppName>) {
+    let mut members: Vec<(IppName, Vec<IppValue>)> = Vec::new();
+    for value in list {
+        match value {
+            IppValue::MemberAttrName(name) => members.push((name, Vec::new())),
+            value => {
+                if let Some((_, values)) = members.last_mut() {
+                    values.push(value);
+                }
+            }
+        }
+    }
+    let mut empty = Vec::new();
+    let collection = members
+        .into_iter()
+        .filter_map(|(name, values)| {
+            if values.is_empty() {
+                empty.push(name);
+                None
+            } else {
+                let member = info.and_then(|info| info.member(&name));
+                Some((name, attribute_value(member, values)))
+            }
+        })
+        .collect();
+    (collection, empty)
+}
+
+/// Resource limits of the parsers, protecting against untrusted input
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParserLimits {
+    /// Maximum number of attributes and collection members in a single attribute group
+    pub max_attributes: usize,
+    /// Maximum number of values of a single attribute
+    pub max_values: usize,
+    /// Maximum nesting depth of collections
+    pub max_depth: usize,
+    /// Maximum size in bytes of the IPP header including all attributes, the payload is not limited
+    pub max_header_size: usize,
+}
+
+impl ParserLimits {
+    /// Limits which never trigger, for trusted input only
+    pub fn unlimited() -> ParserLimits {
+        ParserLimits {
+            max_attributes: usize::MAX,
+            max_values: usize::MAX,
+            max_depth: usize::MAX,
+            max_header_size: usize::MAX,
+        }
+    }
+}
+
+impl Default for ParserLimits {
+    fn default() -> Self {
+        ParserLimits {
+            max_attributes: 100_000,
+            max_values: 10_000,
+            max_depth: 16,
+            max_header_size: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// Parsing mode
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Reject messages which violate the encoding rules
+    #[default]
+    Strict,
+    /// Recover from malformed messages where possible, collecting the problems as warnings
+    Lenient,
+}
+
+/// Problem found in a malformed message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseProblem {
+    /// Unknown delimiter tag, skipped
+    UnknownDelimiter(u8),
+    /// The value cannot be decoded, kept as `IppValue::Other`
+    InvalidValue(String),
+    /// begCollection or endCollection value is not empty, the value is ignored
+    MalformedCollection,
+    /// endCollection without a matching begCollection, skipped
+    UnmatchedEndCollection,
+    /// A collection is not terminated by endCollection, closed implicitly
+    UnterminatedCollection,
+    /// An attribute is not inside an attribute group, dropped
+    AttributeOutsideGroup,
+    /// An additional value without a preceding attribute, dropped
+    ValueWithoutName,
+    /// A collection member name is not followed by a value, the member is dropped
+    MemberWithoutValue(String),
+    /// The message ends before the end-of-attributes tag, the attributes parsed so far are kept
+    Truncated,
+}
+
+impl fmt::Display for ParseProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseProblem::UnknownDelimiter(tag) => write!(f, "unknown delimiter tag: {tag:#04x}"),
+            ParseProblem::InvalidValue(error) => write!(f, "invalid value: {error}"),
+            ParseProblem::MalformedCollection => write!(f, "malformed collection delimiter"),
+            ParseProblem::UnmatchedEndCollection => write!(f, "endCollection without begCollection"),
+            ParseProblem::UnterminatedCollection => write!(f, "collection is not terminated"),
+            ParseProblem::AttributeOutsideGroup => write!(f, "attribute outside of an attribute group"),
+            ParseProblem::ValueWithoutName => write!(f, "additional value without an attribute"),
+            ParseProblem::MemberWithoutValue(name) => write!(f, "collection member without a value: {name}"),
+            ParseProblem::Truncated => write!(f, "message is truncated"),
+        }
+    }
+}
+
+/// Problem found in lenient mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// Offset of the offending tag from the start of the message
+    pub offset: usize,
+    /// Name of the attribute being parsed, empty for group-level problems
+    pub attribute: String,
+    /// The problem found
+    pub problem: ParseProblem,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.attribute.is_empty() {
+            write!(f, "offset {}: {}", self.offset, self.problem)
+        } else {
+            write!(f, "offset {}: {}: {}", self.offset, self.attribute, self.problem)
+        }
+    }
+}
+
+// size of the version, operation or status and request id fields
+const HEADER_SIZE: usize = 8;
+
+fn is_truncated(error: &IppParseError) -> bool {
+    match error {
+        IppParseError::IoError(e) => e.kind() == io::ErrorKind::UnexpectedEof,
+        IppParseError::InvalidValueLength(_) => true,
+        _ => false,
+    }
+}
+
+// names are expected to be UTF-8, invalid sequences are replaced. Valid names share the buffer
+fn parse_name(data: Bytes) -> Result<IppName, IppParseError> {
+    if data.len() > IppName::max() {
+        return Err(IppParseError::InvalidStringLength {
+            len: data.len(),
+            max: IppName::max(),
+        });
+    }
+    match IppName::from_shared(data.clone()) {
+        Some(name) => Ok(name),
+        None => String::from_utf8_lossy(&data).into_owned().try_into(),
+    }
+}
+
 struct ParserState {
     current_group: Option<IppAttributeGroup>,
     last_name: Option<IppName>,
+    // name of the attribute whose value is being decoded
+    current_name: Option<IppName>,
     context: Vec<Vec<IppValue>>,
     attributes: IppAttributes,
+    charset: Charset,
+    limits: ParserLimits,
+    header_size: usize,
+    num_attributes: usize,
+    mode: ParseMode,
+    // offset of the tag being parsed
+    offset: usize,
+    warnings: Vec<ParseWarning>,
 }
 
 impl ParserState {
@@ -81,9 +367,99 @@ impl ParserState {
         ParserState {
             current_group: None,
             last_name: None,
+            current_name: None,
             context: vec![vec![]],
             attributes: IppAttributes::new(),
+            charset: Charset::Utf8,
+            limits: ParserLimits::default(),
+            header_size: HEADER_SIZE,
+            num_attributes: 0,
+            mode: ParseMode::Strict,
+            offset: 0,
+            warnings: Vec::new(),
+        }
+    }
+
+    fn add_header_size(&mut self, size: usize) -> Result<(), IppParseError> {
+        self.header_size = self.header_size.saturating_add(size);
+        if self.header_size > self.limits.max_header_size {
+            return Err(IppParseError::HeaderTooLarge(self.limits.max_header_size));
+        }
+        Ok(())
+    }
+
+    // reject the problem in strict mode, record it in lenient mode
+    // attribute-level problem, an empty name refers to the last attribute
+    fn problem(&mut self, name: &str, problem: ParseProblem, error: IppParseError) -> Result<(), IppParseError> {
+        let attribute = match self.last_name {
+            Some(ref last_name) if name.is_empty() => last_name.to_string(),
+            _ => name.to_owned(),
+        };
+        self.report(attribute, problem, error)
+    }
+
+    // group-level problem, not related to any attribute
+    fn group_problem(&mut self, problem: ParseProblem, error: IppParseError) -> Result<(), IppParseError> {
+        self.report(String::new(), problem, error)
+    }
+
+    fn report(&mut self, attribute: String, problem: ParseProblem, error: IppParseError) -> Result<(), IppParseError> {
+        match self.mode {
+            ParseMode::Strict => Err(error),
+            ParseMode::Lenient => {
+                let warning = ParseWarning {
+                    offset: self.offset,
+                    attribute,
+                    problem,
+                };
+                #[cfg(feature = "log")]
+                warn!("{warning}");
+                self.warnings.push(warning);
+                Ok(())
+            }
+        }
+    }
+
+    // registry entry of the innermost collection being parsed, following the open member names
+    fn collection_info(&self) -> Option<&'static AttributeInfo> {
+        let mut info = registry::lookup(self.last_name.as_ref()?)?;
+        for list in self.context.iter().skip(1).take(self.context.len().saturating_sub(2)) {
+            info = info.member(last_member(list)?)?;
+        }
+        Some(info)
+    }
+
+    fn end_collection(&mut self) -> Result<(), IppParseError> {
+        let info = self.collection_info();
+        if let Some(arr) = self.context.pop() {
+            let (collection, empty) = collect_members(info, arr);
+            for name in empty {
+                let problem = ParseProblem::MemberWithoutValue(name.to_string());
+                self.problem("", problem.clone(), IppParseError::Malformed(problem))?;
+            }
+            if let [val_list] = self.context.as_slice()
+                && val_list.len() >= self.limits.max_values
+            {
+                return Err(IppParseError::TooManyValues(self.limits.max_values));
+            } else if let Some(val_list) = self.context.last_mut() {
+                val_list.push(IppValue::Collection(collection));
+            }
+        }
+        Ok(())
+    }
+
+    fn close_collections(&mut self, name: &str) -> Result<(), IppParseError> {
+        if self.context.len() > 1 {
+            self.problem(
+                name,
+                ParseProblem::UnterminatedCollection,
+                IppParseError::InvalidCollection,
+            )?;
+            while self.context.len() > 1 {
+                self.end_collection()?;
+            }
         }
+        Ok(())
     }
 
     fn add_last_attribute(&mut self) {
@@ -91,19 +467,84 @@ impl ParserState {
             if let Some(val_list) = self.context.pop()
                 && let Some(ref mut group) = self.current_group
             {
-                let attr = IppAttribute::new(last_name.clone(), list_or_value(val_list));
+                let info = registry::lookup(&last_name);
+                let attr = IppAttribute::new(last_name.clone(), attribute_value(info, val_list));
                 group.attributes_mut().push(attr);
             }
             self.context.push(vec![]);
         }
     }
 
-    fn parse_delimiter(&mut self, tag: u8) -> Result<DelimiterTag, IppParseError> {
+    // move out the attributes completed so far, used by the event parsers
+    fn take_attributes(&mut self) -> Vec<IppAttribute> {
+        let mut attributes = Vec::new();
+        for group in self.attributes.groups_mut().drain(..) {
+            attributes.extend(group.into_attributes());
+        }
+        if let Some(ref mut group) = self.current_group {
+            attributes.append(group.attributes_mut());
+        }
+        attributes
+    }
+
+    fn truncated(&mut self, error: IppParseError) -> Result<bool, IppParseError> {
+        self.problem("", ParseProblem::Truncated, error)?;
+        while self.context.len() > 1 {
+            self.end_collection()?;
+        }
+        self.add_last_attribute();
+        if let Some(group) = self.current_group.take() {
+            self.attributes.groups_mut().push(group);
+        }
+        Ok(true)
+    }
+
+    // keep the attributes parsed so far if the message is truncated in lenient mode,
+    // returns true at the end of the attributes
+    fn end_of_attributes(&mut self, result: Result<bool, IppParseError>) -> Result<bool, IppParseError> {
+        let result = match result {
+            // a truncated header cannot be recovered
+            Err(e) if is_truncated(&e) && self.offset >= HEADER_SIZE => self.truncated(e),
+            result => result,
+        };
+        result.map_err(|e| self.with_position(e))
+    }
+
+    // add the current position to the error
+    fn with_position(&self, error: IppParseError) -> IppParseError {
+        if error.position().is_some() {
+            return error;
+        }
+        let group = self.current_group.as_ref();
+        let attribute = self
+            .current_name
+            .as_ref()
+            .or(self.last_name.as_ref())
+            .or_else(|| group?.attributes().last().map(|attr| attr.name()))
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        IppParseError::Positioned {
+            position: ParsePosition {
+                offset: self.offset,
+                group: group.map(|group| group.tag()),
+                attribute,
+            },
+            source: Box::new(error),
+        }
+    }
+
+    fn parse_delimiter(&mut self, tag: u8) -> Result<Option<DelimiterTag>, IppParseError> {
         #[cfg(feature = "log")]
         trace!("Delimiter tag: {tag:0x}");
 
-        let tag = DelimiterTag::from_u8(tag).ok_or(IppParseError::InvalidTag(tag))?;
+        self.add_header_size(1)?;
 
+        let Some(tag) = DelimiterTag::from_u8(tag) else {
+            self.group_problem(ParseProblem::UnknownDelimiter(tag), IppParseError::InvalidTag(tag))?;
+            return Ok(None);
+        };
+
+        self.close_collections("")?;
         self.add_last_attribute();
 
         if let Some(group) = self.current_group.take() {
@@ -111,58 +552,98 @@ impl ParserState {
         }
 
         self.current_group = Some(IppAttributeGroup::new(tag));
+        self.num_attributes = 0;
 
-        Ok(tag)
+        Ok(Some(tag))
     }
 
     fn parse_value(&mut self, tag: u8, name: IppName, value: Bytes) -> Result<(), IppParseError> {
-        let ipp_value = IppValue::parse(tag, value)?;
+        // tag and two length fields
+        if !name.is_empty() {
+            self.current_name = Some(name.clone());
+        }
+
+        self.add_header_size(5 + name.len() + value.len())?;
+
+        if !name.is_empty() || tag == ValueTag::MemberAttrName as u8 {
+            self.num_attributes += 1;
+            if self.num_attributes > self.limits.max_attributes {
+                return Err(IppParseError::TooManyAttributes(self.limits.max_attributes));
+            }
+        }
+
+        let ipp_value = match IppValue::parse_with_charset(tag, value.clone(), self.charset) {
+            Ok(ipp_value) => ipp_value,
+            Err(e) => {
+                self.problem(name.as_str(), ParseProblem::InvalidValue(e.to_string()), e)?;
+                IppValue::Other { tag, data: value }
+            }
+        };
+        self.current_name = None;
+
+        // text and name values which follow are encoded in the declared charset
+        if name.as_str() == IppAttribute::ATTRIBUTES_CHARSET
+            && let IppValue::Charset(ref charset) = ipp_value
+        {
+            self.charset = Charset::from_name(charset).unwrap_or_default();
+        }
 
         #[cfg(feature = "log")]
         trace!("Value tag: {tag:0x}: {name}: {ipp_value}");
 
         if !name.is_empty() {
+            if self.current_group.is_none() {
+                self.problem(
+                    name.as_str(),
+                    ParseProblem::AttributeOutsideGroup,
+                    IppParseError::Malformed(ParseProblem::AttributeOutsideGroup),
+                )?;
+            }
+            self.close_collections(name.as_str())?;
             // single attribute or begin of array
             self.add_last_attribute();
             // store it as a previous attribute
             self.last_name = Some(name);
+        } else if self.last_name.is_none() && self.context.len() == 1 {
+            return self.problem(
+                &name,
+                ParseProblem::ValueWithoutName,
+                IppParseError::Malformed(ParseProblem::ValueWithoutName),
+            );
+        }
+
+        let is_delimiter = tag == ValueTag::BegCollection as u8 || tag == ValueTag::EndCollection as u8;
+        if is_delimiter && !matches!(ipp_value, IppValue::Other { ref data, .. } if data.is_empty()) {
+            #[cfg(feature = "log")]
+            error!("Invalid collection delimiter");
+            self.problem("", ParseProblem::MalformedCollection, IppParseError::InvalidCollection)?;
         }
+
         if tag == ValueTag::BegCollection as u8 {
             // start new collection in the stack
             #[cfg(feature = "log")]
             trace!("Begin collection");
-            match ipp_value {
-                IppValue::Other { ref data, .. } if data.is_empty() => {}
-                _ => {
-                    #[cfg(feature = "log")]
-                    error!("Invalid begin collection attribute");
-                    return Err(IppParseError::InvalidCollection);
-                }
+            // the bottom of the stack holds the attribute values
+            if self.context.len() > self.limits.max_depth {
+                return Err(IppParseError::CollectionTooDeep(self.limits.max_depth));
             }
             self.context.push(vec![]);
         } else if tag == ValueTag::EndCollection as u8 {
             // get collection from the stack and add it to the previous element
             #[cfg(feature = "log")]
             trace!("End collection");
-            match ipp_value {
-                IppValue::Other { ref data, .. } if data.is_empty() => {}
-                _ => {
-                    #[cfg(feature = "log")]
-                    error!("Invalid end collection attribute");
-                    return Err(IppParseError::InvalidCollection);
-                }
-            }
-            if let Some(arr) = self.context.pop()
-                && let Some(val_list) = self.context.last_mut()
-            {
-                let mut map: BTreeMap<IppName, IppValue> = BTreeMap::new();
-                for idx in (0..arr.len()).step_by(2) {
-                    if let (Some(IppValue::MemberAttrName(k)), Some(v)) = (arr.get(idx), arr.get(idx + 1)) {
-                        map.insert(k.clone(), v.clone());
-                    }
-                }
-                val_list.push(IppValue::Collection(map));
+            if self.context.len() == 1 {
+                return self.problem(
+                    "",
+                    ParseProblem::UnmatchedEndCollection,
+                    IppParseError::InvalidCollection,
+                );
             }
+            self.end_collection()?;
+        } else if let [val_list] = self.context.as_slice()
+            && val_list.len() >= self.limits.max_values
+        {
+            return Err(IppParseError::TooManyValues(self.limits.max_values));
         } else if let Some(val_list) = self.context.last_mut() {
             // add attribute to the current collection
             val_list.push(ipp_value);
@@ -196,6 +677,18 @@ where
         }
     }
 
+    /// Set the resource limits, [`ParserLimits::default`] is used otherwise
+    pub fn limits(mut self, limits: ParserLimits) -> Self {
+        self.state.limits = limits;
+        self
+    }
+
+    /// Set the parsing mode, [`ParseMode::Strict`] is used otherwise
+    pub fn mode(mut self, mode: ParseMode) -> Self {
+        self.state.mode = mode;
+        self
+    }
+
     async fn parse_value(&mut self, tag: u8) -> Result<(), IppParseError> {
         // value tag
         let name: IppName = self.reader.read_name().await?;
@@ -205,27 +698,33 @@ where
     }
 
     async fn parse_header_attributes(&mut self) -> Result<IppHeader, IppParseError> {
-        let header = self.get_or_parse_header().await?;
+        let header = match self.get_or_parse_header().await {
+            Ok(header) => header,
+            Err(e) => return Err(self.state.with_position(e)),
+        };
         #[cfg(feature = "log")]
         trace!("IPP header: {header:?}");
 
         loop {
-            match self.reader.read_tag().await? {
-                tag @ 0x01..=0x05 => {
-                    if self.state.parse_delimiter(tag)? == DelimiterTag::EndOfAttributes {
-                        break;
-                    }
-                }
-                tag @ 0x10..=0x4a => self.parse_value(tag).await?,
-                tag => {
-                    return Err(IppParseError::InvalidTag(tag));
-                }
+            self.state.offset = self.reader.offset();
+            let result = self.parse_next().await;
+            if self.state.end_of_attributes(result)? {
+                break;
             }
         }
 
         Ok(header)
     }
 
+    // parse the next delimiter or value, returns true at the end of the attributes
+    async fn parse_next(&mut self) -> Result<bool, IppParseError> {
+        match self.reader.read_tag().await? {
+            tag @ 0x00..=0x0f => Ok(self.state.parse_delimiter(tag)? == Some(DelimiterTag::EndOfAttributes)),
+            tag @ 0x10..=0x7f => self.parse_value(tag).await.map(|_| false),
+            tag => Err(IppParseError::InvalidTag(tag)),
+        }
+    }
+
     /// Parse the IPP stream without reading beyond the end of the attributes. The payload stays untouched.
     pub async fn parse_parts(mut self) -> Result<(IppHeader, IppAttributes, AsyncIppReader<R>), IppParseError> {
         let header = self.parse_header_attributes().await?;
@@ -233,17 +732,28 @@ where
     }
 
     /// Parse the IPP stream
-    pub async fn parse(mut self) -> Result<IppRequestResponse, IppParseError>
+    pub async fn parse(self) -> Result<IppRequestResponse, IppParseError>
+    where
+        R: 'static,
+    {
+        self.parse_with_warnings().await.map(|(response, _)| response)
+    }
+
+    /// Parse the IPP stream, returning the problems found in lenient mode
+    pub async fn parse_with_warnings(mut self) -> Result<(IppRequestResponse, Vec<ParseWarning>), IppParseError>
     where
         R: 'static,
     {
         let header = self.parse_header_attributes().await?;
 
-        Ok(IppRequestResponse {
+        let response = IppRequestResponse {
             header,
             attributes: self.state.attributes,
             payload: self.reader.into_payload(),
-        })
+            default_version: None,
+            default_language: None,
+        };
+        Ok((response, self.state.warnings))
     }
 
     /// Get the IppHeader if it has already been parsed, otherwise parse and return it.
@@ -281,6 +791,18 @@ where
         }
     }
 
+    /// Set the resource limits, [`ParserLimits::default`] is used otherwise
+    pub fn limits(mut self, limits: ParserLimits) -> Self {
+        self.state.limits = limits;
+        self
+    }
+
+    /// Set the parsing mode, [`ParseMode::Strict`] is used otherwise
+    pub fn mode(mut self, mode: ParseMode) -> Self {
+        self.state.mode = mode;
+        self
+    }
+
     fn parse_value(&mut self, tag: u8) -> Result<(), IppParseError> {
         // value tag
         let name: IppName = self.reader.read_name()?;
@@ -290,27 +812,30 @@ where
     }
 
     fn parse_header_attributes(&mut self) -> Result<IppHeader, IppParseError> {
-        let header = self.get_or_parse_header()?;
+        let header = self.get_or_parse_header().map_err(|e| self.state.with_position(e))?;
         #[cfg(feature = "log")]
         trace!("IPP header: {header:?}");
 
         loop {
-            match self.reader.read_tag()? {
-                tag @ 0x01..=0x05 => {
-                    if self.state.parse_delimiter(tag)? == DelimiterTag::EndOfAttributes {
-                        break;
-                    }
-                }
-                tag @ 0x10..=0x4a => self.parse_value(tag)?,
-                tag => {
-                    return Err(IppParseError::InvalidTag(tag));
-                }
+            self.state.offset = self.reader.offset();
+            let result = self.parse_next();
+            if self.state.end_of_attributes(result)? {
+                break;
             }
         }
 
         Ok(header)
     }
 
+    // parse the next delimiter or value, returns true at the end of the attributes
+    fn parse_next(&mut self) -> Result<bool, IppParseError> {
+        match self.reader.read_tag()? {
+            tag @ 0x00..=0x0f => Ok(self.state.parse_delimiter(tag)? == Some(DelimiterTag::EndOfAttributes)),
+            tag @ 0x10..=0x7f => self.parse_value(tag).map(|_| false),
+            tag => Err(IppParseError::InvalidTag(tag)),
+        }
+    }
+
     /// Parse the IPP stream without reading beyond the end of the attributes. The payload stays untouched.
     pub fn parse_parts(mut self) -> Result<(IppHeader, IppAttributes, IppReader<R>), IppParseError> {
         let header = self.parse_header_attributes()?;
@@ -318,17 +843,28 @@ where
     }
 
     /// Parse the IPP stream
-    pub fn parse(mut self) -> Result<IppRequestResponse, IppParseError>
+    pub fn parse(self) -> Result<IppRequestResponse, IppParseError>
+    where
+        R: 'static,
+    {
+        self.parse_with_warnings().map(|(response, _)| response)
+    }
+
+    /// Parse the IPP stream, returning the problems found in lenient mode
+    pub fn parse_with_warnings(mut self) -> Result<(IppRequestResponse, Vec<ParseWarning>), IppParseError>
     where
         R: 'static,
     {
         let header = self.parse_header_attributes()?;
 
-        Ok(IppRequestResponse {
+        let response = IppRequestResponse {
             header,
             attributes: self.state.attributes,
             payload: self.reader.into_payload(),
-        })
+            default_version: None,
+            default_language: None,
+        };
+        Ok((response, self.state.warnings))
     }
 
     /// Get the IppHeader if it has already been parsed, otherwise parse and return it.
@@ -343,99 +879,754 @@ where
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::prelude::IppVersion;
+/// IPP parser over an in-memory buffer.
+///
+/// Unlike [`IppParser`] it does not read every name and value into a separate buffer:
+/// attribute names, UTF-8 string values such as keywords, names and texts, binary values and the payload
+/// are slices of the original buffer, see `benches/parse.rs`. Only values which need a conversion are copied,
+/// e.g. names which are not valid UTF-8 or texts in a charset other than UTF-8.
+pub struct IppBytesParser {
+    data: Bytes,
+    len: usize,
+    state: ParserState,
+}
 
-    #[cfg(feature = "async")]
-    #[tokio::test]
-    async fn test_async_parse_no_attributes() {
-        let data = &[1, 1, 0, 0, 0, 0, 0, 0, 3];
-        let result = AsyncIppParser::new(AsyncIppReader::new(futures_util::io::Cursor::new(data)))
-            .parse()
-            .await;
-        assert!(result.is_ok());
+impl IppBytesParser {
+    /// Create an IPP parser over a buffer holding the whole message
+    pub fn new<T>(data: T) -> IppBytesParser
+    where
+        T: Into<Bytes>,
+    {
+        let data = data.into();
+        IppBytesParser {
+            len: data.len(),
+            data,
+            state: ParserState::new(),
+        }
+    }
 
-        let res = result.ok().unwrap();
-        assert!(res.attributes.groups().is_empty());
+    /// Set the resource limits, [`ParserLimits::default`] is used otherwise
+    pub fn limits(mut self, limits: ParserLimits) -> Self {
+        self.state.limits = limits;
+        self
     }
 
-    #[cfg(feature = "async")]
-    #[tokio::test]
-    async fn test_async_parse_single_value() {
-        let data = &[
-            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x04, 0x12, 0x34, 0x56, 0x78, 3,
-        ];
-        let result = AsyncIppParser::new(AsyncIppReader::new(futures_util::io::Cursor::new(data)))
-            .parse()
-            .await;
-        assert!(result.is_ok());
+    /// Set the parsing mode, [`ParseMode::Strict`] is used otherwise
+    pub fn mode(mut self, mode: ParseMode) -> Self {
+        self.state.mode = mode;
+        self
+    }
 
-        let res = result.ok().unwrap();
-        let group = res
-            .attributes
-            .groups_of(DelimiterTag::PrinterAttributes)
-            .next()
-            .unwrap();
-        let attr = group.get("test").unwrap();
-        assert_eq!(attr.value().as_integer(), Some(&0x1234_5678));
+    fn read_slice(&mut self) -> Result<Bytes, IppParseError> {
+        let len = self.data.try_get_u16()? as usize;
+        if self.data.len() < len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        Ok(self.data.split_to(len))
     }
 
-    #[cfg(feature = "async")]
-    #[tokio::test]
-    async fn test_async_parse_array() {
-        let data = &[
-            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x04, 0x12, 0x34, 0x56, 0x78,
-            0x21, 0x00, 0x00, 0x00, 0x04, 0x77, 0x65, 0x43, 0x21, 3,
-        ];
-        let result = AsyncIppParser::new(AsyncIppReader::new(futures_util::io::Cursor::new(data)))
-            .parse()
-            .await;
-        assert!(result.is_ok());
+    fn parse_value(&mut self, tag: u8) -> Result<(), IppParseError> {
+        let name = parse_name(self.read_slice()?)?;
+        let value = self.read_slice()?;
 
-        let res = result.ok().unwrap();
-        let group = res
-            .attributes
-            .groups_of(DelimiterTag::PrinterAttributes)
-            .next()
-            .unwrap();
-        let attr = group.get("test").unwrap();
-        assert_eq!(
-            attr.value().as_array(),
-            Some(&vec![IppValue::Integer(0x1234_5678), IppValue::Integer(0x7765_4321)])
-        );
+        self.state.parse_value(tag, name, value)
     }
 
-    #[cfg(feature = "async")]
-    #[tokio::test]
-    async fn test_async_parse_collection() {
-        let data = vec![
-            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x34, 0, 4, b'c', b'o', b'l', b'l', 0, 0, 0x4a, 0, 0, 0, 4, b'a', b'b', b'c',
-            b'd', 0x44, 0, 0, 0, 3, b'k', b'e', b'y', 0x37, 0, 0, 0, 0, 3,
-        ];
-        let result = AsyncIppParser::new(AsyncIppReader::new(futures_util::io::Cursor::new(data)))
-            .parse()
-            .await;
-        assert!(result.is_ok());
-
-        let res = result.ok().unwrap();
-        let group = res
-            .attributes
-            .groups_of(DelimiterTag::PrinterAttributes)
-            .next()
-            .unwrap();
-        let attr = group.get("coll").unwrap();
-        assert_eq!(
-            attr.value(),
-            &IppValue::Collection(BTreeMap::from([(
-                "abcd".try_into().unwrap(),
-                IppValue::Keyword("key".try_into().unwrap())
-            )]))
-        );
+    fn parse_header(&mut self) -> Result<IppHeader, IppParseError> {
+        Ok(IppHeader::new(
+            IppVersion(self.data.try_get_u16()?),
+            self.data.try_get_i16()?,
+            self.data.try_get_i32()?,
+        ))
     }
 
-    #[cfg(feature = "async")]
+    fn parse_header_attributes(&mut self) -> Result<IppHeader, IppParseError> {
+        let header = self.parse_header().map_err(|e| self.state.with_position(e))?;
+        #[cfg(feature = "log")]
+        trace!("IPP header: {header:?}");
+
+        loop {
+            self.state.offset = self.len - self.data.len();
+            let result = self.parse_next();
+            if self.state.end_of_attributes(result)? {
+                break;
+            }
+        }
+
+        Ok(header)
+    }
+
+    // parse the next delimiter or value, returns true at the end of the attributes
+    fn parse_next(&mut self) -> Result<bool, IppParseError> {
+        match self.data.try_get_u8()? {
+            tag @ 0x00..=0x0f => Ok(self.state.parse_delimiter(tag)? == Some(DelimiterTag::EndOfAttributes)),
+            tag @ 0x10..=0x7f => self.parse_value(tag).map(|_| false),
+            tag => Err(IppParseError::InvalidTag(tag)),
+        }
+    }
+
+    /// Parse the buffer, returning the remaining part of it after the end of the attributes
+    pub fn parse_parts(mut self) -> Result<(IppHeader, IppAttributes, Bytes), IppParseError> {
+        let header = self.parse_header_attributes()?;
+        Ok((header, self.state.attributes, self.data))
+    }
+
+    /// Parse the buffer, the remaining part of it becomes the payload
+    pub fn parse(self) -> Result<IppRequestResponse, IppParseError> {
+        self.parse_with_warnings().map(|(response, _)| response)
+    }
+
+    /// Parse the buffer, returning the problems found in lenient mode
+    pub fn parse_with_warnings(mut self) -> Result<(IppRequestResponse, Vec<ParseWarning>), IppParseError> {
+        let header = self.parse_header_attributes()?;
+
+        let response = IppRequestResponse {
+            header,
+            attributes: self.state.attributes,
+            payload: if self.data.is_empty() {
+                IppPayload::empty()
+            } else {
+                IppPayload::new(io::Cursor::new(self.data))
+            },
+            default_version: None,
+            default_language: None,
+        };
+        Ok((response, self.state.warnings))
+    }
+}
+
+/// Event produced by the event-based parsers
+#[derive(Debug, Clone, PartialEq)]
+pub enum IppParseEvent {
+    /// Start of a new attribute group
+    GroupStart(DelimiterTag),
+    /// Complete attribute of the current group
+    Attribute(IppAttribute),
+    /// End of the attributes, the payload follows
+    EndOfAttributes,
+}
+
+// queue of events emitted by the parser state, shared by the event parsers
+struct EventState {
+    state: ParserState,
+    events: VecDeque<IppParseEvent>,
+    done: bool,
+}
+
+impl EventState {
+    fn new() -> Self {
+        EventState {
+            state: ParserState::new(),
+            events: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn queue_attributes(&mut self) {
+        let attributes = self.state.take_attributes();
+        self.events.extend(attributes.into_iter().map(IppParseEvent::Attribute));
+    }
+
+    fn parse_delimiter(&mut self, tag: u8) -> Result<(), IppParseError> {
+        let Some(tag) = self.state.parse_delimiter(tag)? else {
+            return Ok(());
+        };
+        self.queue_attributes();
+        if tag == DelimiterTag::EndOfAttributes {
+            self.done = true;
+            self.events.push_back(IppParseEvent::EndOfAttributes);
+        } else {
+            self.events.push_back(IppParseEvent::GroupStart(tag));
+        }
+        Ok(())
+    }
+
+    // stop after an error, unless the message is truncated in lenient mode
+    fn end_of_attributes(&mut self, result: Result<(), IppParseError>) -> Result<(), IppParseError> {
+        let done = self.done;
+        match self.state.end_of_attributes(result.map(|_| done)) {
+            Ok(true) if !done => {
+                self.queue_attributes();
+                self.done = true;
+                self.events.push_back(IppParseEvent::EndOfAttributes);
+                Ok(())
+            }
+            Ok(_) => Ok(()),
+            Err(e) => {
+                self.done = true;
+                Err(e)
+            }
+        }
+    }
+
+    fn parse_value(&mut self, tag: u8, name: IppName, value: Bytes) -> Result<(), IppParseError> {
+        self.state.parse_value(tag, name, value)?;
+        self.queue_attributes();
+        Ok(())
+    }
+
+    // add the event to the group being collected, returns false when the group is complete
+    fn collect_group(&mut self, group: &mut Option<IppAttributeGroup>, event: IppParseEvent) -> bool {
+        match event {
+            IppParseEvent::GroupStart(tag) if group.is_none() => {
+                *group = Some(IppAttributeGroup::new(tag));
+                true
+            }
+            IppParseEvent::GroupStart(tag) => {
+                // keep it for the next group
+                self.events.push_front(IppParseEvent::GroupStart(tag));
+                false
+            }
+            IppParseEvent::Attribute(attr) => {
+                if let Some(group) = group {
+                    group.attributes_mut().push(attr);
+                }
+                true
+            }
+            IppParseEvent::EndOfAttributes => false,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous event-based IPP parser.
+///
+/// Instead of building the whole attribute tree it yields attributes one by one as they are read,
+/// which keeps memory usage low for large responses. After the end of the attributes the reader
+/// is positioned at the payload.
+pub struct AsyncIppEventParser<R> {
+    reader: AsyncIppReader<R>,
+    events: EventState,
+    parsed_header: Option<IppHeader>,
+}
+
+#[cfg(feature = "async")]
+impl<R> AsyncIppEventParser<R>
+where
+    R: AsyncRead + Send + Unpin,
+{
+    /// Create an event parser from AsyncIppReader
+    pub fn new<T>(reader: T) -> AsyncIppEventParser<R>
+    where
+        T: Into<AsyncIppReader<R>>,
+    {
+        AsyncIppEventParser {
+            reader: reader.into(),
+            events: EventState::new(),
+            parsed_header: None,
+        }
+    }
+
+    /// Set the resource limits, [`ParserLimits::default`] is used otherwise
+    pub fn limits(mut self, limits: ParserLimits) -> Self {
+        self.events.state.limits = limits;
+        self
+    }
+
+    /// Set the parsing mode, [`ParseMode::Strict`] is used otherwise
+    pub fn mode(mut self, mode: ParseMode) -> Self {
+        self.events.state.mode = mode;
+        self
+    }
+
+    /// Get the IppHeader if it has already been parsed, otherwise parse and return it.
+    pub async fn get_or_parse_header(&mut self) -> Result<IppHeader, IppParseError> {
+        match self.parsed_header {
+            Some(header) => Ok(header),
+            None => {
+                self.parsed_header = Some(self.reader.read_header().await?);
+                Ok(self.parsed_header.unwrap())
+            }
+        }
+    }
+
+    async fn read_event(&mut self) -> Result<(), IppParseError> {
+        self.get_or_parse_header().await?;
+
+        self.events.state.offset = self.reader.offset();
+        match self.reader.read_tag().await? {
+            tag @ 0x00..=0x0f => self.events.parse_delimiter(tag),
+            tag @ 0x10..=0x7f => {
+                let name = self.reader.read_name().await?;
+                let value = self.reader.read_value().await?;
+                self.events.parse_value(tag, name, value)
+            }
+            tag => Err(IppParseError::InvalidTag(tag)),
+        }
+    }
+
+    /// Return the next event or `None` after the end of the attributes
+    pub async fn next_event(&mut self) -> Result<Option<IppParseEvent>, IppParseError> {
+        loop {
+            if let Some(event) = self.events.events.pop_front() {
+                return Ok(Some(event));
+            }
+            if self.events.done {
+                return Ok(None);
+            }
+            let result = self.read_event().await;
+            self.events.end_of_attributes(result)?;
+        }
+    }
+
+    /// Return the next complete attribute group or `None` after the end of the attributes
+    pub async fn next_group(&mut self) -> Result<Option<IppAttributeGroup>, IppParseError> {
+        let mut group = None;
+        while let Some(event) = self.next_event().await? {
+            if !self.events.collect_group(&mut group, event) {
+                break;
+            }
+        }
+        Ok(group)
+    }
+
+    /// Return a stream of events
+    pub fn events(&mut self) -> impl Stream<Item = Result<IppParseEvent, IppParseError>> + '_ {
+        futures_util::stream::unfold(self, |parser| async move {
+            parser.next_event().await.transpose().map(|event| (event, parser))
+        })
+    }
+
+    /// Return a stream of complete attribute groups
+    pub fn groups(&mut self) -> impl Stream<Item = Result<IppAttributeGroup, IppParseError>> + '_ {
+        futures_util::stream::unfold(self, |parser| async move {
+            parser.next_group().await.transpose().map(|group| (group, parser))
+        })
+    }
+
+    /// Return the problems found so far in lenient mode
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.events.state.warnings
+    }
+
+    /// Return the underlying reader. It is positioned at the payload after the end of the attributes.
+    pub fn into_reader(self) -> AsyncIppReader<R> {
+        self.reader
+    }
+}
+
+/// Synchronous event-based IPP parser.
+///
+/// Instead of building the whole attribute tree it yields attributes one by one as they are read,
+/// which keeps memory usage low for large responses. After the end of the attributes the reader
+/// is positioned at the payload.
+pub struct IppEventParser<R> {
+    reader: IppReader<R>,
+    events: EventState,
+    parsed_header: Option<IppHeader>,
+}
+
+impl<R> IppEventParser<R>
+where
+    R: 'static + Read + Send,
+{
+    /// Create an event parser from IppReader
+    pub fn new<T>(reader: T) -> IppEventParser<R>
+    where
+        T: Into<IppReader<R>>,
+    {
+        IppEventParser {
+            reader: reader.into(),
+            events: EventState::new(),
+            parsed_header: None,
+        }
+    }
+
+    /// Set the resource limits, [`ParserLimits::default`] is used otherwise
+    pub fn limits(mut self, limits: ParserLimits) -> Self {
+        self.events.state.limits = limits;
+        self
+    }
+
+    /// Set the parsing mode, [`ParseMode::Strict`] is used otherwise
+    pub fn mode(mut self, mode: ParseMode) -> Self {
+        self.events.state.mode = mode;
+        self
+    }
+
+    /// Get the IppHeader if it has already been parsed, otherwise parse and return it.
+    pub fn get_or_parse_header(&mut self) -> Result<IppHeader, IppParseError> {
+        match self.parsed_header {
+            Some(header) => Ok(header),
+            None => {
+                self.parsed_header = Some(self.reader.read_header()?);
+                Ok(self.parsed_header.unwrap())
+            }
+        }
+    }
+
+    fn read_event(&mut self) -> Result<(), IppParseError> {
+        self.get_or_parse_header()?;
+
+        self.events.state.offset = self.reader.offset();
+        match self.reader.read_tag()? {
+            tag @ 0x00..=0x0f => self.events.parse_delimiter(tag),
+            tag @ 0x10..=0x7f => {
+                let name = self.reader.read_name()?;
+                let value = self.reader.read_value()?;
+                self.events.parse_value(tag, name, value)
+            }
+            tag => Err(IppParseError::InvalidTag(tag)),
+        }
+    }
+
+    /// Return the next event or `None` after the end of the attributes
+    pub fn next_event(&mut self) -> Result<Option<IppParseEvent>, IppParseError> {
+        loop {
+            if let Some(event) = self.events.events.pop_front() {
+                return Ok(Some(event));
+            }
+            if self.events.done {
+                return Ok(None);
+            }
+            let result = self.read_event();
+            self.events.end_of_attributes(result)?;
+        }
+    }
+
+    /// Return the next complete attribute group or `None` after the end of the attributes
+    pub fn next_group(&mut self) -> Result<Option<IppAttributeGroup>, IppParseError> {
+        let mut group = None;
+        while let Some(event) = self.next_event()? {
+            if !self.events.collect_group(&mut group, event) {
+                break;
+            }
+        }
+        Ok(group)
+    }
+
+    /// Return an iterator of complete attribute groups
+    pub fn groups(&mut self) -> impl Iterator<Item = Result<IppAttributeGroup, IppParseError>> + '_ {
+        iter::from_fn(|| self.next_group().transpose())
+    }
+
+    /// Return the problems found so far in lenient mode
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.events.state.warnings
+    }
+
+    /// Return the underlying reader. It is positioned at the payload after the end of the attributes.
+    pub fn into_reader(self) -> IppReader<R> {
+        self.reader
+    }
+}
+
+impl<R> Iterator for IppEventParser<R>
+where
+    R: 'static + Read + Send,
+{
+    type Item = Result<IppParseEvent, IppParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_event().transpose()
+    }
+}
+
+/// Event produced by [`IppPushParser`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum IppPushEvent {
+    /// The buffered data is not enough to make progress, feed more data
+    NeedMoreData,
+    /// The IPP header has been parsed
+    Header(IppHeader),
+    /// An attribute group, an attribute or the end of the attributes has been parsed
+    Event(IppParseEvent),
+    /// Payload data following the attributes
+    Payload(Bytes),
+    /// The end of the input has been reached and all events have been returned
+    Finished,
+}
+
+/// Sans-IO incremental IPP parser.
+///
+/// The parser does not read from any stream: the caller feeds it with chunks of data as they arrive
+/// and polls it for events until it needs more data. This allows driving it from any event loop or callback.
+///
+/// ```rust
+/// use ipp::parser::{IppParseEvent, IppPushEvent, IppPushParser};
+///
+/// let mut parser = IppPushParser::new();
+/// parser.feed(&[1, 1, 0, 0, 0, 0, 0, 1, 0x04]);
+/// parser.feed(&[0x21, 0, 6, b'c', b'o', b'p', b'i', b'e', b's', 0, 4, 0, 0, 0, 2, 0x03, b'%']);
+/// parser.finish();
+///
+/// loop {
+///     match parser.next_event().unwrap() {
+///         IppPushEvent::Event(IppParseEvent::Attribute(attr)) => assert_eq!(attr.name().as_str(), "copies"),
+///         IppPushEvent::Payload(data) => assert_eq!(data, "%"),
+///         IppPushEvent::Finished => break,
+///         _ => {}
+///     }
+/// }
+/// assert_eq!(parser.payload_offset(), Some(25));
+/// ```
+pub struct IppPushParser {
+    buffer: BytesMut,
+    events: EventState,
+    header: Option<IppHeader>,
+    offset: usize,
+    payload_offset: Option<usize>,
+    eof: bool,
+    failed: bool,
+}
+
+impl IppPushParser {
+    /// Create a push parser
+    pub fn new() -> IppPushParser {
+        IppPushParser {
+            buffer: BytesMut::new(),
+            events: EventState::new(),
+            header: None,
+            offset: 0,
+            payload_offset: None,
+            eof: false,
+            failed: false,
+        }
+    }
+
+    /// Set the resource limits, [`ParserLimits::default`] is used otherwise
+    pub fn limits(mut self, limits: ParserLimits) -> Self {
+        self.events.state.limits = limits;
+        self
+    }
+
+    /// Set the parsing mode, [`ParseMode::Strict`] is used otherwise
+    pub fn mode(mut self, mode: ParseMode) -> Self {
+        self.events.state.mode = mode;
+        self
+    }
+
+    /// Append a chunk of data to the internal buffer
+    pub fn feed(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Signal the end of the input. A message truncated before the end of the attributes
+    /// results in an error in strict mode and in a warning in lenient mode.
+    pub fn finish(&mut self) {
+        self.eof = true;
+    }
+
+    /// Number of bytes consumed so far
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the parsed header
+    pub fn header(&self) -> Option<IppHeader> {
+        self.header
+    }
+
+    /// Offset of the payload in the input, known after the end of the attributes
+    pub fn payload_offset(&self) -> Option<usize> {
+        self.payload_offset
+    }
+
+    /// Return the problems found so far in lenient mode
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.events.state.warnings
+    }
+
+    fn consume(&mut self, len: usize) -> Bytes {
+        self.offset += len;
+        self.buffer.split_to(len).freeze()
+    }
+
+    // parse the next item if it is fully buffered, returns false if more data is needed
+    fn parse_item(&mut self) -> Result<bool, IppParseError> {
+        let Some(&tag) = self.buffer.first() else {
+            return Ok(false);
+        };
+        match tag {
+            0x00..=0x0f => {
+                self.consume(1);
+                self.events.parse_delimiter(tag)?;
+                if self.events.done {
+                    self.payload_offset = Some(self.offset);
+                }
+                Ok(true)
+            }
+            0x10..=0x7f => {
+                let len_at = |pos: usize| {
+                    self.buffer
+                        .get(pos..pos + 2)
+                        .map(|len| u16::from_be_bytes([len[0], len[1]]) as usize)
+                };
+                let Some(name_len) = len_at(1) else {
+                    return Ok(false);
+                };
+                let Some(value_len) = len_at(3 + name_len) else {
+                    return Ok(false);
+                };
+                if self.buffer.len() < 5 + name_len + value_len {
+                    return Ok(false);
+                }
+                let mut item = self.consume(5 + name_len + value_len);
+                item.advance(3);
+                let name = parse_name(item.split_to(name_len))?;
+                item.advance(2);
+                self.events.parse_value(tag, name, item)?;
+                Ok(true)
+            }
+            tag => Err(IppParseError::InvalidTag(tag)),
+        }
+    }
+
+    fn truncated() -> IppParseError {
+        io::Error::from(io::ErrorKind::UnexpectedEof).into()
+    }
+
+    /// Return the next event
+    pub fn next_event(&mut self) -> Result<IppPushEvent, IppParseError> {
+        if self.failed {
+            return Ok(IppPushEvent::Finished);
+        }
+        loop {
+            if let Some(event) = self.events.events.pop_front() {
+                return Ok(IppPushEvent::Event(event));
+            }
+
+            if self.events.done {
+                return Ok(if !self.buffer.is_empty() {
+                    IppPushEvent::Payload(self.consume(self.buffer.len()))
+                } else if self.eof {
+                    IppPushEvent::Finished
+                } else {
+                    IppPushEvent::NeedMoreData
+                });
+            }
+
+            let result = match self.header {
+                None if self.buffer.len() >= HEADER_SIZE => {
+                    let mut data = self.consume(HEADER_SIZE);
+                    let header = IppHeader::new(IppVersion(data.get_u16()), data.get_i16(), data.get_i32());
+                    self.header = Some(header);
+                    return Ok(IppPushEvent::Header(header));
+                }
+                None => Ok(false),
+                Some(_) => {
+                    self.events.state.offset = self.offset;
+                    self.parse_item()
+                }
+            };
+
+            let result = match result {
+                Ok(false) if self.eof => Err(Self::truncated()),
+                Ok(false) => return Ok(IppPushEvent::NeedMoreData),
+                result => result.map(|_| ()),
+            };
+            if let Err(e) = self.events.end_of_attributes(result) {
+                self.failed = true;
+                return Err(e);
+            }
+            if self.events.done && self.payload_offset.is_none() {
+                // truncated message in lenient mode, the incomplete item is discarded
+                self.consume(self.buffer.len());
+                self.payload_offset = Some(self.offset);
+            }
+        }
+    }
+}
+
+impl Default for IppPushParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{model::StatusCode, prelude::IppVersion};
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_parse_no_attributes() {
+        let data = &[1, 1, 0, 0, 0, 0, 0, 0, 3];
+        let result = AsyncIppParser::new(AsyncIppReader::new(futures_util::io::Cursor::new(data)))
+            .parse()
+            .await;
+        assert!(result.is_ok());
+
+        let res = result.ok().unwrap();
+        assert!(res.attributes.groups().is_empty());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_parse_single_value() {
+        let data = &[
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x04, 0x12, 0x34, 0x56, 0x78, 3,
+        ];
+        let result = AsyncIppParser::new(AsyncIppReader::new(futures_util::io::Cursor::new(data)))
+            .parse()
+            .await;
+        assert!(result.is_ok());
+
+        let res = result.ok().unwrap();
+        let group = res
+            .attributes
+            .groups_of(DelimiterTag::PrinterAttributes)
+            .next()
+            .unwrap();
+        let attr = group.get("test").unwrap();
+        assert_eq!(attr.value().as_integer(), Some(&0x1234_5678));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_parse_array() {
+        let data = &[
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x04, 0x12, 0x34, 0x56, 0x78,
+            0x21, 0x00, 0x00, 0x00, 0x04, 0x77, 0x65, 0x43, 0x21, 3,
+        ];
+        let result = AsyncIppParser::new(AsyncIppReader::new(futures_util::io::Cursor::new(data)))
+            .parse()
+            .await;
+        assert!(result.is_ok());
+
+        let res = result.ok().unwrap();
+        let group = res
+            .attributes
+            .groups_of(DelimiterTag::PrinterAttributes)
+            .next()
+            .unwrap();
+        let attr = group.get("test").unwrap();
+        assert_eq!(
+            attr.value().as_array(),
+            Some(&vec![IppValue::Integer(0x1234_5678), IppValue::Integer(0x7765_4321)])
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_parse_collection() {
+        let data = vec![
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x34, 0, 4, b'c', b'o', b'l', b'l', 0, 0, 0x4a, 0, 0, 0, 4, b'a', b'b', b'c',
+            b'd', 0x44, 0, 0, 0, 3, b'k', b'e', b'y', 0x37, 0, 0, 0, 0, 3,
+        ];
+        let result = AsyncIppParser::new(AsyncIppReader::new(futures_util::io::Cursor::new(data)))
+            .parse()
+            .await;
+        assert!(result.is_ok());
+
+        let res = result.ok().unwrap();
+        let group = res
+            .attributes
+            .groups_of(DelimiterTag::PrinterAttributes)
+            .next()
+            .unwrap();
+        let attr = group.get("coll").unwrap();
+        assert_eq!(
+            attr.value(),
+            &IppValue::Collection(IppCollection::from([(
+                "abcd".try_into().unwrap(),
+                IppValue::Keyword("key".try_into().unwrap())
+            )]))
+        );
+    }
+
+    #[cfg(feature = "async")]
     #[tokio::test]
     async fn test_async_parse_with_payload() {
         let data = vec![
@@ -525,42 +1716,255 @@ mod tests {
         let result = IppParser::new(IppReader::new(io::Cursor::new(data))).parse();
         assert!(result.is_ok());
 
-        let res = result.ok().unwrap();
-        let group = res
+        let res = result.ok().unwrap();
+        let group = res
+            .attributes
+            .groups_of(DelimiterTag::PrinterAttributes)
+            .next()
+            .unwrap();
+        let attr = group.get("test").unwrap();
+        assert_eq!(
+            attr.value().as_array(),
+            Some(&vec![IppValue::Integer(0x1234_5678), IppValue::Integer(0x7765_4321)])
+        );
+    }
+
+    #[test]
+    fn test_parse_collection() {
+        let data = vec![
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x34, 0, 4, b'c', b'o', b'l', b'l', 0, 0, 0x4a, 0, 0, 0, 4, b'a', b'b', b'c',
+            b'd', 0x44, 0, 0, 0, 3, b'k', b'e', b'y', 0x37, 0, 0, 0, 0, 3,
+        ];
+        let result = IppParser::new(IppReader::new(io::Cursor::new(data))).parse();
+        assert!(result.is_ok());
+
+        let res = result.ok().unwrap();
+        let group = res
+            .attributes
+            .groups_of(DelimiterTag::PrinterAttributes)
+            .next()
+            .unwrap();
+        let attr = group.get("coll").unwrap();
+        assert_eq!(
+            attr.value(),
+            &IppValue::Collection(IppCollection::from([(
+                "abcd".try_into().unwrap(),
+                IppValue::Keyword("key".try_into().expect("failed to create IPP text value"))
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_collection_round_trip() {
+        let coll = IppCollection::from([
+            (
+                "media-size-name".try_into().unwrap(),
+                IppValue::new_keyword("iso_a4_210x297").unwrap(),
+            ),
+            (
+                "media-source".try_into().unwrap(),
+                IppValue::new_keyword("tray-1").unwrap(),
+            ),
+            (
+                "media-source".try_into().unwrap(),
+                IppValue::new_keyword("tray-2").unwrap(),
+            ),
+            (
+                "media-color".try_into().unwrap(),
+                IppValue::new_keyword("white").unwrap(),
+            ),
+        ]);
+        let buf = IppAttribute::new("media-col".try_into().unwrap(), IppValue::Collection(coll.clone())).to_bytes();
+
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(buf.clone());
+        data.push(3);
+
+        let res = IppParser::new(IppReader::new(io::Cursor::new(data))).parse().unwrap();
+        let attr = res
+            .attributes
+            .groups_of(DelimiterTag::PrinterAttributes)
+            .next()
+            .unwrap()
+            .get("media-col")
+            .unwrap();
+        assert_eq!(attr.value(), &IppValue::Collection(coll));
+        assert_eq!(attr.to_bytes(), buf);
+
+        let coll = attr.value().as_collection().unwrap();
+        assert_eq!(
+            coll.get("media-source").unwrap().as_keyword().unwrap().as_ref(),
+            "tray-1"
+        );
+        assert_eq!(coll.get_all("media-source").count(), 2);
+    }
+
+    #[test]
+    fn test_parse_collection_with_set_of_members() {
+        // synthetic finishings-col modelled on a Get-Printer-Attributes response, stitching-locations has two values
+        let attr = [
+            &[0x34, 0x00, 0x0e][..],
+            b"finishings-col",
+            &[0x00, 0x00],
+            &[0x4a, 0x00, 0x00, 0x00, 0x12],
+            b"finishing-template",
+            &[0x44, 0x00, 0x00, 0x00, 0x10],
+            b"staple-dual-left",
+            &[0x4a, 0x00, 0x00, 0x00, 0x09],
+            b"stitching",
+            &[0x34, 0x00, 0x00, 0x00, 0x00],
+            &[0x4a, 0x00, 0x00, 0x00, 0x13],
+            b"stitching-locations",
+            &[0x21, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x14],
+            &[0x21, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x50],
+            &[0x4a, 0x00, 0x00, 0x00, 0x18],
+            b"stitching-reference-edge",
+            &[0x44, 0x00, 0x00, 0x00, 0x04],
+            b"left",
+            &[0x37, 0x00, 0x00, 0x00, 0x00],
+            &[0x37, 0x00, 0x00, 0x00, 0x00],
+        ]
+        .concat();
+        let data = [&[1, 1, 0, 0, 0, 0, 0, 0, 4][..], &attr, &[3]].concat();
+
+        let res = IppParser::new(IppReader::new(io::Cursor::new(data))).parse().unwrap();
+        let parsed = res
+            .attributes
+            .groups_of(DelimiterTag::PrinterAttributes)
+            .next()
+            .unwrap()
+            .get("finishings-col")
+            .unwrap();
+
+        // finishings-col is registered as 1setOf collection, so a single value is kept as an array
+        let expected = IppValue::Array(vec![IppValue::Collection(IppCollection::from([
+            (
+                "finishing-template".try_into().unwrap(),
+                IppValue::new_keyword("staple-dual-left").unwrap(),
+            ),
+            (
+                "stitching".try_into().unwrap(),
+                IppValue::Collection(IppCollection::from([
+                    (
+                        "stitching-locations".try_into().unwrap(),
+                        IppValue::Array(vec![IppValue::Integer(20), IppValue::Integer(80)]),
+                    ),
+                    (
+                        "stitching-reference-edge".try_into().unwrap(),
+                        IppValue::new_keyword("left").unwrap(),
+                    ),
+                ])),
+            ),
+        ]))]);
+        assert_eq!(parsed.value(), &expected);
+        assert_eq!(parsed.to_bytes(), attr);
+    }
+
+    #[test]
+    fn test_collection_member_with_mixed_tags() {
+        // media-col-database entry where media-source is keyword | name
+        let coll = IppCollection::from([(
+            "media-source".try_into().unwrap(),
+            IppValue::Array(vec![
+                IppValue::new_keyword("tray-1").unwrap(),
+                IppValue::new_name_without_language("Manual Feed").unwrap(),
+            ]),
+        )]);
+        let attr = IppAttribute::new("media-col".try_into().unwrap(), IppValue::Collection(coll.clone()));
+        let buf = attr.to_bytes();
+        assert!(buf.windows(3).any(|w| w == [0x42, 0x00, 0x00]));
+
+        let data = [&[1, 1, 0, 0, 0, 0, 0, 0, 4][..], &buf, &[3]].concat();
+        let res = IppParser::new(IppReader::new(io::Cursor::new(data))).parse().unwrap();
+        let parsed = res
             .attributes
             .groups_of(DelimiterTag::PrinterAttributes)
             .next()
+            .unwrap()
+            .get("media-col")
             .unwrap();
-        let attr = group.get("test").unwrap();
-        assert_eq!(
-            attr.value().as_array(),
-            Some(&vec![IppValue::Integer(0x1234_5678), IppValue::Integer(0x7765_4321)])
-        );
+        assert_eq!(parsed.value(), &IppValue::Collection(coll));
     }
 
     #[test]
-    fn test_parse_collection() {
-        let data = vec![
-            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x34, 0, 4, b'c', b'o', b'l', b'l', 0, 0, 0x4a, 0, 0, 0, 4, b'a', b'b', b'c',
-            b'd', 0x44, 0, 0, 0, 3, b'k', b'e', b'y', 0x37, 0, 0, 0, 0, 3,
-        ];
-        let result = IppParser::new(IppReader::new(io::Cursor::new(data))).parse();
-        assert!(result.is_ok());
+    fn test_parse_single_value_set_of() {
+        let attr = [
+            &[0x49, 0x00, 0x19][..],
+            b"document-format-supported",
+            &[0x00, 0x0f],
+            b"application/pdf",
+            &[0x23, 0x00, 0x0d],
+            b"printer-state",
+            &[0x00, 0x04, 0x00, 0x00, 0x00, 0x03],
+        ]
+        .concat();
+        let data = [&[1, 1, 0, 0, 0, 0, 0, 0, 4][..], &attr, &[3]].concat();
 
-        let res = result.ok().unwrap();
+        let res = IppParser::new(IppReader::new(io::Cursor::new(data))).parse().unwrap();
         let group = res
             .attributes
             .groups_of(DelimiterTag::PrinterAttributes)
             .next()
             .unwrap();
-        let attr = group.get("coll").unwrap();
+
+        let formats = group.get(IppAttribute::DOCUMENT_FORMAT_SUPPORTED).unwrap();
         assert_eq!(
-            attr.value(),
-            &IppValue::Collection(BTreeMap::from([(
-                "abcd".try_into().unwrap(),
-                IppValue::Keyword("key".try_into().expect("failed to create IPP text value"))
-            )]))
+            formats.value(),
+            &IppValue::Array(vec![IppValue::new_mime_media_type("application/pdf").unwrap()])
         );
+        assert_eq!(formats.values().len(), 1);
+
+        let state = group.get(IppAttribute::PRINTER_STATE).unwrap();
+        assert_eq!(state.value(), &IppValue::Enum(3));
+        assert_eq!(state.values(), &[IppValue::Enum(3)]);
+
+        let bytes = [formats.to_bytes(), state.to_bytes()].concat();
+        assert_eq!(bytes, attr);
+    }
+
+    #[test]
+    fn test_parse_single_value_set_of_member() {
+        // finishings-col with a single stitching location and an unregistered attribute with a single value
+        let attr = [
+            &[0x34, 0x00, 0x0e][..],
+            b"finishings-col",
+            &[0x00, 0x00],
+            &[0x4a, 0x00, 0x00, 0x00, 0x09],
+            b"stitching",
+            &[0x34, 0x00, 0x00, 0x00, 0x00],
+            &[0x4a, 0x00, 0x00, 0x00, 0x13],
+            b"stitching-locations",
+            &[0x21, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x14],
+            &[0x37, 0x00, 0x00, 0x00, 0x00],
+            &[0x37, 0x00, 0x00, 0x00, 0x00],
+            &[0x44, 0x00, 0x0c],
+            b"x-vendor-set",
+            &[0x00, 0x03],
+            b"one",
+        ]
+        .concat();
+        let data = [&[1, 1, 0, 0, 0, 0, 0, 0, 4][..], &attr, &[3]].concat();
+
+        let res = IppParser::new(IppReader::new(io::Cursor::new(data))).parse().unwrap();
+        let group = res.attributes().first_of(DelimiterTag::PrinterAttributes).unwrap();
+
+        let finishings = group.get(IppAttribute::FINISHINGS_COL).unwrap();
+        let expected = IppValue::Array(vec![IppValue::Collection(IppCollection::from([(
+            "stitching".try_into().unwrap(),
+            IppValue::Collection(IppCollection::from([(
+                "stitching-locations".try_into().unwrap(),
+                IppValue::Array(vec![IppValue::Integer(20)]),
+            )])),
+        )]))]);
+        assert_eq!(finishings.value(), &expected);
+
+        // the registry does not know whether it is 1setOf, a single value is a scalar
+        let vendor = group.get("x-vendor-set").unwrap();
+        assert_eq!(vendor.value(), &IppValue::new_keyword("one").unwrap());
+        assert_eq!(vendor.values().len(), 1);
+
+        let bytes = [finishings.to_bytes(), vendor.to_bytes()].concat();
+        assert_eq!(bytes, attr);
     }
 
     #[test]
@@ -677,12 +2081,738 @@ mod tests {
         assert_eq!(attr.value().as_integer(), Some(&0x1234_5678));
     }
 
+    const EXTENDED_GROUPS: &[u8] = &[
+        2, 0, 0, 0, 0, 0, 0, 1, 0x06, 0x21, 0x00, 0x0f, b'n', b'o', b't', b'i', b'f', b'y', b'-', b'l', b'e', b'a',
+        b's', b'e', b'-', b'i', b'd', 0x00, 0x04, 0x00, 0x00, 0x00, 0x01, 0x07, 0x09, 0x0a, 0x03,
+    ];
+
+    #[test]
+    fn test_parse_extended_groups() {
+        let res = IppParser::new(IppReader::new(io::Cursor::new(EXTENDED_GROUPS)))
+            .parse()
+            .unwrap();
+
+        let tags = res.attributes().groups().iter().map(|g| g.tag()).collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            vec![
+                DelimiterTag::SubscriptionAttributes,
+                DelimiterTag::EventNotificationAttributes,
+                DelimiterTag::DocumentAttributes,
+                DelimiterTag::SystemAttributes
+            ]
+        );
+        let attr = res.attributes().groups()[0].get("notify-lease-id").unwrap();
+        assert_eq!(attr.value().as_integer(), Some(&1));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_parse_extended_groups() {
+        let res = AsyncIppParser::new(AsyncIppReader::new(futures_util::io::Cursor::new(EXTENDED_GROUPS)))
+            .parse()
+            .await
+            .unwrap();
+
+        let tags = res.attributes().groups().iter().map(|g| g.tag()).collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            vec![
+                DelimiterTag::SubscriptionAttributes,
+                DelimiterTag::EventNotificationAttributes,
+                DelimiterTag::DocumentAttributes,
+                DelimiterTag::SystemAttributes
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extended_groups_to_bytes() {
+        let mut attributes = IppAttributes::new();
+        attributes.add(
+            DelimiterTag::ResourceAttributes,
+            IppAttribute::new("resource-id".try_into().unwrap(), IppValue::Integer(5)),
+        );
+        let res = IppParser::new(IppReader::new(io::Cursor::new(
+            [&[2, 0, 0, 0, 0, 0, 0, 1][..], &attributes.to_bytes()].concat(),
+        )))
+        .parse()
+        .unwrap();
+
+        let group = res.attributes().first_of(DelimiterTag::ResourceAttributes).unwrap();
+        assert_eq!(group.get("resource-id").unwrap().value().as_integer(), Some(&5));
+    }
+
+    #[test]
+    fn test_parse_failed_for_unknown_delimiter() {
+        let data = &[1, 1, 0, 0, 0, 0, 0, 0, 0x0b, 3];
+        let result = IppParser::new(IppReader::new(io::Cursor::new(data))).parse();
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::InvalidTag(0x0b))
+        ));
+    }
+
     #[test]
     fn test_parse_failed_for_invalid_value() {
         let data = &[
             1, 1, 0, 0, 0, 0, 0, 0, 4, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x00, 3,
         ];
         let result = IppParser::new(IppReader::new(io::Cursor::new(data))).parse();
-        assert!(matches!(result, Err(IppParseError::InvalidValueLength(_))));
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::InvalidValueLength(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_declared_charset() {
+        let mut response = IppRequestResponse::new_response(IppVersion::v1_1(), StatusCode::SuccessfulOk, 1).unwrap();
+        let info = IppValue::new_text_without_language("Drucker für Grüße").unwrap();
+        let location = IppValue::new_name_with_language("de", "Büro").unwrap();
+        response.attributes_mut().add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::with_name("printer-info", info.clone()).unwrap(),
+        );
+        response.attributes_mut().add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::with_name("printer-location", location.clone()).unwrap(),
+        );
+        response.encode_charset(Charset::Iso8859_1).unwrap();
+        let group = response.attributes().first_of(DelimiterTag::PrinterAttributes).unwrap();
+        assert!(matches!(
+            group.get("printer-info").unwrap().value(),
+            IppValue::Encoded {
+                charset: Charset::Iso8859_1,
+                ..
+            }
+        ));
+        assert_eq!(group.get("printer-info").unwrap().value().decode_charset(), info);
+
+        let data = response.to_bytes();
+        assert!(data.windows(4).any(|w| w == b"B\xfcro"));
+
+        let res = IppParser::new(IppReader::new(io::Cursor::new(data))).parse().unwrap();
+        let group = res.attributes().first_of(DelimiterTag::PrinterAttributes).unwrap();
+        assert_eq!(group.get("printer-info").unwrap().value(), &info);
+        assert_eq!(group.get("printer-location").unwrap().value(), &location);
+    }
+
+    #[test]
+    fn test_parse_long_latin1_name() {
+        let mut response = IppRequestResponse::new_response(IppVersion::v1_1(), StatusCode::SuccessfulOk, 1).unwrap();
+        response.set_charset("iso-8859-1".try_into().unwrap());
+        for (name, len) in [("short", 100), ("long", 200)] {
+            response.attributes_mut().add(
+                DelimiterTag::PrinterAttributes,
+                IppAttribute::with_name(
+                    name,
+                    IppValue::new_non_utf8(ValueTag::NameWithoutLanguage, Bytes::from(vec![0xe9; len])),
+                )
+                .unwrap(),
+            );
+        }
+
+        // the 255 octet limit applies to the wire, a value which is too long in utf-8 is kept as is
+        let res = IppParser::new(IppReader::new(io::Cursor::new(response.to_bytes())))
+            .parse()
+            .unwrap();
+        let group = res.attributes().first_of(DelimiterTag::PrinterAttributes).unwrap();
+        assert_eq!(
+            group.get("short").unwrap().value(),
+            &IppValue::new_name_without_language("é".repeat(100)).unwrap()
+        );
+        assert_eq!(
+            group.get("long").unwrap().value(),
+            &IppValue::new_non_utf8(ValueTag::NameWithoutLanguage, Bytes::from(vec![0xe9; 200]))
+        );
+    }
+
+    #[test]
+    fn test_parse_undeclared_charset() {
+        let data = &[
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x41, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x01, 0xfc, 3,
+        ];
+        let res = IppParser::new(IppReader::new(io::Cursor::new(data))).parse().unwrap();
+        let group = res.attributes().first_of(DelimiterTag::PrinterAttributes).unwrap();
+        assert!(matches!(group.get("test").unwrap().value(), IppValue::NonUtf8 { .. }));
+    }
+
+    #[test]
+    fn test_bytes_parser_equivalence() {
+        let data = Bytes::from_static(&[
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x34, 0, 4, b'c', b'o', b'l', b'l', 0, 0, 0x4a, 0, 0, 0, 4, b'a', b'b', b'c',
+            b'd', 0x44, 0, 0, 0, 3, b'k', b'e', b'y', 0x37, 0, 0, 0, 0, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00,
+            0x04, 0x12, 0x34, 0x56, 0x78, 3,
+        ]);
+        let expected = IppParser::new(IppReader::new(io::Cursor::new(data.clone())))
+            .parse()
+            .unwrap();
+        let res = IppBytesParser::new(data).parse().unwrap();
+        assert_eq!(res.header(), expected.header());
+        assert_eq!(res.attributes(), expected.attributes());
+    }
+
+    #[test]
+    fn test_bytes_parser_zero_copy() {
+        let data = Bytes::from_static(&[
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x30, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x03, 1, 2, 3, 3, b'f', b'o',
+            b'o',
+        ]);
+        let range = data.as_ptr_range();
+
+        let (header, attributes, payload) = IppBytesParser::new(data.clone()).parse_parts().unwrap();
+        assert_eq!(header.version, IppVersion::v1_1());
+        let group = attributes.first_of(DelimiterTag::PrinterAttributes).unwrap();
+        let value = group.get("test").unwrap().value().as_octet_string().unwrap();
+        assert_eq!(value.as_ref(), &[1, 2, 3]);
+        assert!(range.contains(&value.as_ptr()));
+        assert_eq!(payload, "foo");
+        assert!(range.contains(&payload.as_ptr()));
+
+        let mut res = IppBytesParser::new(data).parse().unwrap();
+        let mut cursor = io::Cursor::new(Vec::new());
+        io::copy(&mut res.payload, &mut cursor).unwrap();
+        assert_eq!(cursor.into_inner(), b"foo");
+    }
+
+    #[test]
+    fn test_bytes_parser_shares_strings() {
+        let data = Bytes::from_static(&[
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x44, 0x00, 0x05, b's', b'i', b'd', b'e', b's', 0x00, 0x09, b'o', b'n', b'e',
+            b'-', b's', b'i', b'd', b'e', b'd', 0x35, 0x00, 0x04, b'i', b'n', b'f', b'o', 0x00, 0x07, 0x00, 0x02, b'd',
+            b'e', 0x00, 0x01, b'x', 3,
+        ]);
+        let range = data.as_ptr_range();
+
+        let res = IppBytesParser::new(data).parse().unwrap();
+        let group = res.attributes().first_of(DelimiterTag::PrinterAttributes).unwrap();
+        let sides = &group.attributes()[0];
+        assert!(range.contains(&sides.name().as_ptr()));
+        let keyword = sides.value().as_keyword().unwrap();
+        assert_eq!(keyword.as_str(), "one-sided");
+        assert!(range.contains(&keyword.as_ptr()));
+
+        let IppValue::TextWithLanguage { language, text } = group.get("info").unwrap().value() else {
+            panic!("expected a text with language");
+        };
+        assert_eq!((language.as_str(), text.as_ref()), ("de", "x"));
+        assert!(range.contains(&language.as_ptr()));
+        assert!(range.contains(&text.as_ptr()));
+    }
+
+    #[test]
+    fn test_bytes_parser_truncated() {
+        let data = vec![
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x04, 0x12,
+        ];
+        let result = IppBytesParser::new(data).parse();
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::IoError(_))
+        ));
+    }
+
+    const GROUPS_WITH_PAYLOAD: &[u8] = &[
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x04,
+        0x12, 0x34, 0x56, 0x78, 0x21, 0x00, 0x05, b't', b'e', b's', b't', b'2', 0x00, 0x04, 0x12, 0x34, 0x56, 0xFF,
+        0x04, 0x34, 0, 4, b'c', b'o', b'l', b'l', 0, 0, 0x4a, 0, 0, 0, 4, b'a', b'b', b'c', b'd', 0x44, 0, 0, 0, 3,
+        b'k', b'e', b'y', 0x37, 0, 0, 0, 0, 0x03, b'f', b'o', b'o',
+    ];
+
+    #[test]
+    fn test_event_parser() {
+        let mut parser = IppEventParser::new(IppReader::new(io::Cursor::new(GROUPS_WITH_PAYLOAD)));
+        let events = parser.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        let names = events
+            .iter()
+            .map(|event| match event {
+                IppParseEvent::GroupStart(tag) => format!("{tag:?}"),
+                IppParseEvent::Attribute(attr) => attr.name().to_string(),
+                IppParseEvent::EndOfAttributes => "end".to_owned(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "OperationAttributes",
+                "test",
+                "test2",
+                "PrinterAttributes",
+                "coll",
+                "end"
+            ]
+        );
+        assert!(parser.next_event().unwrap().is_none());
+
+        let mut payload = parser.into_reader().into_payload();
+        let mut cursor = io::Cursor::new(Vec::new());
+        io::copy(&mut payload, &mut cursor).unwrap();
+        assert_eq!(cursor.into_inner(), b"foo");
+    }
+
+    #[test]
+    fn test_event_parser_groups() {
+        let expected = IppParser::new(IppReader::new(io::Cursor::new(GROUPS_WITH_PAYLOAD)))
+            .parse()
+            .unwrap();
+        let mut parser = IppEventParser::new(IppReader::new(io::Cursor::new(GROUPS_WITH_PAYLOAD)));
+        let groups = parser.groups().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(groups, expected.attributes().groups());
+        assert_eq!(parser.get_or_parse_header().unwrap(), *expected.header());
+    }
+
+    #[test]
+    fn test_event_parser_error() {
+        let data = &[
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x04, 0x12,
+        ];
+        let mut parser = IppEventParser::new(IppReader::new(io::Cursor::new(data)));
+        assert_eq!(
+            parser.next().unwrap().unwrap(),
+            IppParseEvent::GroupStart(DelimiterTag::PrinterAttributes)
+        );
+        assert!(parser.next().unwrap().is_err());
+        assert!(parser.next().is_none());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_event_parser() {
+        use futures_util::TryStreamExt;
+
+        let expected = IppParser::new(IppReader::new(io::Cursor::new(GROUPS_WITH_PAYLOAD)))
+            .parse()
+            .unwrap();
+        let mut parser =
+            AsyncIppEventParser::new(AsyncIppReader::new(futures_util::io::Cursor::new(GROUPS_WITH_PAYLOAD)));
+        let groups = parser.groups().try_collect::<Vec<_>>().await.unwrap();
+        assert_eq!(groups, expected.attributes().groups());
+        assert!(parser.events().try_collect::<Vec<_>>().await.unwrap().is_empty());
+
+        let mut payload = parser.into_reader().into_payload();
+        let mut cursor = io::Cursor::new(Vec::new());
+        io::copy(&mut payload, &mut cursor).unwrap();
+        assert_eq!(cursor.into_inner(), b"foo");
+    }
+
+    fn parse_with_limits(data: &'static [u8], limits: ParserLimits) -> Result<IppRequestResponse, IppParseError> {
+        IppParser::new(IppReader::new(io::Cursor::new(data)))
+            .limits(limits)
+            .parse()
+    }
+
+    #[test]
+    fn test_parser_limits() {
+        let limits = ParserLimits::default();
+        assert!(parse_with_limits(GROUPS_WITH_PAYLOAD, limits).is_ok());
+
+        // test and test2 in the operation group, coll and its member in the printer group
+        let result = parse_with_limits(
+            GROUPS_WITH_PAYLOAD,
+            ParserLimits {
+                max_attributes: 1,
+                ..limits
+            },
+        );
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::TooManyAttributes(1))
+        ));
+        assert!(
+            parse_with_limits(
+                GROUPS_WITH_PAYLOAD,
+                ParserLimits {
+                    max_attributes: 2,
+                    ..limits
+                }
+            )
+            .is_ok()
+        );
+
+        let result = parse_with_limits(GROUPS_WITH_PAYLOAD, ParserLimits { max_depth: 0, ..limits });
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::CollectionTooDeep(0))
+        ));
+        assert!(parse_with_limits(GROUPS_WITH_PAYLOAD, ParserLimits { max_depth: 1, ..limits }).is_ok());
+
+        let result = parse_with_limits(
+            GROUPS_WITH_PAYLOAD,
+            ParserLimits {
+                max_header_size: 68,
+                ..limits
+            },
+        );
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::HeaderTooLarge(68))
+        ));
+        assert!(
+            parse_with_limits(
+                GROUPS_WITH_PAYLOAD,
+                ParserLimits {
+                    max_header_size: 69,
+                    ..limits
+                }
+            )
+            .is_ok()
+        );
+
+        let data = &[
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x04, 0x12, 0x34, 0x56, 0x78,
+            0x21, 0x00, 0x00, 0x00, 0x04, 0x77, 0x65, 0x43, 0x21, 3,
+        ];
+        let result = parse_with_limits(
+            data,
+            ParserLimits {
+                max_values: 1,
+                ..limits
+            },
+        );
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::TooManyValues(1))
+        ));
+        assert!(
+            parse_with_limits(
+                data,
+                ParserLimits {
+                    max_values: 2,
+                    ..limits
+                }
+            )
+            .is_ok()
+        );
+        assert!(parse_with_limits(data, ParserLimits::unlimited()).is_ok());
+    }
+
+    #[test]
+    fn test_parser_limits_nested_collections() {
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4, 0x34, 0, 4, b'c', b'o', b'l', b'l', 0, 0];
+        for _ in 0..100 {
+            data.extend([0x4a, 0, 0, 0, 1, b'm', 0x34, 0, 0, 0, 0]);
+        }
+        let result = IppBytesParser::new(data).parse();
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::CollectionTooDeep(16))
+        ));
+    }
+
+    #[test]
+    fn test_parser_limits_collection_values() {
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        for i in 0..50 {
+            let name: &[u8] = if i == 0 { b"media-col-database" } else { b"" };
+            data.extend([0x34, 0, name.len() as u8]);
+            data.extend(name);
+            data.extend([0, 0, 0x4a, 0, 0, 0, 10]);
+            data.extend(b"media-type");
+            data.extend([0x44, 0, 0, 0, 10]);
+            data.extend(b"stationery");
+            data.extend([0x37, 0, 0, 0, 0]);
+        }
+        data.push(3);
+
+        let result = IppBytesParser::new(data.clone())
+            .limits(ParserLimits {
+                max_values: 10,
+                ..ParserLimits::default()
+            })
+            .parse();
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::TooManyValues(10))
+        ));
+
+        let res = IppBytesParser::new(data).parse().unwrap();
+        let group = res
+            .attributes()
+            .groups_of(DelimiterTag::PrinterAttributes)
+            .next()
+            .unwrap();
+        let value = group.get("media-col-database").unwrap().value();
+        assert!(matches!(value, IppValue::Array(values) if values.len() == 50));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_parser_limits() {
+        let result = AsyncIppParser::new(AsyncIppReader::new(futures_util::io::Cursor::new(GROUPS_WITH_PAYLOAD)))
+            .limits(ParserLimits {
+                max_attributes: 1,
+                ..ParserLimits::default()
+            })
+            .parse()
+            .await;
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::TooManyAttributes(1))
+        ));
+    }
+
+    const MALFORMED: &[u8] = &[
+        1, 1, 0, 0, 0, 0, 0, 1, 0x21, 0, 1, b'x', 0, 4, 0, 0, 0, 1, 0x0b, 0x04, 0x21, 0, 1, b'a', 0, 2, 0, 1, 0x37, 0,
+        0, 0, 0, 0x44, 0, 1, b'b', 0, 2, b'o', b'k', 0x21, 0, 1, b'c', 0, 4, 0,
+    ];
+
+    #[test]
+    fn test_lenient_parse() {
+        let (res, warnings) = IppParser::new(IppReader::new(io::Cursor::new(MALFORMED)))
+            .mode(ParseMode::Lenient)
+            .parse_with_warnings()
+            .unwrap();
+
+        let group = res.attributes().first_of(DelimiterTag::PrinterAttributes).unwrap();
+        assert_eq!(group.attributes().len(), 2);
+        assert_eq!(
+            group.get("a").unwrap().value(),
+            &IppValue::Other {
+                tag: 0x21,
+                data: Bytes::from_static(&[0, 1])
+            }
+        );
+        assert_eq!(group.get("b").unwrap().value().to_string(), "ok");
+
+        let warnings = warnings
+            .into_iter()
+            .map(|w| (w.offset, w.attribute, w.problem))
+            .collect::<Vec<_>>();
+        assert_eq!(warnings[0], (8, "x".to_owned(), ParseProblem::AttributeOutsideGroup));
+        assert_eq!(warnings[1], (18, String::new(), ParseProblem::UnknownDelimiter(0x0b)));
+        assert!(matches!(warnings[2], (20, ref name, ParseProblem::InvalidValue(_)) if name == "a"));
+        assert_eq!(warnings[3], (28, "a".to_owned(), ParseProblem::UnmatchedEndCollection));
+        assert_eq!(warnings[4], (41, "b".to_owned(), ParseProblem::Truncated));
+        assert_eq!(warnings.len(), 5);
+    }
+
+    #[test]
+    fn test_strict_parse() {
+        let result = IppParser::new(IppReader::new(io::Cursor::new(MALFORMED))).parse();
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::Malformed(ParseProblem::AttributeOutsideGroup))
+        ));
+
+        let data = &[
+            1, 1, 0, 0, 0, 0, 0, 1, 0x04, 0x21, 0, 1, b'a', 0, 4, 0, 0, 0, 1, 0x37, 0, 0, 0, 0, 0x03,
+        ];
+        let result = IppParser::new(IppReader::new(io::Cursor::new(data))).parse();
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::InvalidCollection)
+        ));
+
+        let data = &[1, 1, 0, 0, 0, 0, 0, 1, 0x04, 0x21, 0, 0, 0, 4, 0, 0, 0, 1, 0x03];
+        let result = IppParser::new(IppReader::new(io::Cursor::new(data))).parse();
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::Malformed(ParseProblem::ValueWithoutName))
+        ));
+    }
+
+    #[test]
+    fn test_member_without_value() {
+        // media-col where media-type has no value, followed by media-color
+        let data = [
+            &[1, 1, 0, 0, 0, 0, 0, 0, 4, 0x34, 0, 9][..],
+            b"media-col",
+            &[0, 0, 0x4a, 0, 0, 0, 10],
+            b"media-type",
+            &[0x4a, 0, 0, 0, 11],
+            b"media-color",
+            &[0x44, 0, 0, 0, 4],
+            b"blue",
+            &[0x37, 0, 0, 0, 0, 3],
+        ]
+        .concat();
+
+        let result = IppParser::new(IppReader::new(io::Cursor::new(data.clone()))).parse();
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::Malformed(ParseProblem::MemberWithoutValue(name))) if name == "media-type"
+        ));
+
+        let (res, warnings) = IppParser::new(IppReader::new(io::Cursor::new(data.clone())))
+            .mode(ParseMode::Lenient)
+            .parse_with_warnings()
+            .unwrap();
+        let group = res.attributes().first_of(DelimiterTag::PrinterAttributes).unwrap();
+        assert_eq!(
+            group.get("media-col").unwrap().value(),
+            &IppValue::Collection(IppCollection::from([(
+                "media-color".try_into().unwrap(),
+                IppValue::new_keyword("blue").unwrap()
+            )]))
+        );
+        assert_eq!(
+            warnings,
+            vec![ParseWarning {
+                offset: 63,
+                attribute: "media-col".to_owned(),
+                problem: ParseProblem::MemberWithoutValue("media-type".to_owned()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_lenient_unterminated_collection() {
+        let data = &[
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x34, 0, 4, b'c', b'o', b'l', b'l', 0, 0, 0x4a, 0, 0, 0, 4, b'a', b'b', b'c',
+            b'd', 0x44, 0, 0, 0, 3, b'k', b'e', b'y', 3,
+        ];
+        let result = IppBytesParser::new(&data[..]).parse();
+        assert!(matches!(
+            result.as_ref().map_err(|e| e.inner()),
+            Err(IppParseError::InvalidCollection)
+        ));
+
+        let mut parser = IppEventParser::new(IppReader::new(io::Cursor::new(data))).mode(ParseMode::Lenient);
+        let groups = parser.groups().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            groups[0].get("coll").unwrap().value(),
+            &IppValue::Collection(IppCollection::from([(
+                "abcd".try_into().unwrap(),
+                IppValue::Keyword("key".try_into().unwrap())
+            )]))
+        );
+        assert_eq!(
+            parser.warnings(),
+            &[ParseWarning {
+                offset: 35,
+                attribute: "coll".to_owned(),
+                problem: ParseProblem::UnterminatedCollection,
+            }]
+        );
+        assert_eq!(
+            parser.warnings()[0].to_string(),
+            "offset 35: coll: collection is not terminated"
+        );
+    }
+
+    #[test]
+    fn test_error_position() {
+        let data = &[
+            1, 1, 0, 0, 0, 0, 0, 1, 0x04, 0x21, 0, 1, b'a', 0, 4, 0, 0, 0, 1, 0x21, 0, 1, b'b', 0, 2, 0, 1, 0x03,
+        ];
+        let error = IppParser::new(IppReader::new(io::Cursor::new(data)))
+            .parse_parts()
+            .err()
+            .unwrap();
+        assert!(matches!(error.inner(), IppParseError::InvalidValueLength(_)));
+        assert_eq!(
+            error.position(),
+            Some(&ParsePosition {
+                offset: 19,
+                group: Some(DelimiterTag::PrinterAttributes),
+                attribute: "b".to_owned(),
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            "Invalid value length at offset 19 in PrinterAttributes group, attribute: b"
+        );
+
+        let data = &[
+            1, 1, 0, 0, 0, 0, 0, 1, 0x04, 0x21, 0, 1, b'a', 0, 4, 0, 0, 0, 1, 0x0b, 0x03,
+        ];
+        let mut parser = IppEventParser::new(IppReader::new(io::Cursor::new(data)));
+        let error = parser.by_ref().find_map(Result::err).unwrap();
+        assert!(matches!(error.inner(), IppParseError::InvalidTag(0x0b)));
+        assert_eq!(error.position().unwrap().offset, 19);
+        assert_eq!(error.position().unwrap().attribute, "a");
+
+        let error = IppBytesParser::new(&data[..5]).parse_parts().unwrap_err();
+        assert_eq!(error.to_string(), "Invalid value length at offset 0");
+    }
+
+    fn push_events(parser: &mut IppPushParser, data: &[u8]) -> Vec<IppPushEvent> {
+        let mut events = Vec::new();
+        for byte in data {
+            parser.feed(&[*byte]);
+            loop {
+                match parser.next_event().unwrap() {
+                    IppPushEvent::NeedMoreData => break,
+                    event => events.push(event),
+                }
+            }
+        }
+        parser.finish();
+        loop {
+            match parser.next_event().unwrap() {
+                IppPushEvent::Finished => break,
+                event => events.push(event),
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn test_push_parser() {
+        let expected = IppParser::new(IppReader::new(io::Cursor::new(GROUPS_WITH_PAYLOAD)))
+            .parse()
+            .unwrap();
+
+        let mut parser = IppPushParser::new();
+        assert_eq!(parser.next_event().unwrap(), IppPushEvent::NeedMoreData);
+        let events = push_events(&mut parser, GROUPS_WITH_PAYLOAD);
+        assert_eq!(events[0], IppPushEvent::Header(*expected.header()));
+
+        let mut attributes = Vec::new();
+        let mut payload = Vec::new();
+        for event in events {
+            match event {
+                IppPushEvent::Event(IppParseEvent::Attribute(attr)) => attributes.push(attr),
+                IppPushEvent::Payload(data) => payload.extend_from_slice(&data),
+                _ => {}
+            }
+        }
+        let expected_attributes = expected
+            .attributes()
+            .groups()
+            .iter()
+            .flat_map(|group| group.attributes().iter().cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(attributes, expected_attributes);
+        assert_eq!(payload, b"foo");
+        assert_eq!(parser.payload_offset(), Some(GROUPS_WITH_PAYLOAD.len() - 3));
+        assert_eq!(parser.offset(), GROUPS_WITH_PAYLOAD.len());
+        assert_eq!(parser.next_event().unwrap(), IppPushEvent::Finished);
+    }
+
+    #[test]
+    fn test_push_parser_truncated() {
+        let data = &[
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x04, 0x12,
+        ];
+
+        let mut parser = IppPushParser::new();
+        parser.feed(data);
+        parser.finish();
+        assert!(matches!(parser.next_event(), Ok(IppPushEvent::Header(_))));
+        assert!(matches!(
+            parser.next_event(),
+            Ok(IppPushEvent::Event(IppParseEvent::GroupStart(_)))
+        ));
+        let error = parser.next_event().unwrap_err();
+        assert!(matches!(error.inner(), IppParseError::IoError(_)));
+        assert_eq!(error.position().unwrap().offset, 9);
+        assert_eq!(parser.next_event().unwrap(), IppPushEvent::Finished);
+
+        let mut parser = IppPushParser::new().mode(ParseMode::Lenient);
+        let events = push_events(&mut parser, data);
+        assert_eq!(
+            events.last(),
+            Some(&IppPushEvent::Event(IppParseEvent::EndOfAttributes))
+        );
+        assert_eq!(parser.warnings()[0].problem, ParseProblem::Truncated);
+        assert_eq!(parser.warnings()[0].offset, 9);
+        assert_eq!(parser.payload_offset(), Some(data.len()));
+
+        let mut parser = IppPushParser::new().mode(ParseMode::Lenient);
+        parser.feed(&data[..4]);
+        parser.finish();
+        assert!(parser.next_event().is_err());
     }
 }
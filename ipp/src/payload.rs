@@ -43,6 +43,11 @@ impl IppPayload {
         }
     }
 
+    /// Return true if the payload has no data source
+    pub fn is_empty(&self) -> bool {
+        matches!(self.inner, PayloadKind::Empty)
+    }
+
     /// Create a sync payload from the Read object
     pub fn new<R>(r: R) -> Self
     where
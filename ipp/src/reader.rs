@@ -13,6 +13,7 @@ use crate::{IppHeader, model::IppVersion, parser::IppParseError, payload::IppPay
 /// Asynchronous IPP reader containing a set of methods to read from an IPP data stream
 pub struct AsyncIppReader<R> {
     inner: R,
+    offset: usize,
 }
 
 #[cfg(feature = "async")]
@@ -22,12 +23,23 @@ where
 {
     /// Create an IppReader from an AsyncRead instance
     pub fn new(inner: R) -> Self {
-        AsyncIppReader { inner }
+        AsyncIppReader { inner, offset: 0 }
+    }
+
+    /// Number of bytes read from the stream so far
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    async fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(buf).await?;
+        self.offset += buf.len();
+        Ok(())
     }
 
     async fn read_bytes(&mut self, len: usize) -> io::Result<Bytes> {
         let mut buf = vec![0; len];
-        self.inner.read_exact(&mut buf).await?;
+        self.read_exact(&mut buf).await?;
         Ok(buf.into())
     }
 
@@ -39,25 +51,25 @@ where
 
     async fn read_u16(&mut self) -> io::Result<u16> {
         let mut buf = [0u8; 2];
-        self.inner.read_exact(&mut buf).await?;
+        self.read_exact(&mut buf).await?;
         Ok(u16::from_be_bytes(buf))
     }
 
     async fn read_i16(&mut self) -> io::Result<i16> {
         let mut buf = [0u8; 2];
-        self.inner.read_exact(&mut buf).await?;
+        self.read_exact(&mut buf).await?;
         Ok(i16::from_be_bytes(buf))
     }
 
     async fn read_u8(&mut self) -> io::Result<u8> {
         let mut buf = [0u8; 1];
-        self.inner.read_exact(&mut buf).await?;
+        self.read_exact(&mut buf).await?;
         Ok(buf[0])
     }
 
     async fn read_i32(&mut self) -> io::Result<i32> {
         let mut buf = [0u8; 4];
-        self.inner.read_exact(&mut buf).await?;
+        self.read_exact(&mut buf).await?;
         Ok(i32::from_be_bytes(buf))
     }
 
@@ -114,6 +126,7 @@ where
 /// Synchronous IPP reader containing a set of methods to read from an IPP data stream
 pub struct IppReader<R> {
     inner: R,
+    offset: usize,
 }
 
 impl<R> IppReader<R>
@@ -122,12 +135,23 @@ where
 {
     /// Create an IppReader from a Read instance
     pub fn new(inner: R) -> Self {
-        IppReader { inner }
+        IppReader { inner, offset: 0 }
+    }
+
+    /// Number of bytes read from the stream so far
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(buf)?;
+        self.offset += buf.len();
+        Ok(())
     }
 
     fn read_bytes(&mut self, len: usize) -> io::Result<Bytes> {
         let mut buf = vec![0; len];
-        self.inner.read_exact(&mut buf)?;
+        self.read_exact(&mut buf)?;
         Ok(buf.into())
     }
 
@@ -137,25 +161,25 @@ where
 
     fn read_u16(&mut self) -> io::Result<u16> {
         let mut buf = [0u8; 2];
-        self.inner.read_exact(&mut buf)?;
+        self.read_exact(&mut buf)?;
         Ok(u16::from_be_bytes(buf))
     }
 
     fn read_i16(&mut self) -> io::Result<i16> {
         let mut buf = [0u8; 2];
-        self.inner.read_exact(&mut buf)?;
+        self.read_exact(&mut buf)?;
         Ok(i16::from_be_bytes(buf))
     }
 
     fn read_u8(&mut self) -> io::Result<u8> {
         let mut buf = [0u8; 1];
-        self.inner.read_exact(&mut buf)?;
+        self.read_exact(&mut buf)?;
         Ok(buf[0])
     }
 
     fn read_i32(&mut self) -> io::Result<i32> {
         let mut buf = [0u8; 4];
-        self.inner.read_exact(&mut buf)?;
+        self.read_exact(&mut buf)?;
         Ok(i32::from_be_bytes(buf))
     }
 
@@ -229,6 +253,19 @@ mod tests {
         assert_eq!(value.as_ref(), b"test");
     }
 
+    #[test]
+    fn test_read_offset() {
+        let data = io::Cursor::new(vec![0x00, 0x04, b't', b'e', b's', b't', 0x44]);
+        let mut reader = IppReader::new(data);
+        assert_eq!(reader.offset(), 0);
+        reader.read_value().unwrap();
+        assert_eq!(reader.offset(), 6);
+        reader.read_tag().unwrap();
+        assert_eq!(reader.offset(), 7);
+        assert!(reader.read_tag().is_err());
+        assert_eq!(reader.offset(), 7);
+    }
+
     #[test]
     fn test_read_borrowed_value() {
         let data = vec![0x00, 0x04, b't', b'e', b's', b't'];
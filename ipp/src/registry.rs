@@ -0,0 +1,1315 @@
+//!
+//! Attribute registry with value syntax metadata
+//!
+//! The registry maps each attribute to the groups it may appear in, its value syntax,
+//! whether it is a 1setOf attribute and the enum or keyword values it allows.
+//!
+//! The table follows the IANA IPP registry: it covers the operation, job template, job description and
+//! printer description attributes defined in [RFC 8011](https://tools.ietf.org/html/rfc8011) and the job and
+//! printer attributes of the PWG 5100.x specifications, e.g. `media-col`, `print-color-mode`, `finishings-col`
+//! or `printer-supply`, including the member attributes of the registered collections.
+//! Attributes of other specifications such as event notifications (RFC 3995) or the System Service are not listed.
+//! Attributes which are not listed here are unknown to the registry:
+//! [`lookup`] returns `None` for them, the parser keeps a single value of such an attribute as a scalar
+//! rather than a one-element array, the validator applies only the generic rules to them and the JSON decoder
+//! infers their value syntax from the JSON types.
+//!
+use crate::{
+    model::{DelimiterTag, ValueTag},
+    notation,
+    parser::IppParseError,
+    value::IppValue,
+};
+
+const MAX_TEXT: u16 = 1023;
+const MAX_NAME: u16 = 255;
+const MAX_OCTETS: u16 = 1023;
+
+/// Value syntax of an attribute as defined in [RFC 8011](https://tools.ietf.org/html/rfc8011#section-5.1)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Syntax {
+    /// integer(min:max)
+    Integer { min: i32, max: i32 },
+    /// boolean
+    Boolean,
+    /// enum
+    Enum,
+    /// octetString(max)
+    OctetString(u16),
+    /// dateTime
+    DateTime,
+    /// resolution
+    Resolution,
+    /// rangeOfInteger
+    RangeOfInteger,
+    /// collection
+    Collection,
+    /// text(max)
+    Text(u16),
+    /// name(max)
+    Name(u16),
+    /// keyword
+    Keyword,
+    /// uri
+    Uri,
+    /// uriScheme
+    UriScheme,
+    /// charset
+    Charset,
+    /// naturalLanguage
+    NaturalLanguage,
+    /// mimeMediaType
+    MimeMediaType,
+    /// no-value
+    NoValue,
+}
+
+impl Syntax {
+    /// Return the value tag used to encode this syntax
+    pub fn value_tag(&self) -> ValueTag {
+        match self {
+            Syntax::Integer { .. } => ValueTag::Integer,
+            Syntax::Boolean => ValueTag::Boolean,
+            Syntax::Enum => ValueTag::Enum,
+            Syntax::OctetString(_) => ValueTag::OctetStringUnspecified,
+            Syntax::DateTime => ValueTag::DateTime,
+            Syntax::Resolution => ValueTag::Resolution,
+            Syntax::RangeOfInteger => ValueTag::RangeOfInteger,
+            Syntax::Collection => ValueTag::BegCollection,
+            Syntax::Text(_) => ValueTag::TextWithoutLanguage,
+            Syntax::Name(_) => ValueTag::NameWithoutLanguage,
+            Syntax::Keyword => ValueTag::Keyword,
+            Syntax::Uri => ValueTag::Uri,
+            Syntax::UriScheme => ValueTag::UriScheme,
+            Syntax::Charset => ValueTag::Charset,
+            Syntax::NaturalLanguage => ValueTag::NaturalLanguage,
+            Syntax::MimeMediaType => ValueTag::MimeMediaType,
+            Syntax::NoValue => ValueTag::NoValue,
+        }
+    }
+
+    /// Check if a single (non-array) value conforms to this syntax
+    pub fn matches(&self, value: &IppValue) -> bool {
+        match (self, value) {
+            (Syntax::Integer { min, max }, IppValue::Integer(v)) => (*min..=*max).contains(v),
+            (Syntax::Boolean, IppValue::Boolean(_)) => true,
+            (Syntax::Enum, IppValue::Enum(_)) => true,
+            (Syntax::OctetString(max), IppValue::OctetString(data)) => data.len() <= *max as usize,
+            (Syntax::DateTime, IppValue::DateTime(_)) => true,
+            (Syntax::Resolution, IppValue::Resolution { .. }) => true,
+            (Syntax::RangeOfInteger, IppValue::RangeOfInteger { min, max }) => min <= max,
+            (Syntax::Collection, IppValue::Collection(_)) => true,
+            (Syntax::Text(max), IppValue::TextWithoutLanguage(text))
+            | (Syntax::Text(max), IppValue::TextWithLanguage { text, .. }) => text.len() <= *max as usize,
+            (Syntax::Name(max), IppValue::NameWithoutLanguage(name))
+            | (Syntax::Name(max), IppValue::NameWithLanguage { name, .. }) => name.len() <= *max as usize,
+            // the limits apply to the encoded octets
+            (
+                Syntax::Text(max),
+                IppValue::Encoded {
+                    tag: ValueTag::TextWithoutLanguage,
+                    data,
+                    ..
+                },
+            )
+            | (
+                Syntax::Name(max),
+                IppValue::Encoded {
+                    tag: ValueTag::NameWithoutLanguage,
+                    data,
+                    ..
+                },
+            ) => data.len() <= *max as usize,
+            (Syntax::Text(_), IppValue::Encoded { tag, .. }) => *tag == ValueTag::TextWithLanguage,
+            (Syntax::Name(_), IppValue::Encoded { tag, .. }) => *tag == ValueTag::NameWithLanguage,
+            (Syntax::Keyword, IppValue::Keyword(_)) => true,
+            (Syntax::Uri, IppValue::Uri(_)) => true,
+            (Syntax::UriScheme, IppValue::UriScheme(_)) => true,
+            (Syntax::Charset, IppValue::Charset(_)) => true,
+            (Syntax::NaturalLanguage, IppValue::NaturalLanguage(_)) => true,
+            (Syntax::MimeMediaType, IppValue::MimeMediaType(_)) => true,
+            (Syntax::NoValue, IppValue::NoValue) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Values registered for an attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AllowedValues {
+    /// No value list is registered
+    Any,
+    /// Registered keywords
+    Keywords(&'static [&'static str]),
+    /// Registered enum values with their keyword names
+    Enums(&'static [(i32, &'static str)]),
+}
+
+/// Registry entry for a single attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AttributeInfo {
+    /// Attribute name
+    pub name: &'static str,
+    /// Groups the attribute may appear in
+    pub groups: &'static [DelimiterTag],
+    /// Value syntax alternatives, e.g. `keyword | name(MAX)`
+    pub syntax: &'static [Syntax],
+    /// Whether the attribute is a 1setOf attribute
+    pub set_of: bool,
+    /// Registered values
+    pub values: AllowedValues,
+    /// Registered member attributes of a collection attribute, sorted by name
+    pub members: &'static [AttributeInfo],
+}
+
+impl AttributeInfo {
+    const fn new(name: &'static str, groups: &'static [DelimiterTag], syntax: &'static [Syntax]) -> Self {
+        AttributeInfo {
+            name,
+            groups,
+            syntax,
+            set_of: false,
+            values: AllowedValues::Any,
+            members: &[],
+        }
+    }
+
+    const fn set_of(mut self) -> Self {
+        self.set_of = true;
+        self
+    }
+
+    const fn keywords(mut self, keywords: &'static [&'static str]) -> Self {
+        self.values = AllowedValues::Keywords(keywords);
+        self
+    }
+
+    const fn enums(mut self, enums: &'static [(i32, &'static str)]) -> Self {
+        self.values = AllowedValues::Enums(enums);
+        self
+    }
+
+    const fn members(mut self, members: &'static [AttributeInfo]) -> Self {
+        self.members = members;
+        self
+    }
+
+    /// Look up a registered member attribute of a collection attribute
+    pub fn member(&self, name: &str) -> Option<&'static AttributeInfo> {
+        self.members
+            .binary_search_by(|info| info.name.cmp(name))
+            .ok()
+            .map(|index| &self.members[index])
+    }
+
+    /// Check if the keyword is one of the registered values
+    pub fn is_registered_keyword(&self, keyword: &str) -> bool {
+        matches!(self.values, AllowedValues::Keywords(keywords) if keywords.contains(&keyword))
+    }
+
+    /// Return the keyword name of a registered enum value
+    pub fn enum_name(&self, value: i32) -> Option<&'static str> {
+        match self.values {
+            AllowedValues::Enums(enums) => enums.iter().find(|(v, _)| *v == value).map(|(_, name)| *name),
+            _ => None,
+        }
+    }
+
+    /// Return the enum value for a registered keyword name
+    pub fn enum_value(&self, name: &str) -> Option<i32> {
+        match self.values {
+            AllowedValues::Enums(enums) => enums.iter().find(|(_, n)| *n == name).map(|(v, _)| *v),
+            _ => None,
+        }
+    }
+
+    /// Check if a value conforms to the attribute syntax, including the set-of flag
+    pub fn matches(&self, value: &IppValue) -> bool {
+        match value {
+            IppValue::Array(values) => self.set_of && values.iter().all(|v| self.syntax.iter().any(|s| s.matches(v))),
+            value => self.syntax.iter().any(|s| s.matches(value)),
+        }
+    }
+
+    /// Parse a value in the [`notation`](crate::notation) syntax.
+    /// Tagged values such as `name:foo` keep their explicit syntax, untagged values are parsed
+    /// according to the attribute syntax instead of being inferred, e.g. `"My Paper"` is a name for `media`.
+    pub fn parse_value(&self, value: &str) -> Result<IppValue, IppParseError> {
+        notation::parse_attribute_value(value, Some(self))
+    }
+
+    pub(crate) fn parse_single(&self, value: &str) -> Result<IppValue, IppParseError> {
+        let has_name = self.syntax.iter().any(|s| matches!(s, Syntax::Name(_)));
+        for syntax in self.syntax {
+            let parsed = match syntax {
+                Syntax::Integer { min, max } => value
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|v| (*min..=*max).contains(v))
+                    .map(IppValue::Integer),
+                Syntax::Boolean => value.parse::<bool>().ok().map(IppValue::Boolean),
+                Syntax::Enum => value
+                    .parse::<i32>()
+                    .ok()
+                    .or_else(|| self.enum_value(value))
+                    .map(IppValue::Enum),
+                Syntax::Resolution => notation::parse_resolution(value),
+                Syntax::RangeOfInteger => notation::parse_range(value),
+                Syntax::Keyword => {
+                    let allowed = match self.values {
+                        AllowedValues::Keywords(keywords) => keywords.contains(&value) || !has_name,
+                        _ => !has_name || is_keyword(value),
+                    };
+                    if allowed {
+                        Some(IppValue::new_keyword(value)?)
+                    } else {
+                        None
+                    }
+                }
+                Syntax::Text(_) => Some(IppValue::new_text_without_language(value)?),
+                Syntax::Name(_) => Some(IppValue::new_name_without_language(value)?),
+                Syntax::Uri => Some(IppValue::new_uri(value)?),
+                Syntax::UriScheme => Some(IppValue::new_uri_scheme(value)?),
+                Syntax::Charset => Some(IppValue::new_charset(value)?),
+                Syntax::NaturalLanguage => Some(IppValue::new_natural_language(value)?),
+                Syntax::MimeMediaType => Some(IppValue::new_mime_media_type(value)?),
+                Syntax::NoValue if value.is_empty() => Some(IppValue::NoValue),
+                Syntax::OctetString(_) | Syntax::DateTime | Syntax::Collection | Syntax::NoValue => None,
+            };
+            if let Some(parsed) = parsed {
+                return Ok(parsed);
+            }
+        }
+        Err(IppParseError::InvalidValue(self.name.to_owned(), value.to_owned()))
+    }
+}
+
+// keyword grammar from RFC 8011 section 5.1.4: lowercase letters, digits, '-', '_' or '.' starting with a letter.
+// Registered values such as "1.1" in ipp-versions-supported are checked with `AttributeInfo::is_registered_keyword`
+pub(crate) fn is_keyword(value: &str) -> bool {
+    value.bytes().next().is_some_and(|b| b.is_ascii_lowercase())
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'-' | b'_' | b'.'))
+}
+
+/// Look up an attribute in the registry, returning `None` for unregistered attributes
+pub fn lookup(name: &str) -> Option<&'static AttributeInfo> {
+    REGISTRY
+        .binary_search_by(|info| info.name.cmp(name))
+        .ok()
+        .map(|index| &REGISTRY[index])
+}
+
+/// Return all registered attributes, sorted by name
+pub fn attributes() -> &'static [AttributeInfo] {
+    REGISTRY
+}
+
+const OPERATION: &[DelimiterTag] = &[DelimiterTag::OperationAttributes];
+const JOB: &[DelimiterTag] = &[DelimiterTag::JobAttributes];
+const PRINTER: &[DelimiterTag] = &[DelimiterTag::PrinterAttributes];
+const OPERATION_JOB: &[DelimiterTag] = &[DelimiterTag::OperationAttributes, DelimiterTag::JobAttributes];
+// collection members do not appear in a group
+const MEMBER: &[DelimiterTag] = &[];
+
+const INTEGER_1_MAX: Syntax = Syntax::Integer { min: 1, max: i32::MAX };
+const INTEGER_0_MAX: Syntax = Syntax::Integer { min: 0, max: i32::MAX };
+const INTEGER_MIN_MAX: Syntax = Syntax::Integer {
+    min: i32::MIN,
+    max: i32::MAX,
+};
+const INTEGER_1_100: Syntax = Syntax::Integer { min: 1, max: 100 };
+const TEXT: Syntax = Syntax::Text(MAX_TEXT);
+const TEXT_127: Syntax = Syntax::Text(127);
+const TEXT_255: Syntax = Syntax::Text(255);
+const NAME: Syntax = Syntax::Name(MAX_NAME);
+const NAME_127: Syntax = Syntax::Name(127);
+
+const COMPRESSION: &[&str] = &["compress", "deflate", "gzip", "none"];
+
+const FINISHINGS: &[(i32, &str)] = &[
+    (3, "none"),
+    (4, "staple"),
+    (5, "punch"),
+    (6, "cover"),
+    (7, "bind"),
+    (8, "saddle-stitch"),
+    (9, "edge-stitch"),
+    (10, "fold"),
+    (11, "trim"),
+    (12, "bale"),
+    (13, "booklet-maker"),
+    (14, "jog-offset"),
+    (15, "coat"),
+    (16, "laminate"),
+    (20, "staple-top-left"),
+    (21, "staple-bottom-left"),
+    (22, "staple-top-right"),
+    (23, "staple-bottom-right"),
+    (24, "edge-stitch-left"),
+    (25, "edge-stitch-top"),
+    (26, "edge-stitch-right"),
+    (27, "edge-stitch-bottom"),
+    (28, "staple-dual-left"),
+    (29, "staple-dual-top"),
+    (30, "staple-dual-right"),
+    (31, "staple-dual-bottom"),
+    (32, "staple-triple-left"),
+    (33, "staple-triple-top"),
+    (34, "staple-triple-right"),
+    (35, "staple-triple-bottom"),
+    (70, "punch-top-left"),
+    (71, "punch-bottom-left"),
+    (72, "punch-top-right"),
+    (73, "punch-bottom-right"),
+    (74, "punch-dual-left"),
+    (75, "punch-dual-top"),
+    (76, "punch-dual-right"),
+    (77, "punch-dual-bottom"),
+    (78, "punch-triple-left"),
+    (79, "punch-triple-top"),
+    (80, "punch-triple-right"),
+    (81, "punch-triple-bottom"),
+    (82, "punch-quad-left"),
+    (83, "punch-quad-top"),
+    (84, "punch-quad-right"),
+    (85, "punch-quad-bottom"),
+];
+
+const IPP_VERSIONS: &[&str] = &["1.0", "1.1", "2.0", "2.1", "2.2"];
+
+const JOB_HOLD_UNTIL: &[&str] = &[
+    "day-time",
+    "evening",
+    "indefinite",
+    "night",
+    "no-hold",
+    "second-shift",
+    "third-shift",
+    "weekend",
+];
+
+const JOB_SHEETS: &[&str] = &["none", "standard"];
+
+const JOB_STATE: &[(i32, &str)] = &[
+    (3, "pending"),
+    (4, "pending-held"),
+    (5, "processing"),
+    (6, "processing-stopped"),
+    (7, "canceled"),
+    (8, "aborted"),
+    (9, "completed"),
+];
+
+const MULTIPLE_DOCUMENT_HANDLING: &[&str] = &[
+    "separate-documents-collated-copies",
+    "separate-documents-uncollated-copies",
+    "single-document",
+    "single-document-new-sheet",
+];
+
+const OPERATIONS: &[(i32, &str)] = &[
+    (0x0002, "Print-Job"),
+    (0x0003, "Print-URI"),
+    (0x0004, "Validate-Job"),
+    (0x0005, "Create-Job"),
+    (0x0006, "Send-Document"),
+    (0x0007, "Send-URI"),
+    (0x0008, "Cancel-Job"),
+    (0x0009, "Get-Job-Attributes"),
+    (0x000A, "Get-Jobs"),
+    (0x000B, "Get-Printer-Attributes"),
+    (0x000C, "Hold-Job"),
+    (0x000D, "Release-Job"),
+    (0x000E, "Restart-Job"),
+    (0x0010, "Pause-Printer"),
+    (0x0011, "Resume-Printer"),
+    (0x0012, "Purge-Jobs"),
+    (0x4001, "CUPS-Get-Default"),
+    (0x4002, "CUPS-Get-Printers"),
+    (0x4003, "CUPS-Add-Modify-Printer"),
+    (0x4004, "CUPS-Delete-Printer"),
+    (0x4005, "CUPS-Get-Classes"),
+    (0x4006, "CUPS-Add-Modify-Class"),
+    (0x4007, "CUPS-Delete-Class"),
+    (0x4008, "CUPS-Accept-Jobs"),
+    (0x4009, "CUPS-Reject-Jobs"),
+    (0x400A, "CUPS-Set-Default"),
+    (0x400B, "CUPS-Get-Devices"),
+    (0x400C, "CUPS-Get-PPDs"),
+    (0x400D, "CUPS-Move-Job"),
+    (0x400E, "CUPS-Authenticate-Job"),
+    (0x400F, "CUPS-Get-PPD"),
+    (0x4027, "CUPS-Get-Document"),
+    (0x4028, "CUPS-Create-Local-Printer"),
+];
+
+const ORIENTATION_REQUESTED: &[(i32, &str)] = &[
+    (3, "portrait"),
+    (4, "landscape"),
+    (5, "reverse-landscape"),
+    (6, "reverse-portrait"),
+    (7, "none"),
+];
+
+const PDL_OVERRIDE: &[&str] = &["attempted", "not-attempted"];
+
+const PRINT_QUALITY: &[(i32, &str)] = &[(3, "draft"), (4, "normal"), (5, "high")];
+
+const PRINTER_STATE: &[(i32, &str)] = &[(3, "idle"), (4, "processing"), (5, "stopped")];
+
+const SIDES: &[&str] = &["one-sided", "two-sided-long-edge", "two-sided-short-edge"];
+
+const URI_AUTHENTICATION: &[&str] = &["basic", "certificate", "digest", "none", "requesting-user-name"];
+
+const URI_SECURITY: &[&str] = &["none", "tls"];
+
+const WHICH_JOBS: &[&str] = &[
+    "aborted",
+    "all",
+    "canceled",
+    "completed",
+    "fetchable",
+    "not-completed",
+    "pending",
+    "pending-held",
+    "processing",
+    "processing-stopped",
+    "proof-print",
+    "saved",
+];
+
+const FEED_ORIENTATION: &[&str] = &["long-edge-first", "short-edge-first"];
+
+const IDENTIFY_ACTIONS: &[&str] = &["display", "flash", "sound", "speak"];
+
+const JOB_DELAY_OUTPUT_UNTIL: &[&str] = &[
+    "day-time",
+    "evening",
+    "indefinite",
+    "night",
+    "no-delay-output",
+    "second-shift",
+    "third-shift",
+    "weekend",
+];
+
+const JOB_ERROR_ACTION: &[&str] = &["abort-job", "cancel-job", "continue-job", "suspend-job"];
+
+const JOB_PASSWORD_ENCRYPTION: &[&str] = &[
+    "md2",
+    "md4",
+    "md5",
+    "none",
+    "sha",
+    "sha2-224",
+    "sha2-256",
+    "sha2-384",
+    "sha2-512",
+    "sha2-512_224",
+    "sha2-512_256",
+    "sha3-224",
+    "sha3-256",
+    "sha3-384",
+    "sha3-512",
+    "sha3-512_224",
+    "sha3-512_256",
+    "shake-128",
+    "shake-256",
+];
+
+const JOB_RETAIN_UNTIL: &[&str] = &[
+    "day-time",
+    "end-of-day",
+    "end-of-month",
+    "end-of-week",
+    "evening",
+    "indefinite",
+    "night",
+    "none",
+    "second-shift",
+    "third-shift",
+    "weekend",
+];
+
+const PAGE_DELIVERY: &[&str] = &[
+    "reverse-order-face-down",
+    "reverse-order-face-up",
+    "same-order-face-down",
+    "same-order-face-up",
+    "system-specified",
+];
+
+const PRESENTATION_DIRECTION_NUMBER_UP: &[&str] = &[
+    "tobottom-toleft",
+    "tobottom-toright",
+    "toleft-tobottom",
+    "toleft-totop",
+    "toright-tobottom",
+    "toright-totop",
+    "totop-toleft",
+    "totop-toright",
+];
+
+const PRINT_COLOR_MODE: &[&str] = &[
+    "auto",
+    "auto-monochrome",
+    "bi-level",
+    "color",
+    "highlight",
+    "monochrome",
+    "process-bi-level",
+    "process-monochrome",
+];
+
+const PRINT_CONTENT_OPTIMIZE: &[&str] = &["auto", "graphic", "photo", "text", "text-and-graphic"];
+
+const PRINT_RENDERING_INTENT: &[&str] = &[
+    "absolute",
+    "auto",
+    "perceptual",
+    "relative",
+    "relative-bpc",
+    "saturation",
+];
+
+const PRINT_SCALING: &[&str] = &["auto", "auto-fit", "fill", "fit", "none"];
+
+const PWG_RASTER_DOCUMENT_SHEET_BACK: &[&str] = &["flipped", "manual-tumble", "normal", "rotated"];
+
+const SHEET_COLLATE: &[&str] = &["collated", "uncollated"];
+
+const X_IMAGE_POSITION: &[&str] = &["center", "left", "none", "right"];
+
+const Y_IMAGE_POSITION: &[&str] = &["bottom", "center", "none", "top"];
+
+const COVER_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("cover-type", MEMBER, &[Syntax::Keyword]),
+    AttributeInfo::new("media", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-col", MEMBER, &[Syntax::Collection]).members(MEDIA_COL_MEMBERS),
+];
+
+const FINISHINGS_COL_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("baling", MEMBER, &[Syntax::Collection]),
+    AttributeInfo::new("binding", MEMBER, &[Syntax::Collection]),
+    AttributeInfo::new("coating", MEMBER, &[Syntax::Collection]),
+    AttributeInfo::new("finishing-template", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("folding", MEMBER, &[Syntax::Collection]).set_of(),
+    AttributeInfo::new("imposition-template", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("laminating", MEMBER, &[Syntax::Collection]),
+    AttributeInfo::new("media-sheets-supported", MEMBER, &[Syntax::RangeOfInteger]),
+    AttributeInfo::new("media-size", MEMBER, &[Syntax::Collection]).members(MEDIA_SIZE_MEMBERS),
+    AttributeInfo::new("media-size-name", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("punching", MEMBER, &[Syntax::Collection]).members(PUNCHING_MEMBERS),
+    AttributeInfo::new("stitching", MEMBER, &[Syntax::Collection]).members(STITCHING_MEMBERS),
+    AttributeInfo::new("trimming", MEMBER, &[Syntax::Collection]).set_of(),
+];
+
+const ICC_PROFILE_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("profile-name", MEMBER, &[NAME]),
+    AttributeInfo::new("profile-url", MEMBER, &[Syntax::Uri]),
+];
+
+const INSERT_SHEET_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("insert-after-page-number", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("insert-count", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("media", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-col", MEMBER, &[Syntax::Collection]).members(MEDIA_COL_MEMBERS),
+];
+
+const JOB_COUNTER_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("blank", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("blank-two-sided", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("full-color", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("full-color-two-sided", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("highlight-color", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("highlight-color-two-sided", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("monochrome", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("monochrome-two-sided", MEMBER, &[INTEGER_0_MAX]),
+];
+
+const JOB_PRESET_MEMBERS: &[AttributeInfo] = &[AttributeInfo::new("preset-name", MEMBER, &[Syntax::Keyword, NAME])];
+
+const JOB_RESOLVER_MEMBERS: &[AttributeInfo] = &[AttributeInfo::new("resolver-name", MEMBER, &[NAME])];
+
+const JOB_SHEETS_COL_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("job-sheets", MEMBER, &[Syntax::Keyword, NAME]).keywords(JOB_SHEETS),
+    AttributeInfo::new("media", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-col", MEMBER, &[Syntax::Collection]).members(MEDIA_COL_MEMBERS),
+];
+
+const MEDIA_COL_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("media-back-coating", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-bottom-margin", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("media-color", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-front-coating", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-grain", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-hole-count", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("media-info", MEMBER, &[TEXT_255]),
+    AttributeInfo::new("media-key", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-left-margin", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("media-order-count", MEMBER, &[INTEGER_1_MAX]),
+    AttributeInfo::new("media-pre-printed", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-recycled", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-right-margin", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("media-size", MEMBER, &[Syntax::Collection]).members(MEDIA_SIZE_MEMBERS),
+    AttributeInfo::new("media-size-name", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-source", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-source-properties", MEMBER, &[Syntax::Collection])
+        .members(MEDIA_SOURCE_PROPERTIES_MEMBERS),
+    AttributeInfo::new("media-thickness", MEMBER, &[INTEGER_1_MAX]),
+    AttributeInfo::new("media-tooth", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-top-margin", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("media-type", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-weight-metric", MEMBER, &[INTEGER_0_MAX]),
+];
+
+const MEDIA_SIZE_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("x-dimension", MEMBER, &[INTEGER_1_MAX, Syntax::RangeOfInteger]),
+    AttributeInfo::new("y-dimension", MEMBER, &[INTEGER_1_MAX, Syntax::RangeOfInteger]),
+];
+
+const MEDIA_SOURCE_PROPERTIES_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("media-source-feed-direction", MEMBER, &[Syntax::Keyword]),
+    AttributeInfo::new("media-source-feed-orientation", MEMBER, &[Syntax::Enum]).enums(ORIENTATION_REQUESTED),
+];
+
+const OVERRIDES_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("document-copies", MEMBER, &[Syntax::RangeOfInteger]).set_of(),
+    AttributeInfo::new("document-numbers", MEMBER, &[Syntax::RangeOfInteger]).set_of(),
+    AttributeInfo::new("pages", MEMBER, &[Syntax::RangeOfInteger]).set_of(),
+];
+
+const PDL_INIT_FILE_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("pdl-init-file-entry", MEMBER, &[NAME]),
+    AttributeInfo::new("pdl-init-file-location", MEMBER, &[Syntax::Uri]),
+    AttributeInfo::new("pdl-init-file-name", MEMBER, &[NAME]),
+];
+
+const PRINTER_XRI_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("xri-authentication", MEMBER, &[Syntax::Keyword]).keywords(URI_AUTHENTICATION),
+    AttributeInfo::new("xri-security", MEMBER, &[Syntax::Keyword]).keywords(URI_SECURITY),
+    AttributeInfo::new("xri-uri", MEMBER, &[Syntax::Uri]),
+];
+
+const PROOF_PRINT_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("media", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-col", MEMBER, &[Syntax::Collection]).members(MEDIA_COL_MEMBERS),
+    AttributeInfo::new("proof-print-copies", MEMBER, &[INTEGER_0_MAX]),
+];
+
+const PUNCHING_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("punching-locations", MEMBER, &[INTEGER_0_MAX]).set_of(),
+    AttributeInfo::new("punching-offset", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("punching-reference-edge", MEMBER, &[Syntax::Keyword]),
+];
+
+const SEPARATOR_SHEETS_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("media", MEMBER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-col", MEMBER, &[Syntax::Collection]).members(MEDIA_COL_MEMBERS),
+    AttributeInfo::new("separator-sheets-type", MEMBER, &[Syntax::Keyword]).set_of(),
+];
+
+const STITCHING_MEMBERS: &[AttributeInfo] = &[
+    AttributeInfo::new("stitching-angle", MEMBER, &[Syntax::Integer { min: 0, max: 359 }]),
+    AttributeInfo::new("stitching-locations", MEMBER, &[INTEGER_0_MAX]).set_of(),
+    AttributeInfo::new("stitching-method", MEMBER, &[Syntax::Keyword]),
+    AttributeInfo::new("stitching-offset", MEMBER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("stitching-reference-edge", MEMBER, &[Syntax::Keyword]),
+];
+
+// Sorted by name, lookups use binary search
+static REGISTRY: &[AttributeInfo] = &[
+    AttributeInfo::new("attributes-charset", OPERATION, &[Syntax::Charset]),
+    AttributeInfo::new("attributes-natural-language", OPERATION, &[Syntax::NaturalLanguage]),
+    AttributeInfo::new("charset-configured", PRINTER, &[Syntax::Charset]),
+    AttributeInfo::new("charset-supported", PRINTER, &[Syntax::Charset]).set_of(),
+    AttributeInfo::new("color-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("compression", OPERATION, &[Syntax::Keyword]).keywords(COMPRESSION),
+    AttributeInfo::new("compression-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(COMPRESSION),
+    AttributeInfo::new("copies", JOB, &[INTEGER_1_MAX]),
+    AttributeInfo::new("copies-default", PRINTER, &[INTEGER_1_MAX]),
+    AttributeInfo::new("copies-supported", PRINTER, &[Syntax::RangeOfInteger]),
+    AttributeInfo::new("cover-back", JOB, &[Syntax::Collection]).members(COVER_MEMBERS),
+    AttributeInfo::new("cover-back-default", PRINTER, &[Syntax::Collection]).members(COVER_MEMBERS),
+    AttributeInfo::new("cover-back-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("cover-front", JOB, &[Syntax::Collection]).members(COVER_MEMBERS),
+    AttributeInfo::new("cover-front-default", PRINTER, &[Syntax::Collection]).members(COVER_MEMBERS),
+    AttributeInfo::new("cover-front-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("date-time-at-completed", JOB, &[Syntax::DateTime, Syntax::NoValue]),
+    AttributeInfo::new("date-time-at-creation", JOB, &[Syntax::DateTime]),
+    AttributeInfo::new("date-time-at-processing", JOB, &[Syntax::DateTime, Syntax::NoValue]),
+    AttributeInfo::new("detailed-status-message", OPERATION, &[TEXT]),
+    AttributeInfo::new("document-access-error", OPERATION, &[TEXT]),
+    AttributeInfo::new("document-format", OPERATION, &[Syntax::MimeMediaType]),
+    AttributeInfo::new("document-format-default", PRINTER, &[Syntax::MimeMediaType]),
+    AttributeInfo::new("document-format-details-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("document-format-supported", PRINTER, &[Syntax::MimeMediaType]).set_of(),
+    AttributeInfo::new("document-format-varying-attributes", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("document-format-version", OPERATION, &[TEXT_127]),
+    AttributeInfo::new("document-format-version-supported", PRINTER, &[TEXT_127]).set_of(),
+    AttributeInfo::new("document-name", OPERATION, &[NAME]),
+    AttributeInfo::new("document-natural-language", OPERATION, &[Syntax::NaturalLanguage]),
+    AttributeInfo::new("document-password", OPERATION, &[Syntax::OctetString(1023)]),
+    AttributeInfo::new(
+        "document-password-supported",
+        PRINTER,
+        &[Syntax::Integer { min: 0, max: 1023 }],
+    ),
+    AttributeInfo::new("document-uri", OPERATION, &[Syntax::Uri]),
+    AttributeInfo::new("feed-orientation", JOB, &[Syntax::Keyword]).keywords(FEED_ORIENTATION),
+    AttributeInfo::new("feed-orientation-default", PRINTER, &[Syntax::Keyword]).keywords(FEED_ORIENTATION),
+    AttributeInfo::new("feed-orientation-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(FEED_ORIENTATION),
+    AttributeInfo::new("finishings", JOB, &[Syntax::Enum])
+        .set_of()
+        .enums(FINISHINGS),
+    AttributeInfo::new("finishings-col", JOB, &[Syntax::Collection])
+        .set_of()
+        .members(FINISHINGS_COL_MEMBERS),
+    AttributeInfo::new("finishings-col-database", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(FINISHINGS_COL_MEMBERS),
+    AttributeInfo::new("finishings-col-default", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(FINISHINGS_COL_MEMBERS),
+    AttributeInfo::new("finishings-col-ready", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(FINISHINGS_COL_MEMBERS),
+    AttributeInfo::new("finishings-col-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("finishings-default", PRINTER, &[Syntax::Enum])
+        .set_of()
+        .enums(FINISHINGS),
+    AttributeInfo::new("finishings-supported", PRINTER, &[Syntax::Enum])
+        .set_of()
+        .enums(FINISHINGS),
+    AttributeInfo::new("first-index", OPERATION, &[INTEGER_1_MAX]),
+    AttributeInfo::new("force-front-side", JOB, &[INTEGER_1_MAX]).set_of(),
+    AttributeInfo::new("force-front-side-supported", PRINTER, &[INTEGER_1_MAX]),
+    AttributeInfo::new(
+        "generated-natural-language-supported",
+        PRINTER,
+        &[Syntax::NaturalLanguage],
+    )
+    .set_of(),
+    AttributeInfo::new("identify-actions-default", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(IDENTIFY_ACTIONS),
+    AttributeInfo::new("identify-actions-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(IDENTIFY_ACTIONS),
+    AttributeInfo::new("imposition-template", JOB, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("imposition-template-default", PRINTER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("imposition-template-supported", PRINTER, &[Syntax::Keyword, NAME]).set_of(),
+    AttributeInfo::new("insert-sheet", JOB, &[Syntax::Collection])
+        .set_of()
+        .members(INSERT_SHEET_MEMBERS),
+    AttributeInfo::new("insert-sheet-default", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(INSERT_SHEET_MEMBERS),
+    AttributeInfo::new("insert-sheet-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("ipp-attribute-fidelity", OPERATION, &[Syntax::Boolean]),
+    AttributeInfo::new("ipp-features-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("ipp-versions-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(IPP_VERSIONS),
+    AttributeInfo::new("job-account-id", JOB, &[NAME]),
+    AttributeInfo::new("job-account-id-default", PRINTER, &[NAME, Syntax::NoValue]),
+    AttributeInfo::new("job-account-id-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("job-accounting-user-id", JOB, &[NAME]),
+    AttributeInfo::new("job-accounting-user-id-default", PRINTER, &[NAME, Syntax::NoValue]),
+    AttributeInfo::new("job-accounting-user-id-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("job-cancel-after", JOB, &[INTEGER_1_MAX]),
+    AttributeInfo::new("job-cancel-after-default", PRINTER, &[INTEGER_1_MAX]),
+    AttributeInfo::new("job-cancel-after-supported", PRINTER, &[Syntax::RangeOfInteger]),
+    AttributeInfo::new("job-constraints-supported", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(JOB_RESOLVER_MEMBERS),
+    AttributeInfo::new("job-creation-attributes-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("job-delay-output-until", JOB, &[Syntax::Keyword, NAME]).keywords(JOB_DELAY_OUTPUT_UNTIL),
+    AttributeInfo::new("job-delay-output-until-default", PRINTER, &[Syntax::Keyword, NAME])
+        .keywords(JOB_DELAY_OUTPUT_UNTIL),
+    AttributeInfo::new("job-delay-output-until-supported", PRINTER, &[Syntax::Keyword, NAME])
+        .set_of()
+        .keywords(JOB_DELAY_OUTPUT_UNTIL),
+    AttributeInfo::new("job-delay-output-until-time", JOB, &[Syntax::DateTime]),
+    AttributeInfo::new("job-detailed-status-messages", JOB, &[TEXT]).set_of(),
+    AttributeInfo::new("job-document-access-errors", JOB, &[TEXT]).set_of(),
+    AttributeInfo::new("job-error-action", JOB, &[Syntax::Keyword]).keywords(JOB_ERROR_ACTION),
+    AttributeInfo::new("job-error-action-default", PRINTER, &[Syntax::Keyword]).keywords(JOB_ERROR_ACTION),
+    AttributeInfo::new("job-error-action-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(JOB_ERROR_ACTION),
+    AttributeInfo::new("job-hold-until", JOB, &[Syntax::Keyword, NAME]).keywords(JOB_HOLD_UNTIL),
+    AttributeInfo::new("job-hold-until-default", PRINTER, &[Syntax::Keyword, NAME]).keywords(JOB_HOLD_UNTIL),
+    AttributeInfo::new("job-hold-until-supported", PRINTER, &[Syntax::Keyword, NAME])
+        .set_of()
+        .keywords(JOB_HOLD_UNTIL),
+    AttributeInfo::new("job-hold-until-time", JOB, &[Syntax::DateTime]),
+    AttributeInfo::new("job-id", OPERATION_JOB, &[INTEGER_1_MAX]),
+    AttributeInfo::new("job-ids", OPERATION, &[INTEGER_1_MAX]).set_of(),
+    AttributeInfo::new("job-ids-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("job-impressions", OPERATION_JOB, &[INTEGER_0_MAX]),
+    AttributeInfo::new("job-impressions-col", JOB, &[Syntax::Collection]).members(JOB_COUNTER_MEMBERS),
+    AttributeInfo::new("job-impressions-completed", JOB, &[INTEGER_0_MAX]),
+    AttributeInfo::new("job-impressions-completed-col", JOB, &[Syntax::Collection]).members(JOB_COUNTER_MEMBERS),
+    AttributeInfo::new("job-impressions-supported", PRINTER, &[Syntax::RangeOfInteger]),
+    AttributeInfo::new("job-k-octets", OPERATION_JOB, &[INTEGER_0_MAX]),
+    AttributeInfo::new("job-k-octets-processed", JOB, &[INTEGER_0_MAX]),
+    AttributeInfo::new("job-k-octets-supported", PRINTER, &[Syntax::RangeOfInteger]),
+    AttributeInfo::new("job-mandatory-attributes", OPERATION_JOB, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("job-media-sheets", OPERATION_JOB, &[INTEGER_0_MAX]),
+    AttributeInfo::new("job-media-sheets-col", JOB, &[Syntax::Collection]).members(JOB_COUNTER_MEMBERS),
+    AttributeInfo::new("job-media-sheets-completed", JOB, &[INTEGER_0_MAX]),
+    AttributeInfo::new("job-media-sheets-completed-col", JOB, &[Syntax::Collection]).members(JOB_COUNTER_MEMBERS),
+    AttributeInfo::new("job-media-sheets-supported", PRINTER, &[Syntax::RangeOfInteger]),
+    AttributeInfo::new("job-message-from-operator", JOB, &[TEXT_127]),
+    AttributeInfo::new("job-more-info", JOB, &[Syntax::Uri]),
+    AttributeInfo::new("job-name", OPERATION_JOB, &[NAME]),
+    AttributeInfo::new("job-originating-user-name", JOB, &[NAME]),
+    AttributeInfo::new("job-originating-user-uri", JOB, &[Syntax::Uri]),
+    AttributeInfo::new("job-pages", JOB, &[INTEGER_0_MAX]),
+    AttributeInfo::new("job-pages-col", JOB, &[Syntax::Collection]).members(JOB_COUNTER_MEMBERS),
+    AttributeInfo::new("job-pages-completed", JOB, &[INTEGER_0_MAX]),
+    AttributeInfo::new("job-pages-completed-col", JOB, &[Syntax::Collection]).members(JOB_COUNTER_MEMBERS),
+    AttributeInfo::new("job-pages-per-set", JOB, &[INTEGER_1_MAX]),
+    AttributeInfo::new("job-pages-per-set-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("job-password", OPERATION_JOB, &[Syntax::OctetString(255)]),
+    AttributeInfo::new("job-password-encryption", OPERATION_JOB, &[Syntax::Keyword, NAME])
+        .keywords(JOB_PASSWORD_ENCRYPTION),
+    AttributeInfo::new("job-password-encryption-supported", PRINTER, &[Syntax::Keyword, NAME])
+        .set_of()
+        .keywords(JOB_PASSWORD_ENCRYPTION),
+    AttributeInfo::new(
+        "job-password-supported",
+        PRINTER,
+        &[Syntax::Integer { min: 0, max: 255 }],
+    ),
+    AttributeInfo::new("job-phone-number", JOB, &[Syntax::Uri]),
+    AttributeInfo::new("job-phone-number-default", PRINTER, &[Syntax::Uri, Syntax::NoValue]),
+    AttributeInfo::new("job-phone-number-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("job-presets-supported", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(JOB_PRESET_MEMBERS),
+    AttributeInfo::new("job-printer-state-message", JOB, &[TEXT]),
+    AttributeInfo::new("job-printer-state-reasons", JOB, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("job-printer-up-time", JOB, &[INTEGER_1_MAX]),
+    AttributeInfo::new("job-printer-uri", JOB, &[Syntax::Uri]),
+    AttributeInfo::new("job-priority", JOB, &[INTEGER_1_100]),
+    AttributeInfo::new("job-priority-default", PRINTER, &[INTEGER_1_100]),
+    AttributeInfo::new("job-priority-supported", PRINTER, &[INTEGER_1_100]),
+    AttributeInfo::new("job-processing-time", JOB, &[INTEGER_0_MAX]),
+    AttributeInfo::new("job-recipient-name", JOB, &[NAME]),
+    AttributeInfo::new("job-recipient-name-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("job-resolvers-supported", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(JOB_RESOLVER_MEMBERS),
+    AttributeInfo::new("job-retain-until", JOB, &[Syntax::Keyword, NAME]).keywords(JOB_RETAIN_UNTIL),
+    AttributeInfo::new("job-retain-until-default", PRINTER, &[Syntax::Keyword, NAME]).keywords(JOB_RETAIN_UNTIL),
+    AttributeInfo::new("job-retain-until-supported", PRINTER, &[Syntax::Keyword, NAME])
+        .set_of()
+        .keywords(JOB_RETAIN_UNTIL),
+    AttributeInfo::new("job-retain-until-time", JOB, &[Syntax::DateTime]),
+    AttributeInfo::new("job-sheet-message", JOB, &[TEXT]),
+    AttributeInfo::new("job-sheet-message-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("job-sheets", JOB, &[Syntax::Keyword, NAME]).keywords(JOB_SHEETS),
+    AttributeInfo::new("job-sheets-col", JOB, &[Syntax::Collection]).members(JOB_SHEETS_COL_MEMBERS),
+    AttributeInfo::new("job-sheets-col-default", PRINTER, &[Syntax::Collection]).members(JOB_SHEETS_COL_MEMBERS),
+    AttributeInfo::new("job-sheets-col-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("job-sheets-default", PRINTER, &[Syntax::Keyword, NAME]).keywords(JOB_SHEETS),
+    AttributeInfo::new("job-sheets-supported", PRINTER, &[Syntax::Keyword, NAME])
+        .set_of()
+        .keywords(JOB_SHEETS),
+    AttributeInfo::new("job-state", JOB, &[Syntax::Enum]).enums(JOB_STATE),
+    AttributeInfo::new("job-state-message", JOB, &[TEXT]),
+    AttributeInfo::new("job-state-reasons", JOB, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("job-uri", OPERATION_JOB, &[Syntax::Uri]),
+    AttributeInfo::new("job-uuid", JOB, &[Syntax::Uri]),
+    AttributeInfo::new("jpeg-k-octets-supported", PRINTER, &[Syntax::RangeOfInteger]),
+    AttributeInfo::new("jpeg-x-dimension-supported", PRINTER, &[Syntax::RangeOfInteger]),
+    AttributeInfo::new("jpeg-y-dimension-supported", PRINTER, &[Syntax::RangeOfInteger]),
+    AttributeInfo::new("landscape-orientation-requested-preferred", PRINTER, &[Syntax::Enum])
+        .enums(ORIENTATION_REQUESTED),
+    AttributeInfo::new("last-document", OPERATION, &[Syntax::Boolean]),
+    AttributeInfo::new("limit", OPERATION, &[INTEGER_1_MAX]),
+    AttributeInfo::new("max-page-ranges-supported", PRINTER, &[INTEGER_1_MAX]),
+    AttributeInfo::new("media", JOB, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("media-bottom-margin-supported", PRINTER, &[INTEGER_0_MAX]).set_of(),
+    AttributeInfo::new("media-col", JOB, &[Syntax::Collection]).members(MEDIA_COL_MEMBERS),
+    AttributeInfo::new("media-col-database", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(MEDIA_COL_MEMBERS),
+    AttributeInfo::new("media-col-default", PRINTER, &[Syntax::Collection]).members(MEDIA_COL_MEMBERS),
+    AttributeInfo::new("media-col-ready", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(MEDIA_COL_MEMBERS),
+    AttributeInfo::new("media-col-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("media-color-supported", PRINTER, &[Syntax::Keyword, NAME]).set_of(),
+    AttributeInfo::new("media-default", PRINTER, &[Syntax::Keyword, NAME, Syntax::NoValue]),
+    AttributeInfo::new("media-key-supported", PRINTER, &[Syntax::Keyword, NAME]).set_of(),
+    AttributeInfo::new("media-left-margin-supported", PRINTER, &[INTEGER_0_MAX]).set_of(),
+    AttributeInfo::new("media-ready", PRINTER, &[Syntax::Keyword, NAME]).set_of(),
+    AttributeInfo::new("media-right-margin-supported", PRINTER, &[INTEGER_0_MAX]).set_of(),
+    AttributeInfo::new("media-size-supported", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(MEDIA_SIZE_MEMBERS),
+    AttributeInfo::new("media-source-supported", PRINTER, &[Syntax::Keyword, NAME]).set_of(),
+    AttributeInfo::new("media-supported", PRINTER, &[Syntax::Keyword, NAME]).set_of(),
+    AttributeInfo::new("media-top-margin-supported", PRINTER, &[INTEGER_0_MAX]).set_of(),
+    AttributeInfo::new("media-type-supported", PRINTER, &[Syntax::Keyword, NAME]).set_of(),
+    AttributeInfo::new("message", OPERATION, &[TEXT_127]),
+    AttributeInfo::new("multiple-document-handling", JOB, &[Syntax::Keyword]).keywords(MULTIPLE_DOCUMENT_HANDLING),
+    AttributeInfo::new("multiple-document-handling-default", PRINTER, &[Syntax::Keyword])
+        .keywords(MULTIPLE_DOCUMENT_HANDLING),
+    AttributeInfo::new("multiple-document-handling-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(MULTIPLE_DOCUMENT_HANDLING),
+    AttributeInfo::new("multiple-document-jobs-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("multiple-operation-time-out", PRINTER, &[INTEGER_1_MAX]),
+    AttributeInfo::new("my-jobs", OPERATION, &[Syntax::Boolean]),
+    AttributeInfo::new("natural-language-configured", PRINTER, &[Syntax::NaturalLanguage]),
+    AttributeInfo::new("number-of-documents", JOB, &[INTEGER_0_MAX]),
+    AttributeInfo::new("number-of-intervening-jobs", JOB, &[INTEGER_0_MAX]),
+    AttributeInfo::new("number-up", JOB, &[INTEGER_1_MAX]),
+    AttributeInfo::new("number-up-default", PRINTER, &[INTEGER_1_MAX]),
+    AttributeInfo::new("number-up-supported", PRINTER, &[INTEGER_1_MAX, Syntax::RangeOfInteger]).set_of(),
+    AttributeInfo::new("operations-supported", PRINTER, &[Syntax::Enum])
+        .set_of()
+        .enums(OPERATIONS),
+    AttributeInfo::new("orientation-requested", JOB, &[Syntax::Enum]).enums(ORIENTATION_REQUESTED),
+    AttributeInfo::new(
+        "orientation-requested-default",
+        PRINTER,
+        &[Syntax::Enum, Syntax::NoValue],
+    )
+    .enums(ORIENTATION_REQUESTED),
+    AttributeInfo::new("orientation-requested-supported", PRINTER, &[Syntax::Enum])
+        .set_of()
+        .enums(ORIENTATION_REQUESTED),
+    AttributeInfo::new("output-bin", JOB, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("output-bin-default", PRINTER, &[Syntax::Keyword, NAME]),
+    AttributeInfo::new("output-bin-supported", PRINTER, &[Syntax::Keyword, NAME]).set_of(),
+    AttributeInfo::new("output-device-assigned", JOB, &[NAME_127]),
+    AttributeInfo::new("overrides", JOB, &[Syntax::Collection])
+        .set_of()
+        .members(OVERRIDES_MEMBERS),
+    AttributeInfo::new("overrides-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("page-delivery", JOB, &[Syntax::Keyword]).keywords(PAGE_DELIVERY),
+    AttributeInfo::new("page-delivery-default", PRINTER, &[Syntax::Keyword]).keywords(PAGE_DELIVERY),
+    AttributeInfo::new("page-delivery-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(PAGE_DELIVERY),
+    AttributeInfo::new("page-ranges", JOB, &[Syntax::RangeOfInteger]).set_of(),
+    AttributeInfo::new("page-ranges-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("pages-per-minute", PRINTER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("pages-per-minute-color", PRINTER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("pages-per-subset", JOB, &[INTEGER_1_MAX]).set_of(),
+    AttributeInfo::new("pages-per-subset-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("pdf-k-octets-supported", PRINTER, &[Syntax::RangeOfInteger]),
+    AttributeInfo::new("pdf-versions-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("pdl-init-file", JOB, &[Syntax::Collection])
+        .set_of()
+        .members(PDL_INIT_FILE_MEMBERS),
+    AttributeInfo::new("pdl-init-file-default", PRINTER, &[Syntax::Collection, Syntax::NoValue])
+        .set_of()
+        .members(PDL_INIT_FILE_MEMBERS),
+    AttributeInfo::new("pdl-init-file-entry-supported", PRINTER, &[NAME]).set_of(),
+    AttributeInfo::new("pdl-init-file-location-supported", PRINTER, &[Syntax::Uri]).set_of(),
+    AttributeInfo::new("pdl-init-file-name-subdirectory-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("pdl-init-file-name-supported", PRINTER, &[NAME]).set_of(),
+    AttributeInfo::new("pdl-init-file-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("pdl-override-supported", PRINTER, &[Syntax::Keyword]).keywords(PDL_OVERRIDE),
+    AttributeInfo::new("preferred-attributes", OPERATION, &[Syntax::Collection]),
+    AttributeInfo::new("preferred-attributes-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("presentation-direction-number-up", JOB, &[Syntax::Keyword])
+        .keywords(PRESENTATION_DIRECTION_NUMBER_UP),
+    AttributeInfo::new("presentation-direction-number-up-default", PRINTER, &[Syntax::Keyword])
+        .keywords(PRESENTATION_DIRECTION_NUMBER_UP),
+    AttributeInfo::new(
+        "presentation-direction-number-up-supported",
+        PRINTER,
+        &[Syntax::Keyword],
+    )
+    .set_of()
+    .keywords(PRESENTATION_DIRECTION_NUMBER_UP),
+    AttributeInfo::new("print-color-mode", JOB, &[Syntax::Keyword]).keywords(PRINT_COLOR_MODE),
+    AttributeInfo::new("print-color-mode-default", PRINTER, &[Syntax::Keyword]).keywords(PRINT_COLOR_MODE),
+    AttributeInfo::new("print-color-mode-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(PRINT_COLOR_MODE),
+    AttributeInfo::new("print-content-optimize", JOB, &[Syntax::Keyword]).keywords(PRINT_CONTENT_OPTIMIZE),
+    AttributeInfo::new("print-content-optimize-default", PRINTER, &[Syntax::Keyword]).keywords(PRINT_CONTENT_OPTIMIZE),
+    AttributeInfo::new("print-content-optimize-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(PRINT_CONTENT_OPTIMIZE),
+    AttributeInfo::new("print-quality", JOB, &[Syntax::Enum]).enums(PRINT_QUALITY),
+    AttributeInfo::new("print-quality-default", PRINTER, &[Syntax::Enum]).enums(PRINT_QUALITY),
+    AttributeInfo::new("print-quality-supported", PRINTER, &[Syntax::Enum])
+        .set_of()
+        .enums(PRINT_QUALITY),
+    AttributeInfo::new("print-rendering-intent", JOB, &[Syntax::Keyword]).keywords(PRINT_RENDERING_INTENT),
+    AttributeInfo::new("print-rendering-intent-default", PRINTER, &[Syntax::Keyword]).keywords(PRINT_RENDERING_INTENT),
+    AttributeInfo::new("print-rendering-intent-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(PRINT_RENDERING_INTENT),
+    AttributeInfo::new("print-scaling", JOB, &[Syntax::Keyword]).keywords(PRINT_SCALING),
+    AttributeInfo::new("print-scaling-default", PRINTER, &[Syntax::Keyword]).keywords(PRINT_SCALING),
+    AttributeInfo::new("print-scaling-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(PRINT_SCALING),
+    AttributeInfo::new("printer-alert", PRINTER, &[Syntax::OctetString(MAX_OCTETS)]).set_of(),
+    AttributeInfo::new("printer-alert-description", PRINTER, &[TEXT]).set_of(),
+    AttributeInfo::new("printer-charge-info", PRINTER, &[TEXT]),
+    AttributeInfo::new("printer-charge-info-uri", PRINTER, &[Syntax::Uri]),
+    AttributeInfo::new("printer-config-change-date-time", PRINTER, &[Syntax::DateTime]),
+    AttributeInfo::new("printer-config-change-time", PRINTER, &[INTEGER_1_MAX]),
+    AttributeInfo::new("printer-current-time", PRINTER, &[Syntax::DateTime, Syntax::NoValue]),
+    AttributeInfo::new("printer-detailed-status-messages", PRINTER, &[TEXT]).set_of(),
+    AttributeInfo::new("printer-device-id", PRINTER, &[TEXT]),
+    AttributeInfo::new("printer-dns-sd-name", PRINTER, &[Syntax::Name(63)]),
+    AttributeInfo::new("printer-driver-installer", PRINTER, &[Syntax::Uri]),
+    AttributeInfo::new("printer-finisher", PRINTER, &[Syntax::OctetString(MAX_OCTETS)]).set_of(),
+    AttributeInfo::new("printer-finisher-description", PRINTER, &[TEXT]).set_of(),
+    AttributeInfo::new("printer-firmware-name", PRINTER, &[NAME]).set_of(),
+    AttributeInfo::new("printer-firmware-patches", PRINTER, &[TEXT]).set_of(),
+    AttributeInfo::new("printer-firmware-string-version", PRINTER, &[TEXT]).set_of(),
+    AttributeInfo::new("printer-firmware-version", PRINTER, &[Syntax::OctetString(64)]).set_of(),
+    AttributeInfo::new("printer-geo-location", PRINTER, &[Syntax::Uri]),
+    AttributeInfo::new("printer-get-attributes-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("printer-icc-profiles", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(ICC_PROFILE_MEMBERS),
+    AttributeInfo::new("printer-icons", PRINTER, &[Syntax::Uri]).set_of(),
+    AttributeInfo::new("printer-info", PRINTER, &[TEXT_127]),
+    AttributeInfo::new("printer-input-tray", PRINTER, &[Syntax::OctetString(MAX_OCTETS)]).set_of(),
+    AttributeInfo::new("printer-is-accepting-jobs", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("printer-kind", PRINTER, &[Syntax::Keyword, NAME]).set_of(),
+    AttributeInfo::new("printer-location", PRINTER, &[TEXT_127]),
+    AttributeInfo::new("printer-make-and-model", PRINTER, &[TEXT_127]),
+    AttributeInfo::new("printer-mandatory-job-attributes", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("printer-message-date-time", PRINTER, &[Syntax::DateTime]),
+    AttributeInfo::new("printer-message-from-operator", PRINTER, &[TEXT_127]),
+    AttributeInfo::new("printer-message-time", PRINTER, &[INTEGER_MIN_MAX]),
+    AttributeInfo::new("printer-more-info", PRINTER, &[Syntax::Uri]),
+    AttributeInfo::new("printer-more-info-manufacturer", PRINTER, &[Syntax::Uri]),
+    AttributeInfo::new("printer-name", PRINTER, &[NAME_127]),
+    AttributeInfo::new("printer-organization", PRINTER, &[TEXT]).set_of(),
+    AttributeInfo::new("printer-organizational-unit", PRINTER, &[TEXT]).set_of(),
+    AttributeInfo::new("printer-output-tray", PRINTER, &[Syntax::OctetString(MAX_OCTETS)]).set_of(),
+    AttributeInfo::new("printer-resolution", JOB, &[Syntax::Resolution]),
+    AttributeInfo::new("printer-resolution-default", PRINTER, &[Syntax::Resolution]),
+    AttributeInfo::new("printer-resolution-supported", PRINTER, &[Syntax::Resolution]).set_of(),
+    AttributeInfo::new("printer-settable-attributes-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("printer-state", PRINTER, &[Syntax::Enum]).enums(PRINTER_STATE),
+    AttributeInfo::new("printer-state-change-date-time", PRINTER, &[Syntax::DateTime]),
+    AttributeInfo::new("printer-state-change-time", PRINTER, &[INTEGER_1_MAX]),
+    AttributeInfo::new("printer-state-message", PRINTER, &[TEXT]),
+    AttributeInfo::new("printer-state-reasons", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("printer-static-resource-directory-uri", PRINTER, &[Syntax::Uri]),
+    AttributeInfo::new(
+        "printer-strings-languages-supported",
+        PRINTER,
+        &[Syntax::NaturalLanguage],
+    )
+    .set_of(),
+    AttributeInfo::new("printer-strings-uri", PRINTER, &[Syntax::Uri, Syntax::NoValue]),
+    AttributeInfo::new("printer-supply", PRINTER, &[Syntax::OctetString(MAX_OCTETS)]).set_of(),
+    AttributeInfo::new("printer-supply-description", PRINTER, &[TEXT]).set_of(),
+    AttributeInfo::new("printer-supply-info-uri", PRINTER, &[Syntax::Uri]),
+    AttributeInfo::new("printer-up-time", PRINTER, &[INTEGER_1_MAX]),
+    AttributeInfo::new("printer-uri", OPERATION, &[Syntax::Uri]),
+    AttributeInfo::new("printer-uri-supported", PRINTER, &[Syntax::Uri]).set_of(),
+    AttributeInfo::new("printer-uuid", PRINTER, &[Syntax::Uri]),
+    AttributeInfo::new("printer-xri-supported", PRINTER, &[Syntax::Collection])
+        .set_of()
+        .members(PRINTER_XRI_MEMBERS),
+    AttributeInfo::new("proof-print", JOB, &[Syntax::Collection]).members(PROOF_PRINT_MEMBERS),
+    AttributeInfo::new("proof-print-default", PRINTER, &[Syntax::Collection, Syntax::NoValue])
+        .members(PROOF_PRINT_MEMBERS),
+    AttributeInfo::new("proof-print-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new(
+        "pwg-raster-document-resolution-supported",
+        PRINTER,
+        &[Syntax::Resolution],
+    )
+    .set_of(),
+    AttributeInfo::new("pwg-raster-document-sheet-back", PRINTER, &[Syntax::Keyword])
+        .keywords(PWG_RASTER_DOCUMENT_SHEET_BACK),
+    AttributeInfo::new("pwg-raster-document-type-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("queued-job-count", PRINTER, &[INTEGER_0_MAX]),
+    AttributeInfo::new("reference-uri-schemes-supported", PRINTER, &[Syntax::UriScheme]).set_of(),
+    AttributeInfo::new("requested-attributes", OPERATION, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("requesting-user-name", OPERATION, &[NAME]),
+    AttributeInfo::new("requesting-user-uri", OPERATION, &[Syntax::Uri]),
+    AttributeInfo::new("requesting-user-uri-supported", PRINTER, &[Syntax::Boolean]),
+    AttributeInfo::new("separator-sheets", JOB, &[Syntax::Collection]).members(SEPARATOR_SHEETS_MEMBERS),
+    AttributeInfo::new("separator-sheets-default", PRINTER, &[Syntax::Collection]).members(SEPARATOR_SHEETS_MEMBERS),
+    AttributeInfo::new("separator-sheets-supported", PRINTER, &[Syntax::Keyword]).set_of(),
+    AttributeInfo::new("sheet-collate", JOB, &[Syntax::Keyword]).keywords(SHEET_COLLATE),
+    AttributeInfo::new("sheet-collate-default", PRINTER, &[Syntax::Keyword]).keywords(SHEET_COLLATE),
+    AttributeInfo::new("sheet-collate-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(SHEET_COLLATE),
+    AttributeInfo::new("sides", JOB, &[Syntax::Keyword]).keywords(SIDES),
+    AttributeInfo::new("sides-default", PRINTER, &[Syntax::Keyword]).keywords(SIDES),
+    AttributeInfo::new("sides-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(SIDES),
+    AttributeInfo::new("status-message", OPERATION, &[TEXT_255]),
+    AttributeInfo::new("time-at-completed", JOB, &[INTEGER_MIN_MAX, Syntax::NoValue]),
+    AttributeInfo::new("time-at-creation", JOB, &[INTEGER_MIN_MAX]),
+    AttributeInfo::new("time-at-processing", JOB, &[INTEGER_MIN_MAX, Syntax::NoValue]),
+    AttributeInfo::new("uri-authentication-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(URI_AUTHENTICATION),
+    AttributeInfo::new("uri-security-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(URI_SECURITY),
+    AttributeInfo::new("which-jobs", OPERATION, &[Syntax::Keyword]).keywords(WHICH_JOBS),
+    AttributeInfo::new("which-jobs-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(WHICH_JOBS),
+    AttributeInfo::new("x-image-position", JOB, &[Syntax::Keyword]).keywords(X_IMAGE_POSITION),
+    AttributeInfo::new("x-image-position-default", PRINTER, &[Syntax::Keyword]).keywords(X_IMAGE_POSITION),
+    AttributeInfo::new("x-image-position-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(X_IMAGE_POSITION),
+    AttributeInfo::new("x-image-shift", JOB, &[INTEGER_MIN_MAX]),
+    AttributeInfo::new("x-image-shift-default", PRINTER, &[INTEGER_MIN_MAX]),
+    AttributeInfo::new("x-image-shift-supported", PRINTER, &[Syntax::RangeOfInteger]),
+    AttributeInfo::new("y-image-position", JOB, &[Syntax::Keyword]).keywords(Y_IMAGE_POSITION),
+    AttributeInfo::new("y-image-position-default", PRINTER, &[Syntax::Keyword]).keywords(Y_IMAGE_POSITION),
+    AttributeInfo::new("y-image-position-supported", PRINTER, &[Syntax::Keyword])
+        .set_of()
+        .keywords(Y_IMAGE_POSITION),
+    AttributeInfo::new("y-image-shift", JOB, &[INTEGER_MIN_MAX]),
+    AttributeInfo::new("y-image-shift-default", PRINTER, &[INTEGER_MIN_MAX]),
+    AttributeInfo::new("y-image-shift-supported", PRINTER, &[Syntax::RangeOfInteger]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        FromPrimitive,
+        attribute::IppAttribute,
+        model::{Finishings, JobState, Operation, Orientation, PrintQuality, PrinterState},
+    };
+
+    #[test]
+    fn test_registry_is_sorted() {
+        fn check(attributes: &[AttributeInfo]) {
+            assert!(attributes.windows(2).all(|w| w[0].name < w[1].name));
+            for info in attributes {
+                check(info.members);
+            }
+        }
+        check(REGISTRY);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let copies = lookup(IppAttribute::COPIES).unwrap();
+        assert_eq!(copies.syntax, &[INTEGER_1_MAX]);
+        assert!(!copies.set_of);
+        assert_eq!(copies.groups, &[DelimiterTag::JobAttributes]);
+
+        let finishings = lookup(IppAttribute::FINISHINGS).unwrap();
+        assert!(finishings.set_of);
+        assert_eq!(finishings.enum_name(4), Some("staple"));
+        assert_eq!(finishings.enum_value("punch"), Some(5));
+
+        assert!(lookup("x-unknown").is_none());
+
+        let media_col = lookup(IppAttribute::MEDIA_COL_DATABASE).unwrap();
+        assert!(media_col.set_of);
+        assert_eq!(media_col.syntax, &[Syntax::Collection]);
+        let media_size = media_col.member("media-size").unwrap();
+        assert_eq!(media_size.member("x-dimension").unwrap().syntax[0], INTEGER_1_MAX);
+        assert_eq!(media_col.member("media-info").unwrap().syntax, &[TEXT_255]);
+        assert!(media_col.member("x-unknown").is_none());
+
+        let color_mode = lookup(IppAttribute::PRINT_COLOR_MODE_SUPPORTED).unwrap();
+        assert!(color_mode.set_of);
+        assert_eq!(color_mode.values, AllowedValues::Keywords(PRINT_COLOR_MODE));
+    }
+
+    #[test]
+    fn test_model_enums_are_registered() {
+        fn check<T: FromPrimitive>(name: &str) {
+            let info = lookup(name).unwrap();
+            for value in 0..=0x5000 {
+                if T::from_i32(value).is_some() {
+                    assert!(info.enum_name(value).is_some(), "{name}: {value}");
+                }
+            }
+        }
+        check::<PrinterState>(IppAttribute::PRINTER_STATE);
+        check::<JobState>(IppAttribute::JOB_STATE);
+        check::<Finishings>(IppAttribute::FINISHINGS);
+        check::<Orientation>(IppAttribute::ORIENTATION_REQUESTED);
+        check::<PrintQuality>(IppAttribute::PRINT_QUALITY);
+        check::<Operation>(IppAttribute::OPERATIONS_SUPPORTED);
+    }
+
+    #[test]
+    fn test_parse_value() {
+        let info = lookup(IppAttribute::COPIES).unwrap();
+        assert_eq!(info.parse_value("2").unwrap(), IppValue::Integer(2));
+        assert_eq!(info.parse_value("integer:2").unwrap(), IppValue::Integer(2));
+        assert!(info.parse_value("0").is_err());
+
+        let info = lookup(IppAttribute::MEDIA).unwrap();
+        assert_eq!(
+            info.parse_value("iso_a4_210x297").unwrap(),
+            IppValue::new_keyword("iso_a4_210x297").unwrap()
+        );
+        assert_eq!(
+            info.parse_value("\"My Paper\"").unwrap(),
+            IppValue::new_name_without_language("My Paper").unwrap()
+        );
+        assert_eq!(
+            info.parse_value("name:foo").unwrap(),
+            IppValue::new_name_without_language("foo").unwrap()
+        );
+        assert_eq!(info.parse_value("no-value").unwrap(), IppValue::NoValue);
+
+        let info = lookup(IppAttribute::FINISHINGS).unwrap();
+        assert_eq!(
+            info.parse_value("staple,punch").unwrap(),
+            IppValue::Array(vec![IppValue::Enum(4), IppValue::Enum(5)])
+        );
+
+        let info = lookup(IppAttribute::PRINTER_RESOLUTION).unwrap();
+        assert_eq!(
+            info.parse_value("300x600dpi").unwrap(),
+            IppValue::new_resolution(300, 600, 3)
+        );
+        assert_eq!(
+            info.parse_value("300dpi").unwrap(),
+            IppValue::new_resolution(300, 300, 3)
+        );
+
+        let info = lookup(IppAttribute::PAGE_RANGES).unwrap();
+        assert_eq!(info.parse_value("1-5").unwrap(), IppValue::new_range_of_integer(1, 5));
+    }
+
+    #[test]
+    fn test_matches() {
+        let name = IppValue::new_name_without_language("é".repeat(100))
+            .unwrap()
+            .encode_charset(crate::charset::Charset::Iso8859_1)
+            .unwrap();
+        assert!(Syntax::Name(MAX_NAME).matches(&name));
+        assert!(!Syntax::Text(MAX_TEXT).matches(&name));
+
+        let info = lookup(IppAttribute::SIDES).unwrap();
+        assert!(info.matches(&IppValue::new_keyword("one-sided").unwrap()));
+        assert!(!info.matches(&IppValue::Integer(1)));
+        assert!(!info.matches(&IppValue::Array(vec![IppValue::new_keyword("one-sided").unwrap()])));
+    }
+}
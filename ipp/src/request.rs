@@ -15,6 +15,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     IppHeader,
     attribute::{IppAttribute, IppAttributes},
+    charset::Charset,
     model::{DelimiterTag, IppVersion, Operation, StatusCode},
     parser::IppParseError,
     payload::IppPayload,
@@ -28,10 +29,17 @@ pub struct IppRequestResponse {
     pub(crate) attributes: IppAttributes,
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) payload: IppPayload,
+    // version assigned by the operation, None if it was set by the caller
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) default_version: Option<IppVersion>,
+    // natural language assigned by the operation, None if it was set by the caller
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) default_language: Option<IppLanguage>,
 }
 
 impl IppRequestResponse {
-    /// Create a new IPP request for the operation and URI
+    /// Create a new IPP request for the operation and URI.
+    /// The version is kept by the clients, the default natural language "en" may be replaced by the client default.
     pub fn new(
         version: IppVersion,
         operation: Operation,
@@ -40,9 +48,12 @@ impl IppRequestResponse {
         let uri = uri
             .map(|uri| crate::util::canonicalize_uri(&uri).try_into())
             .transpose()?;
-        Ok(Self::new_internal(version, operation, uri))
+        let mut request = Self::new_internal(version, operation, uri);
+        request.default_version = None;
+        Ok(request)
     }
 
+    // the version and natural language may be replaced by the client defaults
     pub(crate) fn new_internal(
         version: IppVersion,
         operation: Operation,
@@ -61,11 +72,12 @@ impl IppRequestResponse {
         );
 
         // unwrap is fine because "en" into bounded string is infallible.
+        let language: IppLanguage = "en".try_into().unwrap();
         attributes.add(
             DelimiterTag::OperationAttributes,
             IppAttribute::new(
                 IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE.try_into().unwrap(),
-                IppValue::NaturalLanguage("en".try_into().unwrap()),
+                IppValue::NaturalLanguage(language.clone()),
             ),
         );
 
@@ -80,16 +92,20 @@ impl IppRequestResponse {
             header,
             attributes,
             payload: IppPayload::empty(),
+            default_version: Some(version),
+            default_language: Some(language),
         }
     }
 
     /// Create a response from a status and id
     pub fn new_response(version: IppVersion, status: StatusCode, id: i32) -> Result<IppRequestResponse, IppParseError> {
-        let header = IppHeader::new(version, status as i16, id);
+        let header = IppHeader::new(version, status.code(), id);
         let mut response = IppRequestResponse {
             header,
             attributes: IppAttributes::new(),
             payload: IppPayload::empty(),
+            default_version: None,
+            default_language: None,
         };
 
         response.attributes_mut().add(
@@ -110,6 +126,67 @@ impl IppRequestResponse {
         Ok(response)
     }
 
+    pub(crate) fn set_operation_attribute(&mut self, attribute: IppAttribute) {
+        match self.attributes.first_of_mut(DelimiterTag::OperationAttributes) {
+            Some(group) => match group.attributes_mut().iter_mut().find(|a| a.name() == attribute.name()) {
+                Some(existing) => *existing = attribute,
+                None => group.attributes_mut().push(attribute),
+            },
+            None => self.attributes.add(DelimiterTag::OperationAttributes, attribute),
+        }
+    }
+
+    /// Set the attributes-charset operation attribute, replacing the existing one
+    pub fn set_charset(&mut self, charset: IppCharset) {
+        self.set_operation_attribute(IppAttribute::new(
+            IppAttribute::ATTRIBUTES_CHARSET.try_into().unwrap(),
+            IppValue::Charset(charset),
+        ));
+    }
+
+    /// Encode all text and name values in a given charset, e.g. one requested by the printer
+    /// in `charset-supported`, and declare it in the `attributes-charset` operation attribute
+    pub fn encode_charset(&mut self, charset: Charset) -> Result<(), IppParseError> {
+        for group in self.attributes.groups_mut() {
+            for attr in group.attributes_mut() {
+                *attr = IppAttribute::new(attr.name().clone(), attr.value().encode_charset(charset)?);
+            }
+        }
+        self.set_charset(charset.name().try_into()?);
+        Ok(())
+    }
+
+    /// Set the attributes-natural-language operation attribute, replacing the existing one.
+    /// The clients keep the language set by this method.
+    pub fn set_natural_language(&mut self, language: IppLanguage) {
+        self.default_language = None;
+        self.set_operation_attribute(IppAttribute::new(
+            IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE.try_into().unwrap(),
+            IppValue::NaturalLanguage(language),
+        ));
+    }
+
+    /// Set the requesting-user-name operation attribute, replacing the existing one
+    pub fn set_user_name(&mut self, user_name: IppName) {
+        self.set_operation_attribute(IppAttribute::new(
+            IppAttribute::REQUESTING_USER_NAME.try_into().unwrap(),
+            IppValue::NameWithoutLanguage(user_name),
+        ));
+    }
+
+    /// Set the IPP version of the request. The clients keep the version set by this method
+    /// instead of replacing it with the preferred or negotiated version.
+    pub fn set_version(&mut self, version: IppVersion) {
+        self.header.version = version;
+        self.default_version = None;
+    }
+
+    /// Return true if the version was assigned by the operation and may be replaced by the clients
+    #[cfg(any(feature = "client", feature = "async-client"))]
+    pub(crate) fn is_default_version(&self) -> bool {
+        self.default_version == Some(self.header.version)
+    }
+
     /// Get the IPP header
     pub fn header(&self) -> &IppHeader {
         &self.header
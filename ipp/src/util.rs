@@ -2,12 +2,11 @@
 //! IPP helper functions
 //!
 use http::Uri;
-use num_traits::FromPrimitive;
 
 use crate::{
     attribute::IppAttribute,
     error::IppError,
-    model::{DelimiterTag, PrinterState},
+    model::{DelimiterTag, PrinterState, PrinterStateReason, PrinterStateReasonValue, Severity},
     prelude::IppRequestResponse,
     value::IppName,
 };
@@ -25,62 +24,138 @@ pub fn canonicalize_uri(uri: &Uri) -> Uri {
     builder.build().unwrap_or_else(|_| uri.to_owned())
 }
 
-const ERROR_STATES: &[&str] = &[
-    "media-jam",
-    "toner-empty",
-    "spool-area-full",
-    "cover-open",
-    "door-open",
-    "input-tray-missing",
-    "output-tray-missing",
-    "marker-supply-empty",
-    "paused",
-    "shutdown",
-];
-
-/// Check if the printer is ready for printing
+/// Policy which decides whether printer-state-reasons prevent printing.
 ///
-/// * `response` - IPP response to check
-pub fn is_printer_ready(response: &IppRequestResponse) -> Result<bool, IppError> {
-    let status = response.header().status_code();
-    if !status.is_success() {
-        return Err(IppError::StatusError(status));
+/// The default policy blocks on reasons with an explicit "-error" suffix and on a fixed set of reasons
+/// which always prevent printing regardless of their suffix. Reasons without a suffix, such as `connecting-to-device`
+/// which CUPS sets for every job, only block if they are in the fixed set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadinessPolicy {
+    min_severity: Option<Severity>,
+    blocking: Vec<PrinterStateReason>,
+    ignored: Vec<PrinterStateReason>,
+}
+
+impl Default for ReadinessPolicy {
+    fn default() -> Self {
+        ReadinessPolicy {
+            min_severity: Some(Severity::Error),
+            blocking: vec![
+                PrinterStateReason::MediaJam,
+                PrinterStateReason::TonerEmpty,
+                PrinterStateReason::SpoolAreaFull,
+                PrinterStateReason::CoverOpen,
+                PrinterStateReason::DoorOpen,
+                PrinterStateReason::InputTrayMissing,
+                PrinterStateReason::OutputTrayMissing,
+                PrinterStateReason::MarkerSupplyEmpty,
+                PrinterStateReason::Paused,
+                PrinterStateReason::Shutdown,
+            ],
+            ignored: Vec::new(),
+        }
     }
+}
 
-    let printer_state_attr_name: IppName = IppAttribute::PRINTER_STATE.try_into()?;
+impl ReadinessPolicy {
+    /// Create a default readiness policy
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    let state = response
-        .attributes()
-        .groups_of(DelimiterTag::PrinterAttributes)
-        .next()
-        .and_then(|g| g.get(&printer_state_attr_name))
-        .and_then(|attr| attr.value().as_enum())
-        .and_then(|v| PrinterState::from_i32(*v));
+    /// Set the minimum severity suffix which prevents printing, `None` disables severity-based checks
+    pub fn min_severity(mut self, severity: Option<Severity>) -> Self {
+        self.min_severity = severity;
+        self
+    }
+
+    /// Always treat a given reason as blocking regardless of its severity
+    pub fn block(mut self, reason: PrinterStateReason) -> Self {
+        self.ignored.retain(|r| *r != reason);
+        self.blocking.push(reason);
+        self
+    }
+
+    /// Never treat a given reason as blocking
+    pub fn ignore(mut self, reason: PrinterStateReason) -> Self {
+        self.blocking.retain(|r| *r != reason);
+        self.ignored.push(reason);
+        self
+    }
+
+    /// Check if a single printer-state-reasons value prevents printing
+    pub fn is_blocking(&self, value: &PrinterStateReasonValue) -> bool {
+        if self.ignored.contains(&value.reason) {
+            false
+        } else if self.blocking.contains(&value.reason) {
+            true
+        } else {
+            self.min_severity
+                .is_some_and(|min| value.suffix.is_some_and(|severity| severity >= min))
+        }
+    }
+
+    /// Check if the printer is ready for printing according to this policy
+    ///
+    /// * `response` - IPP response to check
+    pub fn is_printer_ready(&self, response: &IppRequestResponse) -> Result<bool, IppError> {
+        let status = response.header().status_code();
+        if !status.is_success() {
+            return Err(IppError::StatusError(status));
+        }
+
+        let state = response
+            .attributes()
+            .groups_of(DelimiterTag::PrinterAttributes)
+            .next()
+            .and_then(|g| g.get_as::<PrinterState>(IppAttribute::PRINTER_STATE).ok());
+
+        if let Some(PrinterState::Stopped) = state {
+            return Ok(false);
+        }
 
-    if let Some(PrinterState::Stopped) = state {
-        return Ok(false);
+        Ok(!printer_state_reasons(response)?
+            .iter()
+            .any(|value| self.is_blocking(value)))
     }
+}
 
+/// Return the parsed printer-state-reasons values from the first printer attributes group
+///
+/// * `response` - IPP response to check
+pub fn printer_state_reasons(response: &IppRequestResponse) -> Result<Vec<PrinterStateReasonValue>, IppError> {
     let printer_state_reasons_name: IppName = IppAttribute::PRINTER_STATE_REASONS.try_into()?;
 
-    if let Some(reasons) = response
+    Ok(response
         .attributes()
         .groups_of(DelimiterTag::PrinterAttributes)
         .next()
         .and_then(|g| g.get(&printer_state_reasons_name))
-    {
-        let mut keywords = reasons.value().into_iter().filter_map(|e| e.as_keyword());
+        .map(|reasons| {
+            reasons
+                .values()
+                .iter()
+                .filter_map(|e| e.as_keyword())
+                .map(|k| PrinterStateReasonValue::parse(k))
+                .collect()
+        })
+        .unwrap_or_default())
+}
 
-        if keywords.any(|k| ERROR_STATES.contains(&&k[..])) {
-            return Ok(false);
-        }
-    }
-    Ok(true)
+/// Check if the printer is ready for printing using the default [`ReadinessPolicy`]
+///
+/// * `response` - IPP response to check
+pub fn is_printer_ready(response: &IppRequestResponse) -> Result<bool, IppError> {
+    ReadinessPolicy::default().is_printer_ready(response)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        model::{IppVersion, StatusCode},
+        value::IppValue,
+    };
 
     #[test]
     fn test_canonicalize_uri() {
@@ -93,4 +168,69 @@ mod tests {
             "ipp://example.com/path"
         );
     }
+
+    fn printer_response(reasons: &[&str]) -> IppRequestResponse {
+        let mut response = IppRequestResponse::new_response(IppVersion::v1_1(), StatusCode::SuccessfulOk, 1).unwrap();
+        response.attributes_mut().add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                IppAttribute::PRINTER_STATE.try_into().unwrap(),
+                IppValue::Enum(PrinterState::Idle as i32),
+            ),
+        );
+        response.attributes_mut().add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                IppAttribute::PRINTER_STATE_REASONS.try_into().unwrap(),
+                IppValue::Array(
+                    reasons
+                        .iter()
+                        .map(|r| IppValue::Keyword(r.to_string().try_into().unwrap()))
+                        .collect(),
+                ),
+            ),
+        );
+        response
+    }
+
+    #[test]
+    fn test_is_printer_ready() {
+        assert!(is_printer_ready(&printer_response(&["none"])).unwrap());
+        assert!(is_printer_ready(&printer_response(&["toner-low-warning", "media-low-report"])).unwrap());
+        assert!(!is_printer_ready(&printer_response(&["media-empty-error"])).unwrap());
+        assert!(!is_printer_ready(&printer_response(&["media-jam-warning"])).unwrap());
+        assert!(!is_printer_ready(&printer_response(&["paused"])).unwrap());
+        assert!(is_printer_ready(&printer_response(&["cups-waiting-for-job-completed"])).unwrap());
+        assert!(!is_printer_ready(&printer_response(&["stacker-full-error"])).unwrap());
+        assert!(is_printer_ready(&printer_response(&["connecting-to-device"])).unwrap());
+        assert!(is_printer_ready(&printer_response(&["printer-ready-to-print"])).unwrap());
+        assert!(is_printer_ready(&printer_response(&["moving-to-paused", "power-up"])).unwrap());
+    }
+
+    #[test]
+    fn test_readiness_policy() {
+        let policy = ReadinessPolicy::new()
+            .ignore(PrinterStateReason::Paused)
+            .block(PrinterStateReason::TonerLow);
+        assert!(policy.is_printer_ready(&printer_response(&["paused"])).unwrap());
+        assert!(
+            !policy
+                .is_printer_ready(&printer_response(&["toner-low-report"]))
+                .unwrap()
+        );
+
+        let policy = ReadinessPolicy::new().min_severity(Some(Severity::Warning));
+        assert!(
+            !policy
+                .is_printer_ready(&printer_response(&["media-low-warning"]))
+                .unwrap()
+        );
+
+        let policy = ReadinessPolicy::new().min_severity(None);
+        assert!(
+            policy
+                .is_printer_ready(&printer_response(&["media-empty-error"]))
+                .unwrap()
+        );
+    }
 }
@@ -0,0 +1,596 @@
+//!
+//! Opt-in validation of IPP messages against RFC 8011 rules
+//!
+//! The parser and the value constructors only enforce generic length limits. [`validate`] checks a whole
+//! message against the attribute registry and reports every violation instead of stopping at the first one:
+//!
+//! * operation attributes must come first, starting with `attributes-charset` and `attributes-natural-language`
+//! * values of registered attributes must conform to the attribute syntax, including `text(MAX)` and `name(MAX)` limits
+//! * single-valued attributes must not have multiple values
+//! * keywords must conform to the keyword grammar
+//! * `rangeOfInteger` values must have min <= max
+//!
+//! [`validate_request`] additionally checks the operation attributes required by the request operation
+//! (RFC 8011 section 4): the `printer-uri` or `job-uri`/`job-id` target right after the natural language
+//! and operation-specific attributes such as `last-document`. A missing `requesting-user-name`, which clients
+//! SHOULD supply, is reported as [`ViolationKind::MissingRecommended`]. CUPS operations are not checked.
+//!
+use std::fmt;
+
+use crate::{
+    FromPrimitive as _,
+    attribute::{IppAttribute, IppAttributeGroup},
+    model::{DelimiterTag, Operation},
+    registry::{self, AttributeInfo, Syntax},
+    request::IppRequestResponse,
+    value::IppValue,
+};
+
+/// Kind of a rule violation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// The message does not start with the operation attributes group
+    MissingOperationGroup,
+    /// A required operation attribute is missing
+    MissingAttribute,
+    /// An operation attribute which should be supplied is missing, a recommendation rather than an error
+    MissingRecommended,
+    /// A required operation attribute is not at its required position
+    OutOfOrder { expected: usize, actual: usize },
+    /// A single-valued attribute has multiple values
+    MultipleValues,
+    /// The value does not conform to the attribute syntax
+    InvalidSyntax(String),
+    /// A text or name value exceeds the maximum length of the attribute
+    TooLong { len: usize, max: usize },
+    /// A keyword does not conform to the keyword grammar
+    InvalidKeyword(String),
+    /// A `rangeOfInteger` value has min greater than max
+    InvalidRange { min: i32, max: i32 },
+}
+
+impl fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ViolationKind::MissingOperationGroup => write!(f, "operation attributes group must be the first group"),
+            ViolationKind::MissingAttribute => write!(f, "required attribute is missing"),
+            ViolationKind::MissingRecommended => write!(f, "recommended attribute is missing"),
+            ViolationKind::OutOfOrder { expected, actual } => {
+                write!(f, "attribute must be at position {expected}, found at {actual}")
+            }
+            ViolationKind::MultipleValues => write!(f, "single-valued attribute has multiple values"),
+            ViolationKind::InvalidSyntax(value) => write!(f, "value {value} does not conform to the attribute syntax"),
+            ViolationKind::TooLong { len, max } => write!(f, "value length {len} exceeds the maximum of {max}"),
+            ViolationKind::InvalidKeyword(keyword) => write!(f, "invalid keyword: {keyword}"),
+            ViolationKind::InvalidRange { min, max } => write!(f, "invalid range: {min} > {max}"),
+        }
+    }
+}
+
+/// Single rule violation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Group of the offending attribute, `None` for message-level violations
+    pub group: Option<DelimiterTag>,
+    /// Name of the offending attribute, empty for message-level violations
+    pub attribute: String,
+    /// Kind of the violation
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.attribute.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{}: {}", self.attribute, self.kind)
+        }
+    }
+}
+
+/// Validate a request or response, returning all violations found
+pub fn validate(message: &IppRequestResponse) -> Vec<Violation> {
+    validate_message(message, None)
+}
+
+/// Validate a request, including the operation attributes required by its operation
+pub fn validate_request(request: &IppRequestResponse) -> Vec<Violation> {
+    validate_message(request, Operation::from_i16(request.header().operation_or_status))
+}
+
+fn validate_message(message: &IppRequestResponse, operation: Option<Operation>) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    validate_operation_attributes(message, operation, &mut violations);
+
+    for group in message.attributes().groups() {
+        // unsupported attributes are echoed back as received
+        if group.tag() == DelimiterTag::UnsupportedAttributes {
+            continue;
+        }
+        for attr in group.attributes() {
+            validate_attribute(group.tag(), attr, &mut violations);
+        }
+    }
+
+    violations
+}
+
+/// Target of an operation
+#[derive(Copy, Clone)]
+enum Target {
+    /// `printer-uri`
+    Printer,
+    /// `job-uri`, or `printer-uri` and `job-id`
+    Job,
+}
+
+// operation attributes required in requests of RFC 8011 operations besides the charset, language and target
+fn required_attributes(operation: Operation) -> Option<(Target, &'static [&'static str])> {
+    let required = match operation {
+        Operation::PrintJob
+        | Operation::ValidateJob
+        | Operation::CreateJob
+        | Operation::GetPrinterAttributes
+        | Operation::GetJobs
+        | Operation::PausePrinter
+        | Operation::ResumePrinter
+        | Operation::PurgeJobs => (Target::Printer, &[][..]),
+        Operation::PrintUri => (Target::Printer, &[IppAttribute::DOCUMENT_URI][..]),
+        Operation::SendDocument => (Target::Job, &[IppAttribute::LAST_DOCUMENT][..]),
+        Operation::SendUri => (
+            Target::Job,
+            &[IppAttribute::DOCUMENT_URI, IppAttribute::LAST_DOCUMENT][..],
+        ),
+        Operation::CancelJob
+        | Operation::GetJobAttributes
+        | Operation::HoldJob
+        | Operation::ReleaseJob
+        | Operation::RestartJob => (Target::Job, &[][..]),
+        _ => return None,
+    };
+    Some(required)
+}
+
+fn validate_required_attributes(group: &IppAttributeGroup, operation: Operation, violations: &mut Vec<Violation>) {
+    let Some((target, required)) = required_attributes(operation) else {
+        return;
+    };
+    let has = |name| group.get(name).is_some();
+
+    // the target follows the charset and the natural language
+    let target: &[&str] = match target {
+        Target::Printer => &[IppAttribute::PRINTER_URI],
+        Target::Job if has(IppAttribute::JOB_URI) || !has(IppAttribute::PRINTER_URI) => &[IppAttribute::JOB_URI],
+        Target::Job => &[IppAttribute::PRINTER_URI, IppAttribute::JOB_ID],
+    };
+    for (index, name) in target.iter().enumerate() {
+        validate_position(group, index + 2, name, violations);
+    }
+
+    for name in required.iter().filter(|name| !has(name)) {
+        violations.push(Violation {
+            group: Some(DelimiterTag::OperationAttributes),
+            attribute: (*name).to_owned(),
+            kind: ViolationKind::MissingAttribute,
+        });
+    }
+
+    if !has(IppAttribute::REQUESTING_USER_NAME) {
+        violations.push(Violation {
+            group: Some(DelimiterTag::OperationAttributes),
+            attribute: IppAttribute::REQUESTING_USER_NAME.to_owned(),
+            kind: ViolationKind::MissingRecommended,
+        });
+    }
+}
+
+// check that an operation attribute is present at the expected position
+fn validate_position(group: &IppAttributeGroup, expected: usize, name: &str, violations: &mut Vec<Violation>) {
+    let kind = match group.attributes().iter().position(|a| a.name().as_str() == name) {
+        Some(actual) if actual == expected => return,
+        Some(actual) => ViolationKind::OutOfOrder { expected, actual },
+        None => ViolationKind::MissingAttribute,
+    };
+    violations.push(Violation {
+        group: Some(DelimiterTag::OperationAttributes),
+        attribute: name.to_owned(),
+        kind,
+    });
+}
+
+fn validate_operation_attributes(
+    message: &IppRequestResponse,
+    operation: Option<Operation>,
+    violations: &mut Vec<Violation>,
+) {
+    let Some(group) = message
+        .attributes()
+        .groups()
+        .first()
+        .filter(|g| g.tag() == DelimiterTag::OperationAttributes)
+    else {
+        violations.push(Violation {
+            group: None,
+            attribute: String::new(),
+            kind: ViolationKind::MissingOperationGroup,
+        });
+        return;
+    };
+
+    for (expected, name) in [
+        IppAttribute::ATTRIBUTES_CHARSET,
+        IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        validate_position(group, expected, name, violations);
+    }
+
+    if let Some(operation) = operation {
+        validate_required_attributes(group, operation, violations);
+    }
+}
+
+fn validate_attribute(tag: DelimiterTag, attr: &IppAttribute, violations: &mut Vec<Violation>) {
+    let mut report = |kind| {
+        violations.push(Violation {
+            group: Some(tag),
+            attribute: attr.name().to_string(),
+            kind,
+        })
+    };
+
+    let info = registry::lookup(attr.name());
+
+    if let Some(info) = info
+        && !info.set_of
+        && attr.values().len() > 1
+    {
+        report(ViolationKind::MultipleValues);
+    }
+
+    for value in attr.values() {
+        validate_value(value, info, &mut report);
+    }
+}
+
+fn is_out_of_band(value: &IppValue) -> bool {
+    match value {
+        IppValue::NoValue | IppValue::NotSettable | IppValue::DeleteAttribute | IppValue::AdminDefine => true,
+        IppValue::Other { tag, .. } => (0x10..=0x1f).contains(tag),
+        _ => false,
+    }
+}
+
+// maximum length of a text or name value according to the attribute syntax
+fn max_length(value: &IppValue, info: &AttributeInfo) -> Option<(usize, usize)> {
+    let (len, is_text) = match value {
+        IppValue::TextWithoutLanguage(text) | IppValue::TextWithLanguage { text, .. } => (text.len(), true),
+        IppValue::NameWithoutLanguage(name) | IppValue::NameWithLanguage { name, .. } => (name.len(), false),
+        _ => return None,
+    };
+    info.syntax
+        .iter()
+        .find_map(|syntax| match syntax {
+            Syntax::Text(max) if is_text => Some(*max as usize),
+            Syntax::Name(max) if !is_text => Some(*max as usize),
+            _ => None,
+        })
+        .map(|max| (len, max))
+}
+
+fn validate_value(value: &IppValue, info: Option<&AttributeInfo>, report: &mut impl FnMut(ViolationKind)) {
+    match value {
+        IppValue::Keyword(keyword)
+            if !registry::is_keyword(keyword) && !info.is_some_and(|info| info.is_registered_keyword(keyword)) =>
+        {
+            report(ViolationKind::InvalidKeyword(keyword.to_string()));
+            return;
+        }
+        IppValue::RangeOfInteger { min, max } if min > max => {
+            report(ViolationKind::InvalidRange { min: *min, max: *max });
+            return;
+        }
+        IppValue::Collection(collection) => {
+            for (name, value) in collection.iter() {
+                let member = info.and_then(|info| info.member(name));
+                let members = match value {
+                    IppValue::Array(values) => values.as_slice(),
+                    value => std::slice::from_ref(value),
+                };
+                for value in members {
+                    validate_value(value, member, report);
+                }
+            }
+        }
+        _ => {}
+    }
+
+    let Some(info) = info else { return };
+    if is_out_of_band(value) {
+        return;
+    }
+
+    if let Some((len, max)) = max_length(value, info)
+        && len > max
+    {
+        report(ViolationKind::TooLong { len, max });
+    } else if !info.syntax.iter().any(|syntax| syntax.matches(value)) {
+        report(ViolationKind::InvalidSyntax(value.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::{IppVersion, Operation, StatusCode},
+        value::IppName,
+    };
+
+    fn attribute(name: &str, value: IppValue) -> IppAttribute {
+        IppAttribute::with_name(name, value).unwrap()
+    }
+
+    fn kinds(message: &IppRequestResponse) -> Vec<(String, ViolationKind)> {
+        validate(message).into_iter().map(|v| (v.attribute, v.kind)).collect()
+    }
+
+    #[test]
+    fn test_valid_request() {
+        let mut request = IppRequestResponse::new(
+            IppVersion::v1_1(),
+            Operation::PrintJob,
+            Some("ipp://localhost/printers/test".parse().unwrap()),
+        )
+        .unwrap();
+        request.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            attribute("job-name", IppValue::new_name_without_language("report").unwrap()),
+        );
+        request
+            .attributes_mut()
+            .add(DelimiterTag::JobAttributes, attribute("copies", IppValue::Integer(2)));
+        request.attributes_mut().add(
+            DelimiterTag::JobAttributes,
+            attribute("page-ranges", IppValue::new_range_of_integer(1, 5)),
+        );
+        assert_eq!(validate(&request), vec![]);
+    }
+
+    #[test]
+    fn test_operation_attributes() {
+        let mut response = IppRequestResponse::new_response(IppVersion::v1_1(), StatusCode::SuccessfulOk, 1).unwrap();
+        let group = response.attributes_mut().groups_mut().first_mut().unwrap();
+        group.attributes_mut().swap(0, 1);
+        group
+            .attributes_mut()
+            .retain(|a| a.name().as_str() != IppAttribute::ATTRIBUTES_CHARSET);
+        assert_eq!(
+            kinds(&response),
+            vec![
+                (
+                    IppAttribute::ATTRIBUTES_CHARSET.to_owned(),
+                    ViolationKind::MissingAttribute
+                ),
+                (
+                    IppAttribute::ATTRIBUTES_NATURAL_LANGUAGE.to_owned(),
+                    ViolationKind::OutOfOrder { expected: 1, actual: 0 }
+                ),
+            ]
+        );
+
+        response.attributes_mut().groups_mut().clear();
+        assert_eq!(
+            kinds(&response),
+            vec![(String::new(), ViolationKind::MissingOperationGroup)]
+        );
+    }
+
+    #[test]
+    fn test_keyword_with_leading_digit() {
+        let mut response = IppRequestResponse::new_response(IppVersion::v1_1(), StatusCode::SuccessfulOk, 1).unwrap();
+        response.attributes_mut().add(
+            DelimiterTag::PrinterAttributes,
+            attribute(
+                "ipp-versions-supported",
+                IppValue::Array(vec![
+                    IppValue::new_keyword("1.1").unwrap(),
+                    IppValue::new_keyword("2.0").unwrap(),
+                ]),
+            ),
+        );
+        assert_eq!(validate(&response), vec![]);
+        assert!(!registry::is_keyword("-1"));
+
+        // a leading digit is accepted only for registered values
+        response.attributes_mut().add(
+            DelimiterTag::PrinterAttributes,
+            attribute("sides-default", IppValue::new_keyword("2sided").unwrap()),
+        );
+        assert_eq!(
+            kinds(&response),
+            vec![(
+                "sides-default".to_owned(),
+                ViolationKind::InvalidKeyword("2sided".to_owned())
+            )]
+        );
+    }
+
+    #[test]
+    fn test_value_violations() {
+        let mut response = IppRequestResponse::new_response(IppVersion::v1_1(), StatusCode::SuccessfulOk, 1).unwrap();
+        let attributes = [
+            // name(127)
+            attribute(
+                "printer-name",
+                IppValue::new_name_without_language("x".repeat(128)).unwrap(),
+            ),
+            attribute(
+                "copies",
+                IppValue::Array(vec![IppValue::Integer(1), IppValue::Integer(2)]),
+            ),
+            attribute("copies-default", IppValue::Integer(0)),
+            attribute("sides", IppValue::new_keyword("Two-Sided").unwrap()),
+            attribute("page-ranges", IppValue::new_range_of_integer(5, 1)),
+            attribute("printer-state", IppValue::new_keyword("idle").unwrap()),
+            attribute("printer-info", IppValue::NoValue),
+        ];
+        for attr in attributes {
+            response.attributes_mut().add(DelimiterTag::PrinterAttributes, attr);
+        }
+
+        let mut collection = crate::value::IppCollection::new();
+        collection.push(IppName::new("media-key").unwrap(), IppValue::new_keyword("A4").unwrap());
+        response.attributes_mut().add(
+            DelimiterTag::PrinterAttributes,
+            attribute("media-col-default", IppValue::Collection(collection)),
+        );
+
+        assert_eq!(
+            kinds(&response),
+            vec![
+                ("printer-name".to_owned(), ViolationKind::TooLong { len: 128, max: 127 }),
+                ("copies".to_owned(), ViolationKind::MultipleValues),
+                (
+                    "copies-default".to_owned(),
+                    ViolationKind::InvalidSyntax("0".to_owned())
+                ),
+                (
+                    "sides".to_owned(),
+                    ViolationKind::InvalidKeyword("Two-Sided".to_owned())
+                ),
+                ("page-ranges".to_owned(), ViolationKind::InvalidRange { min: 5, max: 1 }),
+                (
+                    "printer-state".to_owned(),
+                    ViolationKind::InvalidSyntax("idle".to_owned())
+                ),
+                (
+                    "media-col-default".to_owned(),
+                    ViolationKind::InvalidKeyword("A4".to_owned())
+                ),
+            ]
+        );
+    }
+
+    fn request(operation: Operation, uri: Option<&str>, attributes: Vec<IppAttribute>) -> IppRequestResponse {
+        let mut request =
+            IppRequestResponse::new(IppVersion::v1_1(), operation, uri.map(|uri| uri.parse().unwrap())).unwrap();
+        for attr in attributes {
+            request.attributes_mut().add(DelimiterTag::OperationAttributes, attr);
+        }
+        request
+    }
+
+    fn missing(request: &IppRequestResponse) -> Vec<String> {
+        validate_request(request)
+            .into_iter()
+            .map(|v| v.attribute)
+            .collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn test_printer_operation_attributes() {
+        let user = attribute(
+            "requesting-user-name",
+            IppValue::new_name_without_language("user").unwrap(),
+        );
+        let uri = Some("ipp://localhost/printers/test");
+
+        assert!(missing(&request(Operation::GetPrinterAttributes, uri, vec![user.clone()])).is_empty());
+        assert_eq!(
+            missing(&request(Operation::PrintJob, None, vec![user.clone()])),
+            vec!["printer-uri"]
+        );
+        // requesting-user-name is a recommendation
+        assert_eq!(
+            validate_request(&request(Operation::GetJobs, uri, vec![])),
+            vec![Violation {
+                group: Some(DelimiterTag::OperationAttributes),
+                attribute: "requesting-user-name".to_owned(),
+                kind: ViolationKind::MissingRecommended,
+            }]
+        );
+        // the target must follow the natural language
+        let printer_uri = attribute(
+            "printer-uri",
+            IppValue::new_uri("ipp://localhost/printers/test").unwrap(),
+        );
+        assert_eq!(
+            validate_request(&request(Operation::PrintJob, None, vec![user.clone(), printer_uri]))
+                .into_iter()
+                .map(|v| (v.attribute, v.kind))
+                .collect::<Vec<_>>(),
+            vec![(
+                "printer-uri".to_owned(),
+                ViolationKind::OutOfOrder { expected: 2, actual: 3 }
+            )]
+        );
+        assert_eq!(
+            missing(&request(Operation::PrintUri, uri, vec![user.clone()])),
+            vec!["document-uri"]
+        );
+        // the generic validation does not know about the operation
+        assert!(validate(&request(Operation::PrintJob, None, vec![])).is_empty());
+        // CUPS operations are not checked
+        assert!(missing(&request(Operation::CupsGetPrinters, None, vec![])).is_empty());
+    }
+
+    #[test]
+    fn test_job_operation_attributes() {
+        let user = attribute(
+            "requesting-user-name",
+            IppValue::new_name_without_language("user").unwrap(),
+        );
+        let job_id = attribute("job-id", IppValue::Integer(1));
+        let job_uri = attribute("job-uri", IppValue::new_uri("ipp://localhost/jobs/1").unwrap());
+        let printer = Some("ipp://localhost/printers/test");
+
+        assert!(
+            missing(&request(
+                Operation::CancelJob,
+                printer,
+                vec![job_id.clone(), user.clone()]
+            ))
+            .is_empty()
+        );
+        assert!(missing(&request(Operation::GetJobAttributes, None, vec![job_uri, user.clone()])).is_empty());
+        assert_eq!(
+            missing(&request(Operation::HoldJob, printer, vec![user.clone()])),
+            vec!["job-id"]
+        );
+        assert_eq!(
+            missing(&request(
+                Operation::ReleaseJob,
+                None,
+                vec![job_id.clone(), user.clone()]
+            )),
+            vec!["job-uri"]
+        );
+        assert_eq!(
+            missing(&request(
+                Operation::SendDocument,
+                printer,
+                vec![job_id.clone(), user.clone()]
+            )),
+            vec!["last-document"]
+        );
+        assert_eq!(
+            missing(&request(Operation::SendUri, printer, vec![job_id.clone()])),
+            vec!["document-uri", "last-document", "requesting-user-name"]
+        );
+        // job-id must follow printer-uri
+        assert_eq!(
+            validate_request(&request(Operation::CancelJob, printer, vec![user, job_id]))
+                .into_iter()
+                .map(|v| (v.attribute, v.kind))
+                .collect::<Vec<_>>(),
+            vec![(
+                "job-id".to_owned(),
+                ViolationKind::OutOfOrder { expected: 3, actual: 4 }
+            )]
+        );
+    }
+}
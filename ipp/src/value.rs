@@ -2,16 +2,24 @@
 //! IPP value
 //!
 #![allow(unused_assignments)]
-use std::{borrow::Cow, collections::BTreeMap, fmt, ops::Deref, str::FromStr};
-
-use bytes::{Buf, BufMut, Bytes, BytesMut};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut, TryGetError};
 use enum_as_inner::EnumAsInner;
 use http::Uri;
 use num_traits::ToPrimitive;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{FromPrimitive as _, model::ValueTag, parser::IppParseError};
+use crate::{FromPrimitive as _, charset::Charset, model::ValueTag, parser::IppParseError};
 
 const IPP_STRING_MAX_LENGTH: usize = 1023;
 
@@ -23,14 +31,18 @@ const IPP_STRING_MAX_LENGTH: usize = 1023;
 /// The length constraint is measured in UTF-8 encoded bytes,
 /// not Unicode scalar values.
 ///
+/// The string is held in a shared [`Bytes`] buffer: strings parsed by [`IppBytesParser`](crate::parser::IppBytesParser)
+/// are slices of the message buffer and cloning a string does not copy it.
+///
 /// # Type Parameter
 /// - `MAX`: Maximum allowed length in bytes.
 ///
 /// # Errors
 /// Returns [`IppParseError::InvalidStringLength`] if the input exceeds `MAX`.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct BoundedString<const MAX: usize = IPP_STRING_MAX_LENGTH> {
-    inner: String,
+    // always valid UTF-8
+    inner: Bytes,
 }
 
 /// IPP string value with a maximum length of 1023 bytes
@@ -61,21 +73,25 @@ impl<const MAX: usize> BoundedString<MAX> {
             return Err(IppParseError::InvalidStringLength { len, max: MAX });
         }
 
-        Ok(Self { inner: s })
+        Ok(Self { inner: s.into() })
     }
 
     /// Creates a bounded string by truncating the input if needed
     pub fn new_truncated(s: impl Into<String>) -> Self {
         let mut inner = s.into();
         inner.truncate(MAX);
-        Self { inner }
+        Self { inner: inner.into() }
     }
 
+    /// Create a bounded string from a copy of the data, `None` if it is too long or not valid UTF-8
     pub fn from_bytes(data: &[u8]) -> Option<Self> {
-        if data.len() <= MAX
-            && let Ok(s) = str::from_utf8(data)
-        {
-            Some(Self { inner: s.to_owned() })
+        Self::from_shared(Bytes::copy_from_slice(data))
+    }
+
+    /// Create a bounded string sharing the buffer without copying, `None` if it is too long or not valid UTF-8
+    pub fn from_shared(data: Bytes) -> Option<Self> {
+        if data.len() <= MAX && str::from_utf8(&data).is_ok() {
+            Some(Self { inner: data })
         } else {
             None
         }
@@ -88,12 +104,18 @@ impl<const MAX: usize> BoundedString<MAX> {
 
     /// Return the string as a `str` reference
     pub fn as_str(&self) -> &str {
-        &self.inner
+        // SAFETY: the buffer is validated as UTF-8 in all constructors
+        unsafe { str::from_utf8_unchecked(&self.inner) }
     }
 
-    /// Consume the bounded string and return the inner `String`
+    /// Consume the bounded string and return it as a `String`
     pub fn into_inner(self) -> String {
-        self.inner
+        self.as_str().to_owned()
+    }
+
+    /// Return the underlying buffer holding the UTF-8 encoded string
+    pub fn as_bytes_shared(&self) -> &Bytes {
+        &self.inner
     }
 
     /// Return the length of the string in bytes
@@ -129,19 +151,32 @@ impl<const MAX: usize> BoundedString<MAX> {
 
 impl<const MAX: usize> From<BoundedString<MAX>> for String {
     fn from(value: BoundedString<MAX>) -> Self {
-        value.inner
+        value.into_inner()
+    }
+}
+
+impl<const MAX: usize> fmt::Debug for BoundedString<MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundedString").field("inner", &self.as_str()).finish()
+    }
+}
+
+// consistent with `str` for lookups via `Borrow<str>`
+impl<const MAX: usize> Hash for BoundedString<MAX> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
     }
 }
 
 impl<const MAX: usize> std::borrow::Borrow<str> for BoundedString<MAX> {
     fn borrow(&self) -> &str {
-        &self.inner
+        self.as_str()
     }
 }
 
 impl<const MAX: usize> AsRef<str> for BoundedString<MAX> {
     fn as_ref(&self) -> &str {
-        &self.inner
+        self.as_str()
     }
 }
 
@@ -149,7 +184,7 @@ impl<const MAX: usize> Deref for BoundedString<MAX> {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
-        &self.inner
+        self.as_str()
     }
 }
 
@@ -183,7 +218,7 @@ impl<const MAX: usize> TryFrom<Cow<'_, str>> for BoundedString<MAX> {
 
 impl<const MAX: usize> fmt::Display for BoundedString<MAX> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.inner)
+        f.write_str(self.as_str())
     }
 }
 
@@ -200,7 +235,7 @@ impl<const N: usize> Serialize for BoundedString<N> {
     where
         S: Serializer,
     {
-        self.inner.serialize(serializer)
+        self.as_str().serialize(serializer)
     }
 }
 
@@ -217,7 +252,7 @@ impl<'de, const N: usize> Deserialize<'de> for BoundedString<N> {
                 &format!("no more than {N} bytes").as_str(),
             ))
         } else {
-            Ok(Self { inner })
+            Ok(Self { inner: inner.into() })
         }
     }
 }
@@ -342,19 +377,29 @@ impl fmt::Display for IppTextValue {
 }
 
 #[inline]
-fn get_len_string(data: &mut Bytes) -> Result<String, IppParseError> {
+fn get_len_string(data: &mut Bytes) -> Result<IppString, IppParseError> {
     let len = data.try_get_u16()? as usize;
-    if let Ok(s) = str::from_utf8(&data[0..len]) {
-        let res = s.to_owned();
-        data.advance(len);
-        Ok(res)
-    } else {
-        Err(IppParseError::UnsupportedCharset)
+    if data.len() < len {
+        return Err(TryGetError {
+            requested: len,
+            available: data.len(),
+        }
+        .into());
+    }
+    if len > IPP_STRING_MAX_LENGTH {
+        return Err(IppParseError::InvalidStringLength {
+            len,
+            max: IPP_STRING_MAX_LENGTH,
+        });
     }
+    BoundedString::from_shared(data.split_to(len)).ok_or(IppParseError::UnsupportedCharset)
 }
 
-/// Represents IPP datetime value
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Represents IPP datetime value as defined in [RFC 2579](https://tools.ietf.org/html/rfc2579)
+///
+/// Values are ordered by the absolute instant they denote, so that `10:00+02:00` precedes `09:00+00:00`.
+/// Equality is structural: use [`IppDateTime::is_same_instant`] to compare instants.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IppDateTime {
     pub year: u16,
@@ -369,7 +414,191 @@ pub struct IppDateTime {
     pub utc_mins: u8,
 }
 
+// days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let (era, yoe) = (year.div_euclid(400), year.rem_euclid(400));
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+// proleptic Gregorian date of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let (era, doe) = (days.div_euclid(146_097), days.rem_euclid(146_097));
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month as u8, day as u8)
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
 impl IppDateTime {
+    /// Create a validated UTC datetime
+    pub fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minutes: u8,
+        seconds: u8,
+        deci_seconds: u8,
+    ) -> Result<Self, IppParseError> {
+        let datetime = IppDateTime {
+            year,
+            month,
+            day,
+            hour,
+            minutes,
+            seconds,
+            deci_seconds,
+            utc_dir: '+',
+            utc_hours: 0,
+            utc_mins: 0,
+        };
+        datetime.validate()?;
+        Ok(datetime)
+    }
+
+    /// Set the offset from UTC in minutes, keeping the local date and time
+    pub fn with_utc_offset(mut self, minutes: i32) -> Result<Self, IppParseError> {
+        let abs = minutes.unsigned_abs();
+        self.utc_dir = if minutes < 0 { '-' } else { '+' };
+        self.utc_hours = u8::try_from(abs / 60).map_err(|_| IppParseError::InvalidDateTime)?;
+        self.utc_mins = (abs % 60) as u8;
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Return the offset from UTC in minutes
+    pub fn utc_offset(&self) -> i32 {
+        let minutes = i32::from(self.utc_hours) * 60 + i32::from(self.utc_mins);
+        if self.utc_dir == '-' { -minutes } else { minutes }
+    }
+
+    /// Check that all fields are within the ranges allowed by RFC 2579
+    pub fn validate(&self) -> Result<(), IppParseError> {
+        let valid = (1..=12).contains(&self.month)
+            && (1..=days_in_month(self.year, self.month)).contains(&self.day)
+            && self.hour < 24
+            && self.minutes < 60
+            // leap second
+            && self.seconds <= 60
+            && self.deci_seconds < 10
+            && matches!(self.utc_dir, '+' | '-')
+            && self.utc_hours <= 13
+            && self.utc_mins < 60;
+        if valid {
+            Ok(())
+        } else {
+            Err(IppParseError::InvalidDateTime)
+        }
+    }
+
+    // deciseconds since the unix epoch, computed leniently for invalid values
+    fn timestamp_deciseconds(&self) -> i64 {
+        let days = days_from_civil(i64::from(self.year), i64::from(self.month), i64::from(self.day));
+        let seconds =
+            days * 86400 + i64::from(self.hour) * 3600 + i64::from(self.minutes) * 60 + i64::from(self.seconds)
+                - i64::from(self.utc_offset()) * 60;
+        seconds * 10 + i64::from(self.deci_seconds)
+    }
+
+    /// Check if both values denote the same instant regardless of their UTC offsets
+    pub fn is_same_instant(&self, other: &IppDateTime) -> bool {
+        self.timestamp_deciseconds() == other.timestamp_deciseconds()
+    }
+
+    /// Format as an [RFC 3339](https://tools.ietf.org/html/rfc3339) timestamp, e.g. `2024-05-01T10:30:00.5+02:00`
+    pub fn to_rfc3339(&self) -> String {
+        let mut s = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minutes, self.seconds
+        );
+        if self.deci_seconds != 0 {
+            s.push_str(&format!(".{}", self.deci_seconds));
+        }
+        if self.utc_dir == '+' && self.utc_offset() == 0 {
+            s.push('Z');
+        } else {
+            s.push_str(&format!("{}{:02}:{:02}", self.utc_dir, self.utc_hours, self.utc_mins));
+        }
+        s
+    }
+
+    /// Parse an [RFC 3339](https://tools.ietf.org/html/rfc3339) timestamp.
+    /// Fractional seconds are truncated to deciseconds.
+    pub fn parse_rfc3339(s: &str) -> Result<Self, IppParseError> {
+        fn number<T: FromStr>(s: &str, range: std::ops::Range<usize>, sep: Option<u8>) -> Option<T> {
+            if let Some(sep) = sep
+                && s.as_bytes().get(range.end) != Some(&sep)
+            {
+                return None;
+            }
+            let digits = s.get(range)?;
+            if digits.bytes().all(|b| b.is_ascii_digit()) {
+                digits.parse().ok()
+            } else {
+                None
+            }
+        }
+
+        let parse = || -> Option<IppDateTime> {
+            if !matches!(s.as_bytes().get(10)?, b'T' | b't' | b' ') {
+                return None;
+            }
+            let mut datetime = IppDateTime {
+                year: number(s, 0..4, Some(b'-'))?,
+                month: number(s, 5..7, Some(b'-'))?,
+                day: number(s, 8..10, None)?,
+                hour: number(s, 11..13, Some(b':'))?,
+                minutes: number(s, 14..16, Some(b':'))?,
+                seconds: number(s, 17..19, None)?,
+                deci_seconds: 0,
+                utc_dir: '+',
+                utc_hours: 0,
+                utc_mins: 0,
+            };
+            let mut rest = &s[19..];
+            if let Some(fraction) = rest.strip_prefix('.') {
+                let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+                datetime.deci_seconds = number(fraction, 0..1, None)?;
+                rest = &fraction[len..];
+            }
+            match rest {
+                "Z" | "z" => {}
+                offset => {
+                    datetime.utc_dir = match offset.as_bytes().first()? {
+                        b'+' => '+',
+                        b'-' => '-',
+                        _ => return None,
+                    };
+                    if offset.len() != 6 {
+                        return None;
+                    }
+                    datetime.utc_hours = number(offset, 1..3, Some(b':'))?;
+                    datetime.utc_mins = number(offset, 4..6, None)?;
+                }
+            }
+            Some(datetime)
+        };
+
+        let datetime = parse().ok_or(IppParseError::InvalidDateTime)?;
+        datetime.validate()?;
+        Ok(datetime)
+    }
+
     pub fn to_bytes(&self) -> Bytes {
         let mut bytes = BytesMut::new();
         bytes.put_u16(self.year);
@@ -407,6 +636,73 @@ impl fmt::Display for IppDateTime {
     }
 }
 
+impl PartialOrd for IppDateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IppDateTime {
+    /// Compare by absolute instant; values denoting the same instant with different offsets
+    /// are ordered by their fields to stay consistent with `Eq`
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.timestamp_deciseconds()
+            .cmp(&other.timestamp_deciseconds())
+            .then_with(|| self.to_bytes().cmp(&other.to_bytes()))
+    }
+}
+
+impl FromStr for IppDateTime {
+    type Err = IppParseError;
+
+    /// Parse an RFC 3339 timestamp
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IppDateTime::parse_rfc3339(s)
+    }
+}
+
+/// Convert into a UTC datetime. Times outside of the years 0 to 65535 are clamped.
+impl From<SystemTime> for IppDateTime {
+    fn from(value: SystemTime) -> Self {
+        let deciseconds = match value.duration_since(UNIX_EPOCH) {
+            Ok(duration) => (duration.as_millis() / 100) as i64,
+            Err(e) => -(e.duration().as_millis().div_ceil(100) as i64),
+        };
+        let (days, deciseconds) = (deciseconds.div_euclid(864_000), deciseconds.rem_euclid(864_000));
+        let (year, month, day) = civil_from_days(days);
+        let seconds = deciseconds / 10;
+
+        IppDateTime {
+            year: year.clamp(0, u16::MAX as i64) as u16,
+            month,
+            day,
+            hour: (seconds / 3600) as u8,
+            minutes: (seconds % 3600 / 60) as u8,
+            seconds: (seconds % 60) as u8,
+            deci_seconds: (deciseconds % 10) as u8,
+            utc_dir: '+',
+            utc_hours: 0,
+            utc_mins: 0,
+        }
+    }
+}
+
+impl TryFrom<IppDateTime> for SystemTime {
+    type Error = crate::error::IppError;
+
+    fn try_from(value: IppDateTime) -> Result<Self, Self::Error> {
+        value.validate()?;
+        let deciseconds = value.timestamp_deciseconds();
+        let duration = Duration::from_millis(deciseconds.unsigned_abs() * 100);
+        let time = if deciseconds < 0 {
+            UNIX_EPOCH.checked_sub(duration)
+        } else {
+            UNIX_EPOCH.checked_add(duration)
+        };
+        Ok(time.ok_or(IppParseError::InvalidDateTime)?)
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for IppDateTime {
     fn from(value: chrono::DateTime<Tz>) -> Self {
@@ -523,6 +819,206 @@ impl TryFrom<IppDateTime> for jiff::Zoned {
     }
 }
 
+/// IPP resolution value
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct IppResolution {
+    /// Cross feed direction resolution
+    pub cross_feed: i32,
+    /// Feed direction resolution
+    pub feed: i32,
+    /// Units: 3 for dots per inch, 4 for dots per centimeter
+    pub units: i8,
+}
+
+impl From<IppResolution> for IppValue {
+    fn from(value: IppResolution) -> Self {
+        IppValue::new_resolution(value.cross_feed, value.feed, value.units)
+    }
+}
+
+type CollectionEntry = (IppName, IppValue);
+
+/// Ordered IPP collection.
+///
+/// Members are kept in wire order and duplicate member names are preserved, so that a parsed collection
+/// is serialized back to the same bytes. Lookups by name return the first matching member.
+/// Use `From<BTreeMap<IppName, IppValue>>` and `From<IppCollection> for BTreeMap` to migrate from
+/// the map-based representation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IppCollection(Vec<CollectionEntry>);
+
+impl IppCollection {
+    /// Create an empty collection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the number of members
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return true if the collection has no members
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Return the value of the first member with a given name
+    pub fn get(&self, name: &str) -> Option<&IppValue> {
+        self.0.iter().find(|(k, _)| k.as_ref() == name).map(|(_, v)| v)
+    }
+
+    /// Return the mutable value of the first member with a given name
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut IppValue> {
+        self.0.iter_mut().find(|(k, _)| k.as_ref() == name).map(|(_, v)| v)
+    }
+
+    /// Return the values of all members with a given name
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a IppValue> + 'a {
+        self.0.iter().filter(move |(k, _)| k.as_ref() == name).map(|(_, v)| v)
+    }
+
+    /// Return true if the collection contains a member with a given name
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Set the value of the first member with a given name, keeping its position,
+    /// or append a new member. Returns the previous value.
+    pub fn insert(&mut self, name: IppName, value: IppValue) -> Option<IppValue> {
+        match self.get_mut(&name) {
+            Some(old) => Some(std::mem::replace(old, value)),
+            None => {
+                self.0.push((name, value));
+                None
+            }
+        }
+    }
+
+    /// Append a member, even if a member with the same name already exists
+    pub fn push(&mut self, name: IppName, value: IppValue) {
+        self.0.push((name, value));
+    }
+
+    /// Remove the first member with a given name and return its value
+    pub fn remove(&mut self, name: &str) -> Option<IppValue> {
+        let index = self.0.iter().position(|(k, _)| k.as_ref() == name)?;
+        Some(self.0.remove(index).1)
+    }
+
+    /// Iterate over the members in order
+    pub fn iter(&self) -> impl Iterator<Item = (&IppName, &IppValue)> {
+        self.0.iter().map(|(k, v)| (k, v))
+    }
+
+    /// Iterate over the member names in order
+    pub fn keys(&self) -> impl Iterator<Item = &IppName> {
+        self.0.iter().map(|(k, _)| k)
+    }
+
+    /// Iterate over the member values in order
+    pub fn values(&self) -> impl Iterator<Item = &IppValue> {
+        self.0.iter().map(|(_, v)| v)
+    }
+}
+
+impl FromIterator<CollectionEntry> for IppCollection {
+    fn from_iter<T: IntoIterator<Item = CollectionEntry>>(iter: T) -> Self {
+        IppCollection(iter.into_iter().collect())
+    }
+}
+
+impl Extend<CollectionEntry> for IppCollection {
+    fn extend<T: IntoIterator<Item = CollectionEntry>>(&mut self, iter: T) {
+        self.0.extend(iter)
+    }
+}
+
+impl IntoIterator for IppCollection {
+    type Item = CollectionEntry;
+    type IntoIter = std::vec::IntoIter<CollectionEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a IppCollection {
+    type Item = &'a CollectionEntry;
+    type IntoIter = std::slice::Iter<'a, CollectionEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<const N: usize> From<[CollectionEntry; N]> for IppCollection {
+    fn from(entries: [CollectionEntry; N]) -> Self {
+        IppCollection(entries.into())
+    }
+}
+
+impl From<Vec<CollectionEntry>> for IppCollection {
+    fn from(entries: Vec<CollectionEntry>) -> Self {
+        IppCollection(entries)
+    }
+}
+
+impl From<BTreeMap<IppName, IppValue>> for IppCollection {
+    fn from(map: BTreeMap<IppName, IppValue>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl From<IppCollection> for BTreeMap<IppName, IppValue> {
+    /// Convert to a map; for duplicate member names the last value wins
+    fn from(collection: IppCollection) -> Self {
+        collection.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for IppCollection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for IppCollection {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct CollectionVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for CollectionVisitor {
+            type Value = IppCollection;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an IPP collection")
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut collection = IppCollection::new();
+                while let Some((name, value)) = access.next_entry()? {
+                    collection.push(name, value);
+                }
+                Ok(collection)
+            }
+        }
+
+        deserializer.deserialize_map(CollectionVisitor)
+    }
+}
+
 /// IPP attribute values as defined in [RFC 8010](https://tools.ietf.org/html/rfc8010)
 /// the length for TextWithoutLanguage, TextWithLanguage, and OctetString values is heavily attribute dependent
 /// usual values are 127, 255, and 1023 however as these are attribute dependent, a [`IppTextValue`] is used to allow the calling routine to assert expected text length.
@@ -553,7 +1049,7 @@ pub enum IppValue {
     Boolean(bool),
     Keyword(IppKeyword),
     Array(Vec<IppValue>),
-    Collection(BTreeMap<IppName, IppValue>),
+    Collection(IppCollection),
     MimeMediaType(IppMimeMediaType),
     DateTime(IppDateTime),
     MemberAttrName(IppKeyword),
@@ -563,6 +1059,17 @@ pub enum IppValue {
         units: i8,
     },
     NoValue,
+    /// Out-of-band value: the attribute cannot be set
+    NotSettable,
+    /// Out-of-band value: the attribute must be deleted, used in Set-*-Attributes operations
+    DeleteAttribute,
+    /// Out-of-band value: the attribute value is defined by the administrator
+    AdminDefine,
+    /// Value with an extended four-octet tag
+    Extension {
+        tag: u32,
+        data: Bytes,
+    },
     Other {
         tag: u8,
         data: Bytes,
@@ -572,6 +1079,12 @@ pub enum IppValue {
         tag: ValueTag,
         data: Bytes,
     },
+    /// Outgoing text or name value encoded in a non-utf-8 charset, see [`IppValue::encode_charset`]
+    Encoded {
+        tag: ValueTag,
+        charset: Charset,
+        data: Bytes,
+    },
 }
 
 impl IppValue {
@@ -642,7 +1155,7 @@ impl IppValue {
     }
 
     pub fn new_collection() -> Self {
-        Self::Collection(BTreeMap::new())
+        Self::Collection(IppCollection::new())
     }
 
     pub fn new_mime_media_type(value: impl Into<String>) -> Result<Self, IppParseError> {
@@ -669,6 +1182,22 @@ impl IppValue {
         Self::NoValue
     }
 
+    pub fn new_not_settable() -> Self {
+        Self::NotSettable
+    }
+
+    pub fn new_delete_attribute() -> Self {
+        Self::DeleteAttribute
+    }
+
+    pub fn new_admin_define() -> Self {
+        Self::AdminDefine
+    }
+
+    pub fn new_extension(tag: u32, data: Bytes) -> Self {
+        Self::Extension { tag, data }
+    }
+
     pub fn new_other(tag: u8, data: Bytes) -> Self {
         Self::Other { tag, data }
     }
@@ -702,7 +1231,11 @@ impl IppValue {
             IppValue::Resolution { .. } => ValueTag::Resolution as u8,
             IppValue::Other { tag, .. } => tag,
             IppValue::NoValue => ValueTag::NoValue as u8,
-            IppValue::NonUtf8 { tag, .. } => tag as u8,
+            IppValue::NotSettable => ValueTag::NotSettable as u8,
+            IppValue::DeleteAttribute => ValueTag::DeleteAttribute as u8,
+            IppValue::AdminDefine => ValueTag::AdminDefine as u8,
+            IppValue::Extension { .. } => ValueTag::Extension as u8,
+            IppValue::NonUtf8 { tag, .. } | IppValue::Encoded { tag, .. } => tag as u8,
         }
     }
 
@@ -720,7 +1253,7 @@ impl IppValue {
                 max: MAX,
             })
         } else {
-            Ok(BoundedString::<MAX>::from_bytes(&data).map_or_else(|| IppValue::NonUtf8 { tag, data }, into))
+            Ok(BoundedString::<MAX>::from_shared(data.clone()).map_or_else(|| IppValue::NonUtf8 { tag, data }, into))
         }
     }
 
@@ -734,15 +1267,130 @@ impl IppValue {
                 max: IPP_STRING_MAX_LENGTH,
             })
         } else {
-            Ok(BoundedString::from_bytes(&data)
+            Ok(BoundedString::from_shared(data.clone())
                 .map(IppTextValue::Long)
                 .map(IppTextValue::shrink)
                 .map_or_else(|| IppValue::NonUtf8 { tag, data }, into))
         }
     }
 
+    // decode a text or name value in a non-utf-8 charset, None if the value is not a text or name
+    // or cannot be decoded in the given charset. The length limits apply to the encoded octets on the wire,
+    // a value which is too long only after the conversion into utf-8 is kept as NonUtf8.
+    fn decode_text(tag: ValueTag, data: &Bytes, charset: Charset) -> Result<Option<Self>, IppParseError> {
+        let decode_with_language = || -> Result<Option<(String, String)>, IppParseError> {
+            let mut data = data.clone();
+            let language = get_len_string(&mut data)?.into_inner();
+            let len = data.try_get_u16()? as usize;
+            Ok(data
+                .get(..len)
+                .and_then(|text| charset.decode(text).ok())
+                .map(|text| (language, text)))
+        };
+
+        let value =
+            match tag {
+                ValueTag::TextWithoutLanguage => charset
+                    .decode(data)
+                    .ok()
+                    .map(|text| IppValue::new_text_without_language(text).ok()),
+                ValueTag::NameWithoutLanguage => charset
+                    .decode(data)
+                    .ok()
+                    .map(|name| IppValue::new_name_without_language(name).ok()),
+                ValueTag::TextWithLanguage => decode_with_language()?
+                    .map(|(language, text)| IppValue::new_text_with_language(language, text).ok()),
+                ValueTag::NameWithLanguage => decode_with_language()?
+                    .map(|(language, name)| IppValue::new_name_with_language(language, name).ok()),
+                _ => None,
+            };
+        let max = match tag {
+            ValueTag::NameWithoutLanguage => IppName::max(),
+            _ => IPP_STRING_MAX_LENGTH,
+        };
+        match value {
+            // decoded, but longer than the limit in utf-8
+            Some(None) if data.len() <= max => Ok(Some(IppValue::NonUtf8 {
+                tag,
+                data: data.clone(),
+            })),
+            Some(value) => Ok(value),
+            None => Ok(None),
+        }
+    }
+
+    /// Encode text and name values in a given charset.
+    /// Encoded values are returned as [`IppValue::Encoded`] holding the raw bytes,
+    /// arrays and collections are encoded recursively.
+    pub fn encode_charset(&self, charset: Charset) -> Result<IppValue, IppParseError> {
+        let with_language = |tag, language: &IppLanguage, text: &str| -> Result<IppValue, IppParseError> {
+            let text = charset.encode(text)?;
+            let mut data = BytesMut::new();
+            data.put_u16(language.len() as u16);
+            data.put_slice(language.as_bytes());
+            data.put_u16(text.len() as u16);
+            data.put_slice(&text);
+            Ok(IppValue::Encoded {
+                tag,
+                charset,
+                data: data.freeze(),
+            })
+        };
+
+        let value = match self {
+            _ if charset == Charset::Utf8 => self.clone(),
+            IppValue::TextWithoutLanguage(text) => IppValue::Encoded {
+                tag: ValueTag::TextWithoutLanguage,
+                charset,
+                data: charset.encode(text.as_ref())?.into(),
+            },
+            IppValue::NameWithoutLanguage(name) => IppValue::Encoded {
+                tag: ValueTag::NameWithoutLanguage,
+                charset,
+                data: charset.encode(name)?.into(),
+            },
+            IppValue::TextWithLanguage { language, text } => {
+                with_language(ValueTag::TextWithLanguage, language, text.as_ref())?
+            }
+            IppValue::NameWithLanguage { language, name } => with_language(ValueTag::NameWithLanguage, language, name)?,
+            IppValue::Array(items) => IppValue::Array(
+                items
+                    .iter()
+                    .map(|item| item.encode_charset(charset))
+                    .collect::<Result<_, _>>()?,
+            ),
+            IppValue::Collection(collection) => IppValue::Collection(
+                collection
+                    .iter()
+                    .map(|(name, value)| Ok((name.clone(), value.encode_charset(charset)?)))
+                    .collect::<Result<_, IppParseError>>()?,
+            ),
+            other => other.clone(),
+        };
+        Ok(value)
+    }
+
+    /// Return the utf-8 value of an [`IppValue::Encoded`] value, other values are returned unchanged
+    pub fn decode_charset(&self) -> IppValue {
+        match self {
+            IppValue::Encoded { tag, charset, data } => Self::parse_with_charset(*tag as u8, data.clone(), *charset)
+                .unwrap_or_else(|_| IppValue::NonUtf8 {
+                    tag: *tag,
+                    data: data.clone(),
+                }),
+            other => other.clone(),
+        }
+    }
+
     /// Parse value from a byte array which does not include the value length field
-    pub fn parse(value_tag: u8, mut data: Bytes) -> Result<IppValue, IppParseError> {
+    pub fn parse(value_tag: u8, data: Bytes) -> Result<IppValue, IppParseError> {
+        Self::parse_with_charset(value_tag, data, Charset::Utf8)
+    }
+
+    /// Parse value from a byte array which does not include the value length field,
+    /// decoding text and name values in a given charset.
+    /// Values which cannot be decoded in the charset are parsed as utf-8.
+    pub fn parse_with_charset(value_tag: u8, mut data: Bytes, charset: Charset) -> Result<IppValue, IppParseError> {
         let ipp_tag = match ValueTag::from_u8(value_tag) {
             Some(x) => x,
             None => {
@@ -750,6 +1398,12 @@ impl IppValue {
             }
         };
 
+        if charset != Charset::Utf8
+            && let Some(value) = Self::decode_text(ipp_tag, &data, charset)?
+        {
+            return Ok(value);
+        }
+
         let value = match ipp_tag {
             ValueTag::Integer => IppValue::Integer(data.try_get_i32()?),
             ValueTag::Enum => IppValue::Enum(data.try_get_i32()?),
@@ -757,12 +1411,12 @@ impl IppValue {
             ValueTag::TextWithoutLanguage => Self::from_utf8(IppValue::TextWithoutLanguage, ipp_tag, data)?,
             ValueTag::NameWithoutLanguage => Self::from_bounded_utf8(IppValue::NameWithoutLanguage, ipp_tag, data)?,
             ValueTag::TextWithLanguage => IppValue::TextWithLanguage {
-                language: get_len_string(&mut data)?.try_into()?,
-                text: get_len_string(&mut data)?.try_into()?,
+                language: get_len_string(&mut data)?.shrink()?,
+                text: IppTextValue::Long(get_len_string(&mut data)?).shrink(),
             },
             ValueTag::NameWithLanguage => IppValue::NameWithLanguage {
-                language: get_len_string(&mut data)?.try_into()?,
-                name: get_len_string(&mut data)?.try_into()?,
+                language: get_len_string(&mut data)?.shrink()?,
+                name: get_len_string(&mut data)?.shrink()?,
             },
             ValueTag::Charset => Self::from_bounded_utf8(IppValue::Charset, ipp_tag, data)?,
             ValueTag::NaturalLanguage => Self::from_bounded_utf8(IppValue::NaturalLanguage, ipp_tag, data)?,
@@ -794,6 +1448,13 @@ impl IppValue {
                 units: data.try_get_i8()?,
             },
             ValueTag::NoValue => IppValue::NoValue,
+            ValueTag::NotSettable => IppValue::NotSettable,
+            ValueTag::DeleteAttribute => IppValue::DeleteAttribute,
+            ValueTag::AdminDefine => IppValue::AdminDefine,
+            ValueTag::Extension => IppValue::Extension {
+                tag: data.try_get_u32()?,
+                data,
+            },
             _ => IppValue::Other { tag: value_tag, data },
         };
         Ok(value)
@@ -858,11 +1519,12 @@ impl IppValue {
             }
             IppValue::Array(ref list) => {
                 for (i, item) in list.iter().enumerate() {
-                    buffer.put(item.to_bytes());
-                    if i < list.len() - 1 {
-                        buffer.put_u8(self.to_tag());
+                    if i > 0 {
+                        // additional value: own tag and empty name
+                        buffer.put_u8(item.to_tag());
                         buffer.put_u16(0);
                     }
+                    buffer.put(item.to_bytes());
                 }
             }
             IppValue::Collection(ref list) => {
@@ -903,8 +1565,17 @@ impl IppValue {
                 buffer.put_i32(feed);
                 buffer.put_u8(units as u8);
             }
-            IppValue::NoValue => buffer.put_u16(0),
-            IppValue::Other { ref data, .. } | IppValue::NonUtf8 { ref data, .. } => {
+            IppValue::NoValue | IppValue::NotSettable | IppValue::DeleteAttribute | IppValue::AdminDefine => {
+                buffer.put_u16(0)
+            }
+            IppValue::Extension { tag, ref data } => {
+                buffer.put_u16((data.len() + 4) as u16);
+                buffer.put_u32(tag);
+                buffer.put_slice(data);
+            }
+            IppValue::Other { ref data, .. }
+            | IppValue::NonUtf8 { ref data, .. }
+            | IppValue::Encoded { ref data, .. } => {
                 buffer.put_u16(data.len() as u16);
                 buffer.put_slice(data);
             }
@@ -953,9 +1624,14 @@ impl fmt::Display for IppValue {
             }
 
             IppValue::NoValue => Ok(()),
+            IppValue::NotSettable => write!(f, "not-settable"),
+            IppValue::DeleteAttribute => write!(f, "delete-attribute"),
+            IppValue::AdminDefine => write!(f, "admin-define"),
+            IppValue::Extension { tag, ref data } => write!(f, "{tag:0x}: {data:?}"),
             IppValue::OctetString(ref data) => write!(f, "{:0x}: {data:?}", ValueTag::OctetStringUnspecified as u8),
             IppValue::Other { tag, ref data } => write!(f, "{tag:0x}: {data:?}"),
             IppValue::NonUtf8 { tag, ref data } => write!(f, "{:0x}: {}", tag as u8, data.escape_ascii()),
+            IppValue::Encoded { .. } => self.decode_charset().fmt(f),
         }
     }
 }
@@ -963,19 +1639,9 @@ impl fmt::Display for IppValue {
 impl FromStr for IppValue {
     type Err = IppParseError;
 
+    /// Parse a value using the textual notation described in [`crate::notation`]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value = match s {
-            "true" => IppValue::Boolean(true),
-            "false" => IppValue::Boolean(false),
-            other => {
-                if let Ok(iv) = other.parse::<i32>() {
-                    IppValue::Integer(iv)
-                } else {
-                    IppValue::Keyword(other.try_into()?)
-                }
-            }
-        };
-        Ok(value)
+        crate::notation::parse_value(s)
     }
 }
 
@@ -1007,10 +1673,10 @@ impl<'a> Iterator for IppValueIterator<'a> {
                     None
                 }
             }
-            IppValue::Collection(map) => {
-                if let Some(entry) = map.iter().nth(self.index) {
+            IppValue::Collection(coll) => {
+                if let Some(value) = coll.values().nth(self.index) {
                     self.index += 1;
-                    Some(entry.1)
+                    Some(value)
                 } else {
                     None
                 }
@@ -1029,11 +1695,86 @@ impl<'a> Iterator for IppValueIterator<'a> {
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::BTreeMap, io};
+    use std::io;
 
     use super::*;
     use crate::{attribute::IppAttribute, model::DelimiterTag, parser::IppParser, reader::IppReader};
 
+    #[test]
+    fn test_datetime_validation() {
+        let datetime = IppDateTime::new(2024, 2, 29, 23, 59, 60, 9).unwrap();
+        assert_eq!(datetime.utc_offset(), 0);
+        let datetime = datetime.with_utc_offset(-330).unwrap();
+        assert_eq!((datetime.utc_dir, datetime.utc_hours, datetime.utc_mins), ('-', 5, 30));
+        assert_eq!(datetime.utc_offset(), -330);
+
+        assert!(IppDateTime::new(2023, 2, 29, 0, 0, 0, 0).is_err());
+        assert!(IppDateTime::new(2024, 13, 1, 0, 0, 0, 0).is_err());
+        assert!(IppDateTime::new(2024, 1, 1, 24, 0, 0, 0).is_err());
+        assert!(IppDateTime::new(2024, 1, 1, 0, 0, 0, 10).is_err());
+        assert!(datetime.clone().with_utc_offset(14 * 60).is_err());
+        assert!(datetime.with_utc_offset(-13 * 60).is_ok());
+    }
+
+    #[test]
+    fn test_datetime_rfc3339() {
+        let datetime = IppDateTime::parse_rfc3339("2024-05-01T10:30:00.56+02:00").unwrap();
+        assert_eq!(
+            datetime,
+            IppDateTime::new(2024, 5, 1, 10, 30, 0, 5)
+                .unwrap()
+                .with_utc_offset(120)
+                .unwrap()
+        );
+        assert_eq!(datetime.to_rfc3339(), "2024-05-01T10:30:00.5+02:00");
+
+        let datetime: IppDateTime = "1999-12-31t23:59:59z".parse().unwrap();
+        assert_eq!(datetime.to_rfc3339(), "1999-12-31T23:59:59Z");
+
+        for invalid in [
+            "2024-05-01",
+            "2024-05-01T10:30:00",
+            "2024-05-01T10:30:00+2:00",
+            "2024-02-30T10:30:00Z",
+            "2024-05-01T10:30:00.Z",
+            "2024/05/01T10:30:00Z",
+            "+024-05-01T10:30:00Z",
+        ] {
+            assert!(IppDateTime::parse_rfc3339(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_datetime_ordering() {
+        let earlier: IppDateTime = "2024-05-01T10:00:00+02:00".parse().unwrap();
+        let later: IppDateTime = "2024-05-01T09:00:00Z".parse().unwrap();
+        let same: IppDateTime = "2024-05-01T08:00:00Z".parse().unwrap();
+        assert!(earlier < later);
+        assert!(earlier.is_same_instant(&same));
+        assert_ne!(earlier, same);
+        assert_ne!(earlier.cmp(&same), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn test_datetime_system_time() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_714_559_400_500);
+        let datetime = IppDateTime::from(time);
+        assert_eq!(datetime.to_rfc3339(), "2024-05-01T10:30:00.5Z");
+        assert_eq!(SystemTime::try_from(datetime).unwrap(), time);
+
+        let datetime: IppDateTime = "2024-05-01T12:30:00.5+02:00".parse().unwrap();
+        assert_eq!(SystemTime::try_from(datetime).unwrap(), time);
+
+        let before_epoch = UNIX_EPOCH - Duration::from_millis(100);
+        let datetime = IppDateTime::from(before_epoch);
+        assert_eq!(datetime.to_rfc3339(), "1969-12-31T23:59:59.9Z");
+        assert_eq!(SystemTime::try_from(datetime).unwrap(), before_epoch);
+
+        let mut invalid = IppDateTime::from(time);
+        invalid.month = 0;
+        assert!(SystemTime::try_from(invalid).is_err());
+    }
+
     #[cfg(feature = "chrono")]
     #[test]
     fn chrono_datetime_roundtrip() {
@@ -1233,6 +1974,13 @@ mod tests {
             units: 2,
         });
         value_check(IppValue::NoValue);
+        value_check(IppValue::NotSettable);
+        value_check(IppValue::DeleteAttribute);
+        value_check(IppValue::AdminDefine);
+        value_check(IppValue::Extension {
+            tag: 0x4000_0001,
+            data: "ext".into(),
+        });
         value_check(IppValue::Other {
             tag: 123,
             data: "foo".into(),
@@ -1298,7 +2046,7 @@ mod tests {
     fn test_collection() {
         let attr = IppAttribute::new(
             "coll".try_into().unwrap(),
-            IppValue::Collection(BTreeMap::from([(
+            IppValue::Collection(IppCollection::from([(
                 "abcd".try_into().unwrap(),
                 IppValue::Integer(0x2222_2222),
             )])),
@@ -1329,13 +2077,45 @@ mod tests {
         let attr = group.get("coll").unwrap();
         assert_eq!(
             attr.value(),
-            &IppValue::Collection(BTreeMap::from([(
+            &IppValue::Collection(IppCollection::from([(
                 "abcd".try_into().unwrap(),
                 IppValue::Integer(0x2222_2222)
             )]))
         );
     }
 
+    #[test]
+    fn test_extension_value() {
+        let value = IppValue::new_extension(0x1234_5678, Bytes::from_static(b"data"));
+        assert_eq!(value.to_tag(), 0x7f);
+        assert_eq!(
+            value.to_bytes(),
+            vec![0, 8, 0x12, 0x34, 0x56, 0x78, b'd', b'a', b't', b'a']
+        );
+        assert!(matches!(
+            IppValue::parse(0x7f, Bytes::from_static(&[0, 1])),
+            Err(IppParseError::InvalidValueLength(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_out_of_band_attributes() {
+        let data = vec![
+            1, 1, 0, 0, 0, 0, 0, 0, 4, 0x16, 0, 1, b'a', 0, 0, 0x7f, 0, 1, b'b', 0, 5, 0, 0, 0, 1, b'x', 3,
+        ];
+
+        let res = IppParser::new(IppReader::new(io::Cursor::new(data))).parse().unwrap();
+        let group = res.attributes().first_of(DelimiterTag::PrinterAttributes).unwrap();
+        assert_eq!(group.get("a").unwrap().value(), &IppValue::DeleteAttribute);
+        assert_eq!(
+            group.get("b").unwrap().value(),
+            &IppValue::Extension {
+                tag: 1,
+                data: "x".into()
+            }
+        );
+    }
+
     #[test]
     fn test_truncated_bounded_string() {
         let value = IppLanguage::new_truncated("language".repeat(100));
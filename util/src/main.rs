@@ -12,7 +12,7 @@ use std::{
 };
 
 use clap::Parser;
-use ipp::{prelude::*, util};
+use ipp::{prelude::*, registry, util};
 
 fn new_client(uri: Uri, params: &IppParams) -> io::Result<IppClient> {
     let mut builder = IppClient::builder(uri).ignore_tls_errors(params.ignore_tls_errors);
@@ -44,7 +44,7 @@ fn new_payload(cmd: &IppPrintCmd) -> io::Result<IppPayload> {
 fn dump_attributes(response: &IppRequestResponse, tag: DelimiterTag) {
     for group in response.attributes().groups_of(tag) {
         for v in group.attributes() {
-            println!("{}: {}", v.name(), v.value());
+            println!("{v}");
         }
         println!();
     }
@@ -73,7 +73,11 @@ fn do_print_job(params: &IppParams, cmd: IppPrintCmd) -> Result<(), IppError> {
 
     for arg in cmd.options {
         if let Some((k, v)) = arg.split_once('=') {
-            builder = builder.attribute(IppAttribute::new(k.try_into().unwrap(), v.parse().unwrap()));
+            let value = match registry::lookup(k) {
+                Some(info) => info.parse_value(v)?,
+                None => v.parse()?,
+            };
+            builder = builder.attribute(IppAttribute::new(k.try_into()?, value));
         }
     }
 
@@ -280,7 +284,11 @@ struct IppPrintCmd {
     )]
     user_name: Option<String>,
 
-    #[clap(long = "option", short = 'o', help = "Extra IPP job attributes in key=value format")]
+    #[clap(
+        long = "option",
+        short = 'o',
+        help = "Extra IPP job attributes in key=value format, values use the ipptool-style notation"
+    )]
     options: Vec<String>,
 }
 